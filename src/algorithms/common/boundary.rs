@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::rng::UniformF64BoundedRandomGenerator;
+use crate::core::problem::Bounds;
+
+/// Penalty coefficient used by `penalty` when no coefficient is given.
+pub const DEFAULT_PENALTY_COEFFICIENT: f64 = 1000f64;
+
+/// How a move that leaves the problem bounds is handled. Every coordinate outside the bounds
+/// is handled separately, coordinates inside the bounds are never changed.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BoundaryHandling {
+    /// Moves the coordinate onto the violated bound.
+    Clamp,
+
+    /// Mirrors the coordinate back into the bounds at the violated bound.
+    Reflect,
+
+    /// Wraps the coordinate around to the opposite bound (toroidal search space).
+    Wrap,
+
+    /// Replaces the coordinate with a uniformly random value inside the bounds.
+    RandomReinitialization,
+
+    /// Moves the coordinate halfway between its value before the move and the violated bound.
+    MidpointToParent,
+
+    /// Keeps the point outside the bounds, but adds `coefficient` times the squared
+    /// distance from the bounds to its objective value when comparing it (see `penalty`).
+    Penalty { coefficient: f64 },
+
+    /// Discards the entire move (the point stays where it was).
+    Discard,
+}
+
+/// Parses `clamp`, `reflect`, `wrap`, `random`, `midpoint`, `penalty` (or `penalty=<coefficient>`)
+/// and `discard`.
+impl FromStr for BoundaryHandling {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, parameter) = match value.split_once('=') {
+            Some((name, parameter)) => (name.trim(), Some(parameter.trim())),
+            None => (value.trim(), None),
+        };
+
+        match (name, parameter) {
+            ("clamp", None) => Ok(Self::Clamp),
+            ("reflect", None) => Ok(Self::Reflect),
+            ("wrap", None) => Ok(Self::Wrap),
+            ("random", None) => Ok(Self::RandomReinitialization),
+            ("midpoint", None) => Ok(Self::MidpointToParent),
+            ("discard", None) => Ok(Self::Discard),
+            ("penalty", None) => Ok(Self::Penalty {
+                coefficient: DEFAULT_PENALTY_COEFFICIENT,
+            }),
+            ("penalty", Some(parameter)) => {
+                let coefficient = parameter.parse::<f64>().map_err(|_| {
+                    format!("Invalid penalty coefficient \"{parameter}\": expected a number.")
+                })?;
+                if coefficient.is_nan() || coefficient < 0f64 {
+                    return Err(format!(
+                        "Invalid penalty coefficient \"{parameter}\": expected a non-negative number."
+                    ));
+                }
+
+                Ok(Self::Penalty { coefficient })
+            }
+            _ => Err(format!(
+                "Unknown boundary handling \"{value}\" \
+                (expected clamp, reflect, wrap, random, midpoint, penalty[=<coefficient>] or discard)."
+            )),
+        }
+    }
+}
+
+impl BoundaryHandling {
+    /// Brings a single coordinate `value` of `dimension`, moved from `parent_value`,
+    /// back into `bounds`. Returns `None` if the move must be discarded.
+    ///
+    /// `generator` is only sampled by `RandomReinitialization` (any bounds will do).
+    pub fn repair(
+        &self,
+        value: f64,
+        parent_value: f64,
+        dimension: usize,
+        bounds: &Bounds,
+        generator: &mut UniformF64BoundedRandomGenerator,
+    ) -> Option<f64> {
+        let lower_bound = bounds.lower_bound(dimension);
+        let upper_bound = bounds.upper_bound(dimension);
+
+        if (lower_bound..=upper_bound).contains(&value) {
+            return Some(value);
+        }
+
+        let width = upper_bound - lower_bound;
+        let violated_bound = if value < lower_bound {
+            lower_bound
+        } else {
+            upper_bound
+        };
+
+        match self {
+            Self::Clamp => Some(value.clamp(lower_bound, upper_bound)),
+            Self::Reflect => {
+                let offset = (value - lower_bound).rem_euclid(2f64 * width);
+                if offset > width {
+                    Some(lower_bound + 2f64 * width - offset)
+                } else {
+                    Some(lower_bound + offset)
+                }
+            }
+            Self::Wrap => {
+                Some(lower_bound + (value - lower_bound).rem_euclid(width))
+            }
+            Self::RandomReinitialization => {
+                Some(lower_bound + width * generator.sample_0_to_1())
+            }
+            Self::MidpointToParent => {
+                Some((violated_bound + parent_value) / 2f64)
+            }
+            Self::Penalty { .. } => Some(value),
+            Self::Discard => None,
+        }
+    }
+
+    /// Brings `position`, moved from `parent`, back into `bounds` (see `repair`).
+    /// Returns `false` if the move must be discarded (`position` is then left as it is).
+    pub fn repair_position(
+        &self,
+        position: &mut [f64],
+        parent: &[f64],
+        bounds: &Bounds,
+        generator: &mut UniformF64BoundedRandomGenerator,
+    ) -> bool {
+        if matches!(self, Self::Discard) {
+            return position
+                .iter()
+                .enumerate()
+                .all(|(dimension, value)| bounds.contains(dimension, *value));
+        }
+
+        for (dimension, (value, parent_value)) in
+            position.iter_mut().zip(parent.iter()).enumerate()
+        {
+            if let Some(repaired_value) = self.repair(
+                *value,
+                *parent_value,
+                dimension,
+                bounds,
+                generator,
+            ) {
+                *value = repaired_value;
+            }
+        }
+
+        true
+    }
+
+    /// Penalty of `position` for leaving the `bounds` when using `Penalty`
+    /// (`0` inside the bounds and with all other strategies, which never leave them).
+    ///
+    /// The penalty is only added to the objective value when comparing points,
+    /// the reported best value is always the objective value itself.
+    pub fn penalty(&self, bounds: &Bounds, position: &[f64]) -> f64 {
+        match self {
+            Self::Penalty { coefficient } => {
+                let squared_distance = position
+                    .iter()
+                    .enumerate()
+                    .map(|(dimension, value)| {
+                        let distance = (bounds.lower_bound(dimension) - value)
+                            .max(value - bounds.upper_bound(dimension))
+                            .max(0f64);
+                        distance * distance
+                    })
+                    .sum::<f64>();
+
+                coefficient * squared_distance
+            }
+            _ => 0f64,
+        }
+    }
+}
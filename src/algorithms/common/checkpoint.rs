@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::observer::OptimizationObserver;
+use crate::algorithms::common::trajectory::TrajectoryRecorderState;
+
+/// Default minimum amount of seconds between two checkpoints.
+pub const DEFAULT_CHECKPOINT_INTERVAL_SECONDS: u64 = 60;
+
+/// Contents of a checkpoint file.
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint<C, S> {
+    /// State of the command, e.g. which problem is being optimized and the results so far.
+    pub context: C,
+
+    /// Wall time spent on the current problem up to the checkpoint.
+    pub problem_elapsed_seconds: f64,
+
+    /// State of the trajectory recorder, if trajectories are being recorded.
+    pub trajectory: Option<TrajectoryRecorderState>,
+
+    /// Full state of the optimizer (everything needed to continue bit-exactly).
+    pub state: S,
+}
+
+/// Periodically saves the full state of an optimization to a checkpoint file.
+/// `C` is the state of the command (see `Checkpoint::context`).
+pub struct CheckpointRecorder<C> {
+    path: PathBuf,
+
+    interval: Duration,
+    last_saved_at: Instant,
+
+    context: Option<C>,
+    problem_started_at: Instant,
+    problem_elapsed_seconds_before_start: f64,
+}
+
+impl<C: Serialize> CheckpointRecorder<C> {
+    pub fn new(path: &Path, interval: Duration) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            interval,
+            last_saved_at: Instant::now(),
+            context: None,
+            problem_started_at: Instant::now(),
+            problem_elapsed_seconds_before_start: 0f64,
+        }
+    }
+
+    /// Marks the start of a new problem (or a new repetition of the same problem).
+    /// `context` is saved in all following checkpoints, `elapsed_seconds` is the wall time
+    /// already spent on the problem (non-zero when resuming).
+    pub fn start_problem(&mut self, context: C, elapsed_seconds: f64) {
+        self.context = Some(context);
+        self.problem_started_at = Instant::now();
+        self.problem_elapsed_seconds_before_start = elapsed_seconds;
+    }
+
+    /// Whether at least the checkpoint interval has passed since the last checkpoint.
+    pub fn is_due(&self) -> bool {
+        self.last_saved_at.elapsed() >= self.interval
+    }
+
+    /// Saves a checkpoint with the optimizer `state`, replacing the previous one.
+    /// The `observer` is notified first, so e.g. the trajectory file contents match the checkpoint.
+    pub fn save<S: Serialize>(
+        &mut self,
+        state: &S,
+        observer: &mut dyn OptimizationObserver,
+    ) -> Result<()> {
+        let context = self.context.as_ref().ok_or_else(|| {
+            miette!("BUG: Checkpoint saved before the start of a problem.")
+        })?;
+
+        let trajectory = observer.on_checkpoint()?;
+
+        let checkpoint = Checkpoint {
+            context,
+            problem_elapsed_seconds: self.problem_elapsed_seconds_before_start
+                + self.problem_started_at.elapsed().as_secs_f64(),
+            trajectory,
+            state,
+        };
+
+        let contents = serde_json::to_string(&checkpoint).into_diagnostic()?;
+
+        if let Some(parent_directory) = self.path.parent() {
+            if !parent_directory.as_os_str().is_empty() {
+                fs::create_dir_all(parent_directory).into_diagnostic()?;
+            }
+        }
+
+        // Write into a temporary file first, so a crash while writing
+        // doesn't corrupt the previous checkpoint.
+        let temporary_path = self.path.with_extension("tmp");
+        fs::write(&temporary_path, contents)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                miette!("Could not write {}.", temporary_path.display())
+            })?;
+        fs::rename(&temporary_path, &self.path)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                miette!(
+                    "Could not write checkpoint {}.",
+                    self.path.display()
+                )
+            })?;
+
+        self.last_saved_at = Instant::now();
+
+        Ok(())
+    }
+
+    /// Removes the checkpoint file (after the optimization has finished).
+    pub fn remove(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)
+                .into_diagnostic()
+                .wrap_err_with(|| {
+                    miette!("Could not remove {}.", self.path.display())
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a checkpoint (as saved by `CheckpointRecorder`) from `path`.
+pub fn read_checkpoint<C: DeserializeOwned, S: DeserializeOwned>(
+    path: &Path,
+) -> Result<Checkpoint<C, S>> {
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| miette!("Could not read {}.", path.display()))?;
+
+    serde_json::from_str(&contents)
+        .into_diagnostic()
+        .wrap_err_with(|| miette!("Invalid checkpoint {}.", path.display()))
+}
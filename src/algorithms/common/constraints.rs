@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Penalty coefficient used by `static-penalty` when no coefficient is given.
+pub const DEFAULT_CONSTRAINT_PENALTY_COEFFICIENT: f64 = 10000f64;
+
+/// Starting penalty coefficient used by `adaptive-penalty` when none is given.
+pub const DEFAULT_ADAPTIVE_PENALTY_COEFFICIENT: f64 = 100f64;
+
+/// Amount of consecutive iterations with the best point on the same side of the feasibility
+/// boundary after which the adaptive penalty coefficient is changed.
+const ADAPTIVE_PENALTY_WINDOW: usize = 10;
+
+/// Factor the adaptive penalty coefficient is divided by while the best point is feasible.
+const ADAPTIVE_PENALTY_DECREASE_FACTOR: f64 = 1.5;
+
+/// Factor the adaptive penalty coefficient is multiplied by while the best point is infeasible.
+const ADAPTIVE_PENALTY_INCREASE_FACTOR: f64 = 2f64;
+
+/// Bounds of the adaptive penalty coefficient (so it can't vanish or overflow on long runs).
+const ADAPTIVE_PENALTY_COEFFICIENT_RANGE: (f64, f64) = (1e-6, 1e12);
+
+/// Factor the epsilon level is multiplied by after each iteration when no decay is given.
+pub const DEFAULT_EPSILON_DECAY: f64 = 0.99;
+
+/// Objective value of a point along with its constraint violation
+/// (the sum of the positive constraint values, `0` if the point is feasible).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConstrainedValue {
+    pub objective_value: f64,
+    pub constraint_violation: f64,
+}
+
+impl ConstrainedValue {
+    #[inline]
+    pub fn new(objective_value: f64, constraint_violation: f64) -> Self {
+        Self {
+            objective_value,
+            constraint_violation,
+        }
+    }
+
+    #[inline]
+    pub fn is_feasible(&self) -> bool {
+        self.constraint_violation <= 0f64
+    }
+
+    /// Whether this value is better than `other` by Deb's feasibility rules: feasible points
+    /// beat infeasible ones, feasible points are compared by their objective values
+    /// and infeasible ones by their constraint violations.
+    ///
+    /// This is how the best solution of a run is chosen, regardless of the `ConstraintHandling`
+    /// used during the search (on unconstrained problems, it's a plain comparison of the values).
+    #[inline]
+    pub fn is_better_than(&self, other: &ConstrainedValue) -> bool {
+        match (self.is_feasible(), other.is_feasible()) {
+            (true, true) => self.objective_value < other.objective_value,
+            (true, false) => true,
+            (false, true) => false,
+            (false, false) => {
+                self.constraint_violation < other.constraint_violation
+            }
+        }
+    }
+}
+
+/// How points violating the constraints of a constrained problem are compared during the search.
+/// On unconstrained problems, all of them simply compare the objective values.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConstraintHandling {
+    /// Adds `coefficient` times the constraint violation to the objective value.
+    StaticPenalty { coefficient: f64 },
+
+    /// Adds a coefficient times the constraint violation to the objective value. The coefficient
+    /// starts at `initial_coefficient` and is decreased while the best point of the last
+    /// iterations was feasible and increased while it was infeasible (Hadj-Alouane and Bean).
+    AdaptivePenalty { initial_coefficient: f64 },
+
+    /// Deb's feasibility rules (see `ConstrainedValue::is_better_than`).
+    #[default]
+    FeasibilityRules,
+
+    /// Epsilon-constraint method (Takahama and Sakai): points violating the constraints
+    /// by at most the epsilon level are compared as if they were feasible.
+    /// The level starts at `epsilon` and is multiplied by `decay` after each iteration.
+    EpsilonConstraint { epsilon: f64, decay: f64 },
+}
+
+fn parse_non_negative(name: &str, value: &str) -> Result<f64, String> {
+    let number = value.trim().parse::<f64>().map_err(|_| {
+        format!("Invalid {name} \"{value}\": expected a number.")
+    })?;
+    if number.is_nan() || number < 0f64 {
+        return Err(format!(
+            "Invalid {name} \"{value}\": expected a non-negative number."
+        ));
+    }
+
+    Ok(number)
+}
+
+/// Parses `static-penalty` (or `static-penalty=<coefficient>`), `adaptive-penalty`
+/// (or `adaptive-penalty=<initial coefficient>`), `deb` and `epsilon=<level>[,<decay>]`.
+impl FromStr for ConstraintHandling {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, parameter) = match value.split_once('=') {
+            Some((name, parameter)) => (name.trim(), Some(parameter.trim())),
+            None => (value.trim(), None),
+        };
+
+        match (name, parameter) {
+            ("static-penalty", None) => Ok(Self::StaticPenalty {
+                coefficient: DEFAULT_CONSTRAINT_PENALTY_COEFFICIENT,
+            }),
+            ("static-penalty", Some(parameter)) => Ok(Self::StaticPenalty {
+                coefficient: parse_non_negative(
+                    "penalty coefficient",
+                    parameter,
+                )?,
+            }),
+            ("adaptive-penalty", None) => Ok(Self::AdaptivePenalty {
+                initial_coefficient: DEFAULT_ADAPTIVE_PENALTY_COEFFICIENT,
+            }),
+            ("adaptive-penalty", Some(parameter)) => {
+                let initial_coefficient =
+                    parse_non_negative("penalty coefficient", parameter)?;
+                if initial_coefficient == 0f64 {
+                    return Err(format!(
+                        "Invalid penalty coefficient \"{parameter}\": expected a positive number."
+                    ));
+                }
+
+                Ok(Self::AdaptivePenalty {
+                    initial_coefficient,
+                })
+            }
+            ("deb", None) => Ok(Self::FeasibilityRules),
+            ("epsilon", Some(parameter)) => {
+                let (epsilon, decay) = match parameter.split_once(',') {
+                    Some((epsilon, decay)) => (
+                        parse_non_negative("epsilon level", epsilon)?,
+                        parse_non_negative("epsilon decay", decay)?,
+                    ),
+                    None => (
+                        parse_non_negative("epsilon level", parameter)?,
+                        DEFAULT_EPSILON_DECAY,
+                    ),
+                };
+                if decay > 1f64 {
+                    return Err(format!(
+                        "Invalid epsilon decay in \"{parameter}\": expected a number from 0 to 1."
+                    ));
+                }
+
+                Ok(Self::EpsilonConstraint { epsilon, decay })
+            }
+            _ => Err(format!(
+                "Unknown constraint handling \"{value}\" (expected static-penalty[=<coefficient>], \
+                adaptive-penalty[=<coefficient>], deb or epsilon=<level>[,<decay>])."
+            )),
+        }
+    }
+}
+
+/// State of a `ConstraintHandling` during a single run (the adaptive penalty coefficient
+/// and the epsilon level change as the run progresses, see `finish_iteration`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConstraintHandler {
+    handling: ConstraintHandling,
+
+    /// Current penalty coefficient (penalty methods only).
+    penalty_coefficient: f64,
+
+    /// Current epsilon level (epsilon-constraint only).
+    epsilon: f64,
+
+    /// Consecutive iterations in which the best point was feasible (or infeasible, if negative).
+    feasibility_streak: i64,
+}
+
+impl ConstraintHandler {
+    pub fn new(handling: ConstraintHandling) -> Self {
+        let (penalty_coefficient, epsilon) = match handling {
+            ConstraintHandling::StaticPenalty { coefficient } => {
+                (coefficient, 0f64)
+            }
+            ConstraintHandling::AdaptivePenalty {
+                initial_coefficient,
+            } => (initial_coefficient, 0f64),
+            ConstraintHandling::FeasibilityRules => (0f64, 0f64),
+            ConstraintHandling::EpsilonConstraint { epsilon, .. } => {
+                (0f64, epsilon)
+            }
+        };
+
+        Self {
+            handling,
+            penalty_coefficient,
+            epsilon,
+            feasibility_streak: 0,
+        }
+    }
+
+    /// Level and key the point is ranked by: a lower level is always better,
+    /// points of the same level are compared by their keys (lower is better).
+    #[inline]
+    fn rank(&self, value: &ConstrainedValue) -> (u8, f64) {
+        match self.handling {
+            ConstraintHandling::StaticPenalty { .. }
+            | ConstraintHandling::AdaptivePenalty { .. } => (
+                0,
+                value.objective_value
+                    + self.penalty_coefficient * value.constraint_violation,
+            ),
+            ConstraintHandling::FeasibilityRules
+            | ConstraintHandling::EpsilonConstraint { .. } => {
+                if value.constraint_violation <= self.epsilon {
+                    (0, value.objective_value)
+                } else {
+                    (1, value.constraint_violation)
+                }
+            }
+        }
+    }
+
+    /// Orders `first` and `second` from the better (`Ordering::Less`) to the worse one.
+    #[inline]
+    pub fn compare(
+        &self,
+        first: &ConstrainedValue,
+        second: &ConstrainedValue,
+    ) -> Ordering {
+        let (first_level, first_key) = self.rank(first);
+        let (second_level, second_key) = self.rank(second);
+
+        first_level
+            .cmp(&second_level)
+            .then_with(|| first_key.total_cmp(&second_key))
+    }
+
+    /// Whether `first` is strictly better than `second`.
+    #[inline]
+    pub fn is_better(
+        &self,
+        first: &ConstrainedValue,
+        second: &ConstrainedValue,
+    ) -> bool {
+        self.compare(first, second) == Ordering::Less
+    }
+
+    /// How much worse `first` is than `second` (negative if it is better), e.g. for
+    /// the acceptance probability of simulated annealing. Points on different levels
+    /// (see `rank`) differ infinitely.
+    #[inline]
+    pub fn worsening(
+        &self,
+        first: &ConstrainedValue,
+        second: &ConstrainedValue,
+    ) -> f64 {
+        let (first_level, first_key) = self.rank(first);
+        let (second_level, second_key) = self.rank(second);
+
+        match first_level.cmp(&second_level) {
+            Ordering::Less => f64::NEG_INFINITY,
+            Ordering::Equal => first_key - second_key,
+            Ordering::Greater => f64::INFINITY,
+        }
+    }
+
+    /// Updates the adaptive penalty coefficient or the epsilon level
+    /// after an iteration whose best point was `best`.
+    pub fn finish_iteration(&mut self, best: &ConstrainedValue) {
+        match self.handling {
+            ConstraintHandling::AdaptivePenalty { .. } => {
+                self.feasibility_streak =
+                    match (best.is_feasible(), self.feasibility_streak) {
+                        (true, streak) if streak > 0 => streak + 1,
+                        (true, _) => 1,
+                        (false, streak) if streak < 0 => streak - 1,
+                        (false, _) => -1,
+                    };
+
+                let window = ADAPTIVE_PENALTY_WINDOW as i64;
+                if self.feasibility_streak >= window {
+                    self.penalty_coefficient /= ADAPTIVE_PENALTY_DECREASE_FACTOR;
+                } else if self.feasibility_streak <= -window {
+                    self.penalty_coefficient *= ADAPTIVE_PENALTY_INCREASE_FACTOR;
+                }
+
+                let (minimum, maximum) = ADAPTIVE_PENALTY_COEFFICIENT_RANGE;
+                self.penalty_coefficient =
+                    self.penalty_coefficient.clamp(minimum, maximum);
+            }
+            ConstraintHandling::EpsilonConstraint { decay, .. } => {
+                self.epsilon *= decay;
+            }
+            ConstraintHandling::StaticPenalty { .. }
+            | ConstraintHandling::FeasibilityRules => {}
+        }
+    }
+}
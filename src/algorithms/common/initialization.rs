@@ -0,0 +1,276 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use rand::distributions::{Distribution, Uniform};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::constraints::{
+    ConstraintHandler,
+    ConstraintHandling,
+};
+use crate::algorithms::common::rng::{
+    HaltonSequence,
+    SobolSequence,
+    UniformF64BoundedRandomGenerator,
+};
+use crate::algorithms::common::structs::State;
+use crate::core::problem::{BBOBProblem, Bounds};
+
+/// How the initial points of an optimization (e.g. the firefly swarm) are chosen inside the bounds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InitializationStrategy {
+    /// Independent uniformly random points.
+    #[default]
+    Uniform,
+
+    /// Latin hypercube sampling: each dimension is split into as many equal intervals as there
+    /// are points and every interval contains exactly one point.
+    LatinHypercube,
+
+    /// Randomly shifted Halton low-discrepancy sequence.
+    Halton,
+
+    /// Randomly shifted Sobol low-discrepancy sequence. Falls back to the Halton sequence
+    /// for problems with more than `SOBOL_MAXIMUM_DIMENSIONS` dimensions.
+    Sobol,
+
+    /// Opposition-based learning: evaluates uniformly random points and their opposites
+    /// (mirrored around the centre of the bounds) and keeps the better half.
+    Opposition,
+
+    /// Evaluates this many uniformly random candidates for each point and keeps the best one.
+    BestOfCandidates(usize),
+}
+
+/// Parses `uniform`, `latin-hypercube`, `halton`, `sobol`, `opposition` and `best-of-<K>`.
+impl FromStr for InitializationStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "uniform" => Ok(Self::Uniform),
+            "latin-hypercube" => Ok(Self::LatinHypercube),
+            "halton" => Ok(Self::Halton),
+            "sobol" => Ok(Self::Sobol),
+            "opposition" => Ok(Self::Opposition),
+            value => {
+                let candidates = value
+                    .strip_prefix("best-of-")
+                    .and_then(|candidates| candidates.parse::<usize>().ok())
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown initialization strategy \"{value}\" (expected uniform, \
+                            latin-hypercube, halton, sobol, opposition or best-of-<K>)."
+                        )
+                    })?;
+                if candidates == 0 {
+                    return Err(
+                        "Initialization strategy best-of-<K> needs at least one candidate."
+                            .to_string(),
+                    );
+                }
+
+                Ok(Self::BestOfCandidates(candidates))
+            }
+        }
+    }
+}
+
+/// Generates and evaluates `population_size` initial points of `problem` using `strategy`.
+/// The points are returned in the order they were generated in.
+///
+/// `InitializationStrategy::Uniform` samples and evaluates the points in exactly the same way
+/// as sampling them one by one from `UniformF64BoundedRandomGenerator::new(bounds, seed)`.
+/// All strategies honour the per-dimension bounds of the problem.
+pub fn initialize_population(
+    problem: &mut BBOBProblem,
+    strategy: InitializationStrategy,
+    population_size: usize,
+    seed: [u8; 16],
+) -> Vec<State> {
+    let points = generate_initial_points(
+        strategy,
+        problem.bounds(),
+        population_size,
+        seed,
+    );
+    let mut candidates = evaluate_all(problem, points);
+
+    match strategy {
+        InitializationStrategy::Opposition => {
+            candidates.sort_by(compare_states);
+            candidates.truncate(population_size);
+
+            candidates
+        }
+        InitializationStrategy::BestOfCandidates(candidate_count) => {
+            let mut candidates = candidates.into_iter();
+
+            (0..population_size)
+                .map(|_| {
+                    candidates
+                        .by_ref()
+                        .take(candidate_count)
+                        .min_by(compare_states)
+                        .expect(
+                            "BUG: best-of-K initialization without candidates.",
+                        )
+                })
+                .collect()
+        }
+        _ => candidates,
+    }
+}
+
+/// Generates the candidate points `strategy` chooses `population_size` initial points from
+/// (without evaluating them), for optimizers that select among candidates on their own terms.
+///
+/// Returns `population_size` points, except for `InitializationStrategy::Opposition`
+/// (each random point followed by its opposite) and `InitializationStrategy::BestOfCandidates`
+/// (the K candidates of each point one after another).
+pub fn generate_initial_points(
+    strategy: InitializationStrategy,
+    bounds: &Bounds,
+    population_size: usize,
+    seed: [u8; 16],
+) -> Vec<Vec<f64>> {
+    let input_dimensions = bounds.dimensions();
+
+    match strategy {
+        InitializationStrategy::Uniform => {
+            let mut generator =
+                UniformF64BoundedRandomGenerator::new(bounds.clone(), seed);
+
+            (0..population_size)
+                .map(|_| generator.sample_point())
+                .collect()
+        }
+        InitializationStrategy::LatinHypercube => {
+            let mut rng = Pcg64Mcg::from_seed(seed);
+            let offset_distribution = Uniform::new(0f64, 1f64);
+
+            let mut points: Vec<Vec<f64>> =
+                vec![Vec::with_capacity(input_dimensions); population_size];
+            for dimension in 0..input_dimensions {
+                let mut intervals: Vec<usize> = (0..population_size).collect();
+                intervals.shuffle(&mut rng);
+
+                for (point, interval) in points.iter_mut().zip(intervals) {
+                    let unit_value = (interval as f64
+                        + offset_distribution.sample(&mut rng))
+                        / population_size as f64;
+                    point.push(scale_to_bounds(unit_value, dimension, bounds));
+                }
+            }
+
+            points
+        }
+        InitializationStrategy::Halton => {
+            let mut sequence = HaltonSequence::new(input_dimensions, seed);
+
+            (0..population_size)
+                .map(|_| scale_point_to_bounds(sequence.next_point(), bounds))
+                .collect()
+        }
+        InitializationStrategy::Sobol => {
+            let Some(mut sequence) = SobolSequence::new(input_dimensions, seed)
+            else {
+                return generate_initial_points(
+                    InitializationStrategy::Halton,
+                    bounds,
+                    population_size,
+                    seed,
+                );
+            };
+
+            (0..population_size)
+                .map(|_| scale_point_to_bounds(sequence.next_point(), bounds))
+                .collect()
+        }
+        InitializationStrategy::Opposition => {
+            let mut generator =
+                UniformF64BoundedRandomGenerator::new(bounds.clone(), seed);
+
+            let mut points: Vec<Vec<f64>> =
+                Vec::with_capacity(population_size * 2);
+            for _ in 0..population_size {
+                let point = generator.sample_point();
+                let opposite_point = point
+                    .iter()
+                    .enumerate()
+                    .map(|(dimension, value)| {
+                        bounds.lower_bound(dimension)
+                            + bounds.upper_bound(dimension)
+                            - value
+                    })
+                    .collect();
+
+                points.push(point);
+                points.push(opposite_point);
+            }
+
+            points
+        }
+        InitializationStrategy::BestOfCandidates(candidates) => {
+            let mut generator =
+                UniformF64BoundedRandomGenerator::new(bounds.clone(), seed);
+
+            (0..population_size * candidates)
+                .map(|_| generator.sample_point())
+                .collect()
+        }
+    }
+}
+
+fn scale_point_to_bounds(unit_point: Vec<f64>, bounds: &Bounds) -> Vec<f64> {
+    unit_point
+        .into_iter()
+        .enumerate()
+        .map(|(dimension, unit_value)| {
+            scale_to_bounds(unit_value, dimension, bounds)
+        })
+        .collect()
+}
+
+#[inline]
+fn scale_to_bounds(unit_value: f64, dimension: usize, bounds: &Bounds) -> f64 {
+    bounds.lower_bound(dimension)
+        + (bounds.upper_bound(dimension) - bounds.lower_bound(dimension))
+            * unit_value
+}
+
+/// Orders evaluated points from the better to the worse one by Deb's feasibility rules
+/// (by their objective values on unconstrained problems).
+fn compare_states(first: &State, second: &State) -> Ordering {
+    ConstraintHandler::new(ConstraintHandling::FeasibilityRules).compare(
+        &first.constrained_value(),
+        &second.constrained_value(),
+    )
+}
+
+/// Evaluates all `points` at once (see `BBOBProblem::evaluate_batch`),
+/// after rounding their integer variables on mixed-integer problems.
+fn evaluate_all(
+    problem: &mut BBOBProblem,
+    mut points: Vec<Vec<f64>>,
+) -> Vec<State> {
+    for point in points.iter_mut() {
+        problem.round_integer_variables(point);
+    }
+
+    let objective_values = problem.evaluate_batch(&points);
+
+    points
+        .into_iter()
+        .zip(objective_values)
+        .map(|(vector, objective_value)| State {
+            constraint_violation: problem.constraint_violation(&vector),
+            vector,
+            objective_value,
+            boundary_penalty: 0f64,
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use miette::{miette, Context, IntoDiagnostic, Result};
+
+/// Exit code of a process terminated by SIGINT (`128 + 2`).
+const FORCE_QUIT_EXIT_CODE: i32 = 130;
+
+static INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the Ctrl-C (SIGINT) handler.
+///
+/// The first Ctrl-C only requests an interrupt: optimizers stop at the next iteration boundary
+/// (see `is_interrupt_requested`), so the best-so-far results can still be reported.
+/// The second Ctrl-C quits immediately.
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPT_REQUESTED.swap(true, Ordering::SeqCst) {
+            eprintln!("\nInterrupted again, quitting.");
+            process::exit(FORCE_QUIT_EXIT_CODE);
+        }
+
+        eprintln!(
+            "\nInterrupted, stopping after the current iteration (press Ctrl-C again to quit immediately)."
+        );
+    })
+    .into_diagnostic()
+    .wrap_err_with(|| miette!("Could not install the Ctrl-C handler."))
+}
+
+/// Whether an interrupt (Ctrl-C) has been requested.
+#[inline]
+pub fn is_interrupt_requested() -> bool {
+    INTERRUPT_REQUESTED.load(Ordering::Relaxed)
+}
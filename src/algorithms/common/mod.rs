@@ -1,2 +1,11 @@
+pub mod boundary;
+pub mod checkpoint;
+pub mod constraints;
+pub mod initialization;
+pub mod interrupt;
+pub mod observer;
+pub mod progress;
 pub mod rng;
-pub mod structs;
\ No newline at end of file
+pub mod stopping;
+pub mod structs;
+pub mod trajectory;
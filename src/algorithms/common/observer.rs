@@ -0,0 +1,240 @@
+use miette::Result;
+
+use crate::algorithms::common::trajectory::TrajectoryRecorderState;
+use crate::core::functions::{BiObjectiveFunction, SuiteFunction};
+
+/// Function being optimized, as reported to observers (see `OptimizationObserver::on_start`).
+#[derive(Clone, Copy)]
+pub enum ObservedFunction {
+    /// Reported best values are objective values.
+    SingleObjective(SuiteFunction),
+
+    /// Reported best values are hypervolumes of the Pareto front found so far
+    /// (larger is better).
+    BiObjective(BiObjectiveFunction),
+}
+
+impl ObservedFunction {
+    /// Function index in its suite (`None` for pairs of BBOB functions).
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            ObservedFunction::SingleObjective(function) => {
+                Some(function.index())
+            }
+            ObservedFunction::BiObjective(function) => function.index(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            ObservedFunction::SingleObjective(function) => function.name(),
+            ObservedFunction::BiObjective(function) => function.name(),
+        }
+    }
+
+    /// Global minimum of a single-objective function (`None` for bi-objective ones).
+    pub fn global_minimum(&self) -> Option<f64> {
+        match self {
+            ObservedFunction::SingleObjective(function) => {
+                Some(function.global_minimum())
+            }
+            ObservedFunction::BiObjective(_) => None,
+        }
+    }
+}
+
+impl From<SuiteFunction> for ObservedFunction {
+    fn from(function: SuiteFunction) -> Self {
+        ObservedFunction::SingleObjective(function)
+    }
+}
+
+/// Type of a single run inside an optimization.
+#[derive(Clone, Copy)]
+pub enum OptimizationRunType {
+    /// Independent run (restart), e.g. with the swarm at random positions.
+    Exploration {
+        run_number: usize,
+        total_runs: usize,
+    },
+
+    /// Run starting at the best solution of all previous runs.
+    Refinement {
+        run_number: usize,
+        total_runs: usize,
+        best_value_before_refinement: f64,
+    },
+}
+
+/// Start of a single run (see `OptimizationObserver::on_restart`).
+pub struct RunStart {
+    pub run_type: OptimizationRunType,
+
+    pub maximum_iterations: usize,
+
+    /// Iterations without improvement after which the run is aborted, if any.
+    pub stuck_after_iterations: Option<usize>,
+
+    /// Iterations without improvement after which the movement jitter
+    /// starts heating up (firefly only).
+    pub reheat_after_iterations: Option<usize>,
+
+    /// Iterations already performed (non-zero when the run continues from a checkpoint).
+    pub iterations_performed: usize,
+}
+
+/// State of the optimizer after a single iteration, as reported by the algorithm.
+pub struct IterationStatus {
+    /// Iteration number (1-based) inside the current run.
+    pub iteration: usize,
+
+    /// Total objective function evaluations performed on the problem so far.
+    pub evaluations: u64,
+
+    /// Best value found in the current run so far
+    /// (the hypervolume for bi-objective functions, see `ObservedFunction`).
+    pub best_value: f64,
+
+    /// Iterations since the best value of the current run last improved.
+    pub iterations_since_improvement: usize,
+
+    /// Current movement jitter coefficient (firefly only).
+    pub jitter: Option<f64>,
+
+    /// Current temperature (simulated annealing only).
+    pub temperature: Option<f64>,
+
+    /// Mean objective value of the population (population-based algorithms only).
+    pub population_mean: Option<f64>,
+
+    /// Standard deviation of the objective values of the population
+    /// (population-based algorithms only).
+    pub population_std: Option<f64>,
+}
+
+/// Receives events from an optimizer, e.g. to show progress bars or record trajectories.
+///
+/// Events of a single optimization arrive in this order: `on_start`, then for each run
+/// `on_restart` followed by `on_iteration` (and `on_improvement`, if the iteration improved
+/// the best value of the run) for each iteration, and finally `on_finish`.
+/// All callbacks do nothing by default.
+pub trait OptimizationObserver {
+    /// Called once before optimizing `function` (also when continuing from a checkpoint).
+    fn on_start(&mut self, _function: ObservedFunction) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at the start of each run, including the first one.
+    fn on_restart(&mut self, _run: &RunStart) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_iteration(&mut self, _iteration: &IterationStatus) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after `on_iteration` if the iteration improved the best value of the run.
+    fn on_improvement(&mut self, _iteration: &IterationStatus) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once the optimization has finished with the best value `best_value`
+    /// (also when it was interrupted or stopped early).
+    fn on_finish(&mut self, _best_value: f64) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called right before a checkpoint is saved. Observers writing into files must flush them,
+    /// the trajectory recorder also returns its state, which is saved in the checkpoint.
+    fn on_checkpoint(&mut self) -> Result<Option<TrajectoryRecorderState>> {
+        Ok(None)
+    }
+}
+
+/// Observer that ignores all events (silent mode).
+pub struct SilentObserver;
+
+impl OptimizationObserver for SilentObserver {}
+
+/// Forwards all events to multiple observers (in order).
+#[derive(Default)]
+pub struct MultiObserver<'o> {
+    observers: Vec<&'o mut dyn OptimizationObserver>,
+}
+
+impl<'o> MultiObserver<'o> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_observer(
+        mut self,
+        observer: &'o mut dyn OptimizationObserver,
+    ) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn with_optional_observer<O: OptimizationObserver>(
+        self,
+        observer: Option<&'o mut O>,
+    ) -> Self {
+        match observer {
+            Some(observer) => self.with_observer(observer),
+            None => self,
+        }
+    }
+}
+
+impl<'o> OptimizationObserver for MultiObserver<'o> {
+    fn on_start(&mut self, function: ObservedFunction) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.on_start(function)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_restart(&mut self, run: &RunStart) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.on_restart(run)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_iteration(&mut self, iteration: &IterationStatus) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.on_iteration(iteration)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_improvement(&mut self, iteration: &IterationStatus) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.on_improvement(iteration)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_finish(&mut self, best_value: f64) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.on_finish(best_value)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_checkpoint(&mut self) -> Result<Option<TrajectoryRecorderState>> {
+        let mut trajectory_state = None;
+        for observer in self.observers.iter_mut() {
+            if let Some(state) = observer.on_checkpoint()? {
+                trajectory_state = Some(state);
+            }
+        }
+
+        Ok(trajectory_state)
+    }
+}
@@ -0,0 +1,282 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use indicatif::ProgressDrawTarget;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::Serialize;
+
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    ObservedFunction,
+    OptimizationObserver,
+    OptimizationRunType,
+    RunStart,
+};
+
+/// How optimization progress is shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Progress bars if stdout is a terminal, plain lines otherwise.
+    Auto,
+
+    /// Interactive progress bars.
+    Bars,
+
+    /// One line on stdout at the start and end of each run.
+    Plain,
+
+    /// One JSON object on stderr per start, restart, improvement and finish.
+    Jsonl,
+}
+
+impl ProgressMode {
+    /// Resolves `Auto` into `Bars` or `Plain`, depending on whether stdout is a terminal.
+    pub fn resolve(self) -> Self {
+        match self {
+            ProgressMode::Auto => {
+                if ProgressDrawTarget::stdout().is_hidden() {
+                    ProgressMode::Plain
+                } else {
+                    ProgressMode::Bars
+                }
+            }
+            mode => mode,
+        }
+    }
+}
+
+/// Short description of a run, e.g. `explore | run 1/5`.
+fn describe_run(run_type: &OptimizationRunType) -> String {
+    match run_type {
+        OptimizationRunType::Exploration {
+            run_number,
+            total_runs,
+        } => format!("explore | run {}/{}", run_number, total_runs),
+        OptimizationRunType::Refinement {
+            run_number,
+            total_runs,
+            ..
+        } => format!("refine best | run {}/{}", run_number, total_runs),
+    }
+}
+
+/// Observer printing one plain line (without colours or redraws) at the start and end of each run,
+/// e.g. for log files of non-interactive runs.
+pub struct PlainProgressPrinter {
+    /// Global minimum of the function (`None` for bi-objective functions,
+    /// whose best values are hypervolumes).
+    global_minimum: Option<f64>,
+
+    /// Description and maximum iterations of the run in progress.
+    current_run: Option<(String, usize)>,
+
+    /// Iterations performed and best value of the run in progress after the last iteration.
+    last_iteration: Option<(usize, f64)>,
+}
+
+impl PlainProgressPrinter {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            global_minimum: None,
+            current_run: None,
+            last_iteration: None,
+        }
+    }
+
+    fn finish_current_run(&mut self) {
+        let Some((run_description, maximum_iterations)) =
+            self.current_run.take()
+        else {
+            return;
+        };
+
+        if let Some((iterations_performed, best_value)) =
+            self.last_iteration.take()
+        {
+            let best_value_description = match self.global_minimum {
+                Some(global_minimum) => format!(
+                    "minimum: {:.5}, distance: {:.5}",
+                    best_value,
+                    best_value - global_minimum
+                ),
+                None => format!("hypervolume: {:.5}", best_value),
+            };
+
+            println!(
+                "[ {} ] finished after {}/{} iterations | {}",
+                run_description,
+                iterations_performed,
+                maximum_iterations,
+                best_value_description
+            );
+        }
+    }
+}
+
+impl OptimizationObserver for PlainProgressPrinter {
+    fn on_start(&mut self, function: ObservedFunction) -> Result<()> {
+        self.global_minimum = function.global_minimum();
+        Ok(())
+    }
+
+    fn on_restart(&mut self, run: &RunStart) -> Result<()> {
+        self.finish_current_run();
+
+        let run_description = describe_run(&run.run_type);
+        if run.iterations_performed == 0 {
+            println!("[ {} ] started", run_description);
+        } else {
+            println!(
+                "[ {} ] continuing after {} iterations",
+                run_description, run.iterations_performed
+            );
+        }
+
+        self.current_run = Some((run_description, run.maximum_iterations));
+        Ok(())
+    }
+
+    fn on_iteration(&mut self, iteration: &IterationStatus) -> Result<()> {
+        self.last_iteration = Some((iteration.iteration, iteration.best_value));
+        Ok(())
+    }
+
+    fn on_finish(&mut self, _best_value: f64) -> Result<()> {
+        self.finish_current_run();
+        Ok(())
+    }
+}
+
+/// A single line of the JSONL progress stream. Every event identifies the problem
+/// by its suite, dimension and function index (indices of different suites overlap,
+/// pairs of BBOB functions optimized by NSGA-II have no index).
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ProgressEvent {
+    Start {
+        suite: &'static str,
+        dimension: usize,
+        function_index: Option<usize>,
+        function_name: String,
+    },
+    Restart {
+        suite: &'static str,
+        dimension: usize,
+        function_index: Option<usize>,
+        run_type: &'static str,
+        run_number: usize,
+        total_runs: usize,
+        iterations_performed: usize,
+    },
+    Improvement {
+        suite: &'static str,
+        dimension: usize,
+        function_index: Option<usize>,
+        run_type: &'static str,
+        run_number: usize,
+        iteration: usize,
+        evaluations: u64,
+        best_value: f64,
+    },
+    Finish {
+        suite: &'static str,
+        dimension: usize,
+        function_index: Option<usize>,
+        best_value: f64,
+    },
+}
+
+/// Observer writing one JSON object per line on stderr for each start, restart,
+/// improvement and finish, for machine-readable progress of non-interactive runs.
+pub struct JsonlProgressWriter {
+    suite: &'static str,
+    dimension: usize,
+    function_index: Option<usize>,
+    run_type: &'static str,
+    run_number: usize,
+}
+
+impl JsonlProgressWriter {
+    /// Creates a writer for the problems of `suite` (see `SuiteType::name`)
+    /// with `dimension` variables.
+    pub fn new(suite: &'static str, dimension: usize) -> Self {
+        Self {
+            suite,
+            dimension,
+            function_index: None,
+            run_type: "exploration",
+            run_number: 0,
+        }
+    }
+
+    fn write(&self, event: &ProgressEvent) -> Result<()> {
+        let mut stderr = io::stderr().lock();
+
+        serde_json::to_writer(&mut stderr, event)
+            .into_diagnostic()
+            .and_then(|_| stderr.write_all(b"\n").into_diagnostic())
+            .wrap_err_with(|| miette!("Could not write progress event."))
+    }
+}
+
+impl OptimizationObserver for JsonlProgressWriter {
+    fn on_start(&mut self, function: ObservedFunction) -> Result<()> {
+        self.function_index = function.index();
+
+        self.write(&ProgressEvent::Start {
+            suite: self.suite,
+            dimension: self.dimension,
+            function_index: self.function_index,
+            function_name: function.name(),
+        })
+    }
+
+    fn on_restart(&mut self, run: &RunStart) -> Result<()> {
+        let (run_type, run_number, total_runs) = match run.run_type {
+            OptimizationRunType::Exploration {
+                run_number,
+                total_runs,
+            } => ("exploration", run_number, total_runs),
+            OptimizationRunType::Refinement {
+                run_number,
+                total_runs,
+                ..
+            } => ("refinement", run_number, total_runs),
+        };
+        self.run_type = run_type;
+        self.run_number = run_number;
+
+        self.write(&ProgressEvent::Restart {
+            suite: self.suite,
+            dimension: self.dimension,
+            function_index: self.function_index,
+            run_type,
+            run_number,
+            total_runs,
+            iterations_performed: run.iterations_performed,
+        })
+    }
+
+    fn on_improvement(&mut self, iteration: &IterationStatus) -> Result<()> {
+        self.write(&ProgressEvent::Improvement {
+            suite: self.suite,
+            dimension: self.dimension,
+            function_index: self.function_index,
+            run_type: self.run_type,
+            run_number: self.run_number,
+            iteration: iteration.iteration,
+            evaluations: iteration.evaluations,
+            best_value: iteration.best_value,
+        })
+    }
+
+    fn on_finish(&mut self, best_value: f64) -> Result<()> {
+        self.write(&ProgressEvent::Finish {
+            suite: self.suite,
+            dimension: self.dimension,
+            function_index: self.function_index,
+            best_value,
+        })
+    }
+}
@@ -1,10 +1,11 @@
 use rand::distributions::{Distribution, Uniform};
 use rand::{thread_rng, SeedableRng};
 use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
 
 use crate::core::problem::Bounds;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UniformU8RandomGenerator {
     distribution: Uniform<u8>,
     rng: Pcg64Mcg,
@@ -32,44 +33,282 @@ impl UniformU8RandomGenerator {
 
 
 
-#[derive(Clone)]
+/// Uniform random generator of points inside (per-dimension) bounds.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UniformF64BoundedRandomGenerator {
     pub bounds: Bounds,
 
-    distribution: Uniform<f64>,
+    /// Uniform distribution of each dimension.
+    distributions: Vec<Uniform<f64>>,
     rng: Pcg64Mcg,
 }
 
 impl UniformF64BoundedRandomGenerator {
     pub fn new(bounds: Bounds, seed: [u8; 16]) -> Self {
-        let distribution = bounds.uniform_random_generator();
+        let distributions = bounds.uniform_random_generators();
         let pcg_rng = Pcg64Mcg::from_seed(seed);
 
         Self {
             bounds,
-            distribution,
+            distributions,
             rng: pcg_rng,
         }
     }
 
+    /// Samples a single value inside the bounds of the first dimension
+    /// (meant for one-dimensional generators, e.g. `Bounds::uniform(-0.5, 0.5, 1)`).
     pub fn sample(&mut self) -> f64 {
-        self.distribution.sample(&mut self.rng)
+        self.distributions[0].sample(&mut self.rng)
     }
 
-    pub fn sample_multiple(&mut self, samples: usize) -> Vec<f64> {
-        (0..samples)
-            .map(|_| self.distribution.sample(&mut self.rng))
+    /// Samples a point inside the bounds (one value per dimension).
+    pub fn sample_point(&mut self) -> Vec<f64> {
+        self.distributions
+            .iter()
+            .map(|distribution| distribution.sample(&mut self.rng))
             .collect()
     }
 
+    /// Samples a single value in `[0, 1]` (see `sample`).
     pub fn sample_0_to_1(&mut self) -> f64 {
         let random_num = self.sample();
-        (1f64 / (self.bounds.upper_bound - self.bounds.lower_bound))
-            * (random_num - self.bounds.lower_bound)
+        (1f64 / (self.bounds.upper_bound(0) - self.bounds.lower_bound(0)))
+            * (random_num - self.bounds.lower_bound(0))
+    }
+}
+
+/// Halton low-discrepancy sequence in `[0, 1)^dimensions` (using the first `dimensions` primes
+/// as bases), randomized with a seeded random shift (modulo 1) of each coordinate.
+#[derive(Clone)]
+pub struct HaltonSequence {
+    bases: Vec<u64>,
+    shift: Vec<f64>,
+    index: u64,
+}
+
+impl HaltonSequence {
+    pub fn new(dimensions: usize, seed: [u8; 16]) -> Self {
+        Self {
+            bases: first_primes(dimensions),
+            shift: random_unit_shift(dimensions, seed),
+            index: 0,
+        }
+    }
+
+    pub fn next_point(&mut self) -> Vec<f64> {
+        // The first point of the unshifted sequence is the origin, so we skip it.
+        self.index += 1;
+
+        self.bases
+            .iter()
+            .zip(self.shift.iter())
+            .map(|(base, shift)| {
+                (radical_inverse(self.index, *base) + shift).fract()
+            })
+            .collect()
     }
 }
 
-pub fn choose_random<T: Clone>(vec: Vec<T>) -> T {
+/// Van der Corput radical inverse of `index` in the given `base`.
+fn radical_inverse(mut index: u64, base: u64) -> f64 {
+    let mut inverse = 0f64;
+    let mut digit_weight = 1f64 / base as f64;
+
+    while index > 0 {
+        inverse += (index % base) as f64 * digit_weight;
+        index /= base;
+        digit_weight /= base as f64;
+    }
+
+    inverse
+}
+
+fn first_primes(count: usize) -> Vec<u64> {
+    // Upper bound for the `count`-th prime (Rosser's theorem), with a margin for small counts.
+    let count_f64 = count.max(6) as f64;
+    let sieve_size =
+        (count_f64 * (count_f64.ln() + count_f64.ln().ln())).ceil() as usize + 1;
+
+    // Sieve of Eratosthenes.
+    let mut is_composite = vec![false; sieve_size];
+    let mut primes: Vec<u64> = Vec::with_capacity(count);
+
+    for candidate in 2..sieve_size {
+        if primes.len() == count {
+            break;
+        }
+        if is_composite[candidate] {
+            continue;
+        }
+
+        primes.push(candidate as u64);
+        for multiple in (candidate * candidate..sieve_size).step_by(candidate) {
+            is_composite[multiple] = true;
+        }
+    }
+
+    primes
+}
+
+fn random_unit_shift(dimensions: usize, seed: [u8; 16]) -> Vec<f64> {
+    let mut rng = Pcg64Mcg::from_seed(seed);
+    let distribution = Uniform::new(0f64, 1f64);
+
+    (0..dimensions)
+        .map(|_| distribution.sample(&mut rng))
+        .collect()
+}
+
+/// Maximum amount of dimensions `SobolSequence` supports.
+pub const SOBOL_MAXIMUM_DIMENSIONS: usize = SOBOL_DIRECTION_NUMBERS.len() + 1;
+
+/// Primitive polynomials and initial direction numbers for dimensions 2 to 40
+/// (Joe & Kuo, "Constructing Sobol sequences with better two-dimensional projections", 2008):
+/// degree `s`, coefficients `a` and initial direction numbers `m_1, ..., m_s`.
+const SOBOL_DIRECTION_NUMBERS: [(u32, u32, &[u32]); 39] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+    (7, 7, &[1, 1, 3, 13, 7, 35, 63]),
+    (7, 8, &[1, 3, 5, 9, 1, 25, 53]),
+    (7, 14, &[1, 3, 1, 13, 9, 35, 107]),
+    (7, 19, &[1, 3, 1, 5, 27, 61, 31]),
+    (7, 21, &[1, 1, 5, 11, 19, 41, 61]),
+    (7, 28, &[1, 3, 5, 3, 3, 13, 69]),
+    (7, 31, &[1, 1, 7, 13, 1, 19, 1]),
+    (7, 32, &[1, 3, 7, 5, 13, 19, 59]),
+    (7, 37, &[1, 1, 3, 9, 25, 29, 41]),
+    (7, 41, &[1, 3, 5, 13, 23, 1, 55]),
+    (7, 42, &[1, 3, 7, 3, 13, 59, 17]),
+    (7, 50, &[1, 3, 1, 3, 5, 53, 69]),
+    (7, 55, &[1, 1, 5, 5, 23, 33, 13]),
+    (7, 56, &[1, 1, 7, 7, 1, 61, 123]),
+    (7, 59, &[1, 1, 7, 9, 13, 61, 49]),
+    (7, 62, &[1, 3, 3, 5, 3, 55, 33]),
+    (8, 14, &[1, 3, 1, 15, 31, 13, 49, 245]),
+    (8, 21, &[1, 3, 5, 15, 31, 59, 63, 97]),
+    (8, 22, &[1, 3, 1, 11, 11, 11, 77, 249]),
+];
+
+/// Sobol low-discrepancy sequence in `[0, 1)^dimensions` (generated in Gray code order),
+/// randomized with a seeded random digital shift (XOR) of each coordinate.
+#[derive(Clone)]
+pub struct SobolSequence {
+    /// 32-bit direction numbers of each dimension.
+    direction_numbers: Vec<[u32; 32]>,
+    shift: Vec<u32>,
+
+    current: Vec<u32>,
+    index: u32,
+}
+
+impl SobolSequence {
+    /// Returns `None` if `dimensions` is larger than `SOBOL_MAXIMUM_DIMENSIONS`.
+    pub fn new(dimensions: usize, seed: [u8; 16]) -> Option<Self> {
+        if dimensions > SOBOL_MAXIMUM_DIMENSIONS {
+            return None;
+        }
+
+        let mut direction_numbers: Vec<[u32; 32]> =
+            Vec::with_capacity(dimensions);
+
+        // The first dimension is the van der Corput sequence in base 2.
+        let mut first_dimension = [0u32; 32];
+        for (bit, direction_number) in first_dimension.iter_mut().enumerate() {
+            *direction_number = 1 << (31 - bit);
+        }
+        direction_numbers.push(first_dimension);
+
+        for (degree, coefficients, initial_numbers) in SOBOL_DIRECTION_NUMBERS
+            .iter()
+            .take(dimensions.saturating_sub(1))
+        {
+            let degree = *degree as usize;
+            let mut dimension = [0u32; 32];
+
+            for bit in 0..32 {
+                dimension[bit] = if bit < degree {
+                    initial_numbers[bit] << (31 - bit)
+                } else {
+                    let mut direction_number = dimension[bit - degree]
+                        ^ (dimension[bit - degree] >> degree);
+                    for term in 1..degree {
+                        if (coefficients >> (degree - 1 - term)) & 1 == 1 {
+                            direction_number ^= dimension[bit - term];
+                        }
+                    }
+
+                    direction_number
+                };
+            }
+
+            direction_numbers.push(dimension);
+        }
+
+        let mut rng = Pcg64Mcg::from_seed(seed);
+        let shift = (0..dimensions)
+            .map(|_| Uniform::new_inclusive(u32::MIN, u32::MAX).sample(&mut rng))
+            .collect();
+
+        Some(Self {
+            direction_numbers,
+            shift,
+            current: vec![0; dimensions],
+            index: 0,
+        })
+    }
+
+    pub fn next_point(&mut self) -> Vec<f64> {
+        // The first point of the unshifted sequence is the origin, so we skip it.
+        let changed_bit = self.index.trailing_ones() as usize;
+        self.index += 1;
+
+        self.current
+            .iter_mut()
+            .zip(self.direction_numbers.iter())
+            .zip(self.shift.iter())
+            .map(|((value, direction_numbers), shift)| {
+                *value ^= direction_numbers[changed_bit];
+                (*value ^ shift) as f64 / 2f64.powi(32)
+            })
+            .collect()
+    }
+}
+
+/// Derives `count` random generator seeds for independent repetitions from `base_seed`.
+/// The first seed is `base_seed` itself, so a single repetition is the same as a normal run.
+pub fn derive_seeds(base_seed: [u8; 16], count: usize) -> Vec<[u8; 16]> {
+    let mut seed_generator = UniformU8RandomGenerator::new(base_seed);
+
+    (0..count)
+        .map(|repetition_index| {
+            if repetition_index == 0 {
+                base_seed
+            } else {
+                seed_generator.sample_multiple::<16>()
+            }
+        })
+        .collect()
+}
+
+pub fn choose_random<T: Clone>(vec: &[T]) -> T {
     let mut rng = thread_rng();
     let uniform = Uniform::new(0, vec.len());
     vec[uniform.sample(&mut rng)].clone()
@@ -0,0 +1,223 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Criterion for stopping an entire optimization early (across all its runs),
+/// checked after every iteration. Criteria can be combined with `All` (AND) and `Any` (OR).
+///
+/// These come on top of the algorithm-specific stopping conditions
+/// (e.g. `FireflyRunOptions::maximum_iterations`), which still apply.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StoppingCriterion {
+    /// Stop after this many objective function evaluations.
+    EvaluationBudget(u64),
+
+    /// Stop after this much wall-clock time.
+    TimeBudget(Duration),
+
+    /// Stop once the best value is within this distance of the global minimum.
+    TargetReached(f64),
+
+    /// Stop if the best value hasn't improved in this many objective function evaluations.
+    Stagnation(u64),
+
+    /// Stop once the population diversity (mean distance of the population from its centroid)
+    /// drops below this value. Never met by algorithms without a population.
+    DiversityCollapse(f64),
+
+    /// Stop once all of the criteria are met.
+    All(Vec<StoppingCriterion>),
+
+    /// Stop once any of the criteria is met.
+    Any(Vec<StoppingCriterion>),
+}
+
+impl StoppingCriterion {
+    /// Whether the criterion (or any of its parts) is `TargetReached`,
+    /// which needs a known global minimum.
+    pub fn uses_target(&self) -> bool {
+        match self {
+            Self::TargetReached(_) => true,
+            Self::All(criteria) | Self::Any(criteria) => {
+                criteria.iter().any(StoppingCriterion::uses_target)
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_single(value: &str) -> Result<Self, String> {
+        let (name, parameter) = value.split_once('=').ok_or_else(|| {
+            format!("Invalid stopping criterion \"{value}\": expected <name>=<value>.")
+        })?;
+
+        let parameter = parameter.trim();
+        let number = parameter.parse::<f64>().map_err(|_| {
+            format!("Invalid stopping criterion value \"{parameter}\": expected a number.")
+        })?;
+        if number.is_nan() || number < 0f64 {
+            return Err(format!(
+                "Invalid stopping criterion value \"{parameter}\": expected a non-negative number."
+            ));
+        }
+
+        match name.trim() {
+            "evaluations" => Ok(Self::EvaluationBudget(number as u64)),
+            "time" => Ok(Self::TimeBudget(Duration::from_secs_f64(number))),
+            "target" => Ok(Self::TargetReached(number)),
+            "stagnation" => Ok(Self::Stagnation(number as u64)),
+            "diversity" => Ok(Self::DiversityCollapse(number)),
+            name => Err(format!(
+                "Unknown stopping criterion \"{name}\" \
+                (expected evaluations, time, target, stagnation or diversity)."
+            )),
+        }
+    }
+
+    fn is_met(
+        &self,
+        state: &StoppingState,
+        status: &OptimizationStatus,
+    ) -> bool {
+        match self {
+            Self::EvaluationBudget(evaluations) => {
+                status.evaluations >= *evaluations
+            }
+            Self::TimeBudget(duration) => state.elapsed() >= *duration,
+            Self::TargetReached(precision) => matches!(
+                state.best_value,
+                Some(best_value) if best_value - state.target_value <= *precision
+            ),
+            Self::Stagnation(evaluations) => {
+                status.evaluations - state.evaluations_at_last_improvement
+                    >= *evaluations
+            }
+            Self::DiversityCollapse(threshold) => matches!(
+                status.population_diversity,
+                Some(diversity) if diversity < *threshold
+            ),
+            Self::All(criteria) => criteria
+                .iter()
+                .all(|criterion| criterion.is_met(state, status)),
+            Self::Any(criteria) => criteria
+                .iter()
+                .any(|criterion| criterion.is_met(state, status)),
+        }
+    }
+}
+
+/// Parses criteria such as `target=1e-8 | evaluations=1e6 & stagnation=50000`:
+/// `name=value` criteria (`evaluations`, `time` in seconds, `target`, `stagnation` in evaluations
+/// and `diversity`), combined with `&` (AND) and `|` (OR). `&` binds tighter than `|`.
+impl FromStr for StoppingCriterion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut alternatives = value
+            .split('|')
+            .map(|alternative| {
+                let mut criteria = alternative
+                    .split('&')
+                    .map(Self::parse_single)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(match criteria.len() {
+                    1 => criteria.remove(0),
+                    _ => Self::All(criteria),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => Self::Any(alternatives),
+        })
+    }
+}
+
+/// Status of an optimization after an iteration, as reported by the algorithm.
+pub struct OptimizationStatus {
+    /// Total objective function evaluations performed on the problem so far.
+    pub evaluations: u64,
+
+    /// Best value found in the current run so far.
+    pub best_value: f64,
+
+    /// Mean distance of the population from its centroid (population-based algorithms only).
+    pub population_diversity: Option<f64>,
+}
+
+/// What a `StoppingTracker` saves in checkpoints.
+#[derive(Clone, Serialize, Deserialize)]
+struct StoppingState {
+    target_value: f64,
+
+    /// Best value of the entire optimization, `None` if nothing has been reported yet.
+    best_value: Option<f64>,
+    evaluations_at_last_improvement: u64,
+
+    /// Wall time spent before `started_at` (non-zero when resuming from a checkpoint).
+    elapsed_before: Duration,
+
+    #[serde(skip, default = "Instant::now")]
+    started_at: Instant,
+}
+
+impl StoppingState {
+    fn elapsed(&self) -> Duration {
+        self.elapsed_before + self.started_at.elapsed()
+    }
+}
+
+/// Tracks the status of a single optimization and checks it against a `StoppingCriterion`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoppingTracker {
+    criterion: StoppingCriterion,
+
+    #[serde(serialize_with = "serialize_stopping_state")]
+    state: StoppingState,
+}
+
+impl StoppingTracker {
+    /// Creates a tracker for an optimization of a problem with the global minimum `target_value`.
+    pub fn new(criterion: StoppingCriterion, target_value: f64) -> Self {
+        Self {
+            criterion,
+            state: StoppingState {
+                target_value,
+                best_value: None,
+                evaluations_at_last_improvement: 0,
+                elapsed_before: Duration::ZERO,
+                started_at: Instant::now(),
+            },
+        }
+    }
+
+    /// Updates the tracker with the latest `status` and returns whether the optimization should stop.
+    pub fn should_stop(&mut self, status: &OptimizationStatus) -> bool {
+        let has_improved = match self.state.best_value {
+            Some(best_value) => status.best_value < best_value,
+            None => true,
+        };
+        if has_improved {
+            self.state.best_value = Some(status.best_value);
+            self.state.evaluations_at_last_improvement = status.evaluations;
+        }
+
+        self.criterion.is_met(&self.state, status)
+    }
+}
+
+/// Saves the elapsed time instead of the start `Instant` (which can't be saved),
+/// so the time budget continues where it left off when resuming from a checkpoint.
+fn serialize_stopping_state<S: Serializer>(
+    state: &StoppingState,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    StoppingState {
+        elapsed_before: state.elapsed(),
+        started_at: Instant::now(),
+        ..state.clone()
+    }
+    .serialize(serializer)
+}
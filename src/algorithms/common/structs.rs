@@ -1,13 +1,43 @@
+use crate::algorithms::common::constraints::ConstrainedValue;
+
 #[derive(Debug)]
 pub struct Minimum {
     pub value: f64,
     pub vector: Vec<f64>,
+
+    /// Constraint violation at `vector` (`0` if it is feasible or the problem is unconstrained).
+    pub constraint_violation: f64,
 }
 
 impl Minimum {
     #[inline]
-    pub fn new(value: f64, vector: Vec<f64>) -> Minimum {
-        Self { value, vector }
+    pub fn new(
+        value: f64,
+        vector: Vec<f64>,
+        constraint_violation: f64,
+    ) -> Minimum {
+        Self {
+            value,
+            vector,
+            constraint_violation,
+        }
+    }
+
+    #[inline]
+    pub fn is_feasible(&self) -> bool {
+        self.constraint_violation <= 0f64
+    }
+
+    /// Describes whether the minimum is feasible, e.g. `Feasible: no (constraint violation 0.25)`.
+    pub fn describe_feasibility(&self) -> String {
+        if self.is_feasible() {
+            "Feasible: yes".to_string()
+        } else {
+            format!(
+                "Feasible: no (constraint violation {})",
+                self.constraint_violation
+            )
+        }
     }
 }
 
@@ -15,19 +45,36 @@ impl Minimum {
 pub struct State {
     pub vector: Vec<f64>,
     pub objective_value: f64,
+
+    /// Penalty for leaving the bounds (see `BoundaryHandling::penalty`), added to
+    /// the objective value when comparing states (`0` inside the bounds).
+    pub boundary_penalty: f64,
+
+    /// Constraint violation at `vector` (`0` if it is feasible or the problem is unconstrained).
+    pub constraint_violation: f64,
 }
 
 impl State {
     pub fn set_objective_value(&mut self, obj_value: f64) {
         self.objective_value = obj_value;
     }
+
+    #[inline]
+    pub fn constrained_value(&self) -> ConstrainedValue {
+        ConstrainedValue::new(
+            self.objective_value + self.boundary_penalty,
+            self.constraint_violation,
+        )
+    }
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
             objective_value: f64::MAX,
+            boundary_penalty: 0f64,
             vector: Vec::new(),
+            constraint_violation: f64::MAX,
         }
     }
 }
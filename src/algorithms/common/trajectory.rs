@@ -0,0 +1,335 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    OptimizationObserver,
+    RunStart,
+};
+use crate::core::suite::SuiteType;
+
+/// Trajectory file format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TrajectoryFormat {
+    /// CSV file with a header row.
+    Csv,
+
+    /// One JSON object per line.
+    Jsonl,
+}
+
+impl TrajectoryFormat {
+    /// Infers the format from the file extension (`.csv` or `.jsonl`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(TrajectoryFormat::Csv),
+            "jsonl" => Some(TrajectoryFormat::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// A single line in the trajectory file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrajectoryEntry {
+    /// Suite the function is from (see `SuiteType::of_recorded_function` for older files without it).
+    #[serde(default)]
+    pub suite: Option<SuiteType>,
+
+    pub function_index: usize,
+
+    /// Repetition number (1-based) of independent runs on the same problem.
+    pub repetition: usize,
+
+    /// Run number (1-based), e.g. the firefly restart or refinement run.
+    pub run: usize,
+
+    pub iteration: usize,
+    pub evaluations: u64,
+
+    /// Best value found on this problem so far (across all runs).
+    pub best_value: f64,
+
+    pub jitter: Option<f64>,
+    pub temperature: Option<f64>,
+    pub population_mean: Option<f64>,
+    pub population_std: Option<f64>,
+}
+
+impl TrajectoryEntry {
+    /// Suite the function is from (also for entries written before it was recorded).
+    pub fn suite(&self) -> SuiteType {
+        SuiteType::of_recorded_function(self.suite, self.function_index)
+    }
+}
+
+enum TrajectoryWriter {
+    Csv(Box<csv::Writer<File>>),
+    Jsonl(BufWriter<File>),
+}
+
+impl TrajectoryWriter {
+    fn new(
+        file: File,
+        format: TrajectoryFormat,
+        write_csv_header: bool,
+    ) -> Self {
+        match format {
+            TrajectoryFormat::Csv => TrajectoryWriter::Csv(Box::new(
+                csv::WriterBuilder::new()
+                    .has_headers(write_csv_header)
+                    .from_writer(file),
+            )),
+            TrajectoryFormat::Jsonl => {
+                TrajectoryWriter::Jsonl(BufWriter::new(file))
+            }
+        }
+    }
+
+    fn file(&self) -> &File {
+        match self {
+            TrajectoryWriter::Csv(writer) => writer.get_ref(),
+            TrajectoryWriter::Jsonl(writer) => writer.get_ref(),
+        }
+    }
+}
+
+/// State of a `TrajectoryRecorder`, saved in checkpoints so recording can be resumed.
+#[derive(Serialize, Deserialize)]
+pub struct TrajectoryRecorderState {
+    #[serde(default)]
+    suite: Option<SuiteType>,
+    function_index: usize,
+    repetition: usize,
+    run: usize,
+
+    /// `None` if nothing has been recorded on the current problem yet.
+    best_value_so_far: Option<f64>,
+
+    /// Length of the trajectory file (in bytes) when the state was saved.
+    file_length: u64,
+}
+
+/// Records per-iteration convergence trajectories of optimization runs to a file.
+pub struct TrajectoryRecorder {
+    writer: TrajectoryWriter,
+
+    /// `None` until the first problem is started.
+    suite: Option<SuiteType>,
+    function_index: usize,
+    repetition: usize,
+    run: usize,
+    best_value_so_far: f64,
+}
+
+impl TrajectoryRecorder {
+    pub fn create(path: &Path, format: TrajectoryFormat) -> Result<Self> {
+        if let Some(parent_directory) = path.parent() {
+            if !parent_directory.as_os_str().is_empty() {
+                fs::create_dir_all(parent_directory).into_diagnostic()?;
+            }
+        }
+
+        let file = File::create(path).into_diagnostic()?;
+
+        Ok(Self {
+            writer: TrajectoryWriter::new(file, format, true),
+            suite: None,
+            function_index: 0,
+            repetition: 0,
+            run: 0,
+            best_value_so_far: f64::INFINITY,
+        })
+    }
+
+    /// Continues recording into an existing trajectory file from a saved `state`.
+    /// Entries written after the state was saved are discarded.
+    pub fn resume(
+        path: &Path,
+        format: TrajectoryFormat,
+        state: &TrajectoryRecorderState,
+    ) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                miette!("Could not open trajectory {}.", path.display())
+            })?;
+
+        file.set_len(state.file_length).into_diagnostic()?;
+        file.seek(SeekFrom::End(0)).into_diagnostic()?;
+
+        Ok(Self {
+            writer: TrajectoryWriter::new(file, format, state.file_length == 0),
+            suite: state.suite,
+            function_index: state.function_index,
+            repetition: state.repetition,
+            run: state.run,
+            best_value_so_far: state.best_value_so_far.unwrap_or(f64::INFINITY),
+        })
+    }
+
+    /// Flushes the file and returns the current state of the recorder (see `resume`).
+    pub fn save_state(&mut self) -> Result<TrajectoryRecorderState> {
+        self.flush()?;
+
+        let file_length = self.writer.file().metadata().into_diagnostic()?.len();
+
+        Ok(TrajectoryRecorderState {
+            suite: self.suite,
+            function_index: self.function_index,
+            repetition: self.repetition,
+            run: self.run,
+            best_value_so_far: Some(self.best_value_so_far)
+                .filter(|best_value| best_value.is_finite()),
+            file_length,
+        })
+    }
+
+    /// Marks the start of a new problem (or a new repetition of the same problem).
+    /// This resets the run counter and best value.
+    pub fn start_problem(
+        &mut self,
+        suite: SuiteType,
+        function_index: usize,
+        repetition: usize,
+    ) {
+        self.suite = Some(suite);
+        self.function_index = function_index;
+        self.repetition = repetition;
+        self.run = 0;
+        self.best_value_so_far = f64::INFINITY;
+    }
+
+    /// Marks the start of a new run on the current problem.
+    pub fn start_run(&mut self) {
+        self.run += 1;
+    }
+
+    pub fn record(&mut self, point: &IterationStatus) -> Result<()> {
+        self.best_value_so_far = self.best_value_so_far.min(point.best_value);
+
+        let entry = TrajectoryEntry {
+            suite: self.suite,
+            function_index: self.function_index,
+            repetition: self.repetition,
+            run: self.run,
+            iteration: point.iteration,
+            evaluations: point.evaluations,
+            best_value: self.best_value_so_far,
+            jitter: point.jitter,
+            temperature: point.temperature,
+            population_mean: point.population_mean,
+            population_std: point.population_std,
+        };
+
+        match &mut self.writer {
+            TrajectoryWriter::Csv(writer) => {
+                writer.serialize(entry).into_diagnostic()
+            }
+            TrajectoryWriter::Jsonl(writer) => {
+                serde_json::to_writer(&mut *writer, &entry).into_diagnostic()?;
+                writer.write_all(b"\n").into_diagnostic()
+            }
+        }
+        .wrap_err_with(|| miette!("Could not write trajectory entry."))
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        match &mut self.writer {
+            TrajectoryWriter::Csv(writer) => writer.flush(),
+            TrajectoryWriter::Jsonl(writer) => writer.flush(),
+        }
+        .into_diagnostic()
+    }
+}
+
+/// Records every iteration of every run.
+impl OptimizationObserver for TrajectoryRecorder {
+    fn on_restart(&mut self, run: &RunStart) -> Result<()> {
+        // A run continuing from a checkpoint keeps its run number.
+        if run.iterations_performed == 0 {
+            self.start_run();
+        }
+
+        Ok(())
+    }
+
+    fn on_iteration(&mut self, iteration: &IterationStatus) -> Result<()> {
+        self.record(iteration)
+    }
+
+    fn on_finish(&mut self, _best_value: f64) -> Result<()> {
+        self.flush()
+    }
+
+    fn on_checkpoint(&mut self) -> Result<Option<TrajectoryRecorderState>> {
+        Ok(Some(self.save_state()?))
+    }
+}
+
+/// Reads trajectory entries from `path` (as written by `TrajectoryRecorder`).
+/// The format is inferred from the file extension.
+pub fn read_trajectory_entries(path: &Path) -> Result<Vec<TrajectoryEntry>> {
+    let format = TrajectoryFormat::from_path(path).ok_or_else(|| {
+        miette!(
+            "Could not infer trajectory format from {}, expected a .csv or .jsonl file.",
+            path.display()
+        )
+    })?;
+
+    match format {
+        TrajectoryFormat::Csv => {
+            let mut reader = csv::Reader::from_path(path).into_diagnostic()?;
+
+            reader
+                .deserialize::<TrajectoryEntry>()
+                .map(|entry| entry.into_diagnostic())
+                .collect::<Result<Vec<_>>>()
+        }
+        TrajectoryFormat::Jsonl => {
+            let file = File::open(path).into_diagnostic()?;
+
+            BufReader::new(file)
+                .lines()
+                .filter(|line| {
+                    line.as_ref()
+                        .map(|line| !line.trim().is_empty())
+                        .unwrap_or(true)
+                })
+                .map(|line| {
+                    serde_json::from_str(&line.into_diagnostic()?)
+                        .into_diagnostic()
+                })
+                .collect()
+        }
+    }
+    .wrap_err_with(|| {
+        miette!(
+            "Could not read trajectory from {}.",
+            path.display()
+        )
+    })
+}
+
+/// Calculates the mean and (population) standard deviation of `values`.
+pub fn mean_and_standard_deviation(values: &[f64]) -> (f64, f64) {
+    let count = values.len() as f64;
+
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean) * (value - mean))
+        .sum::<f64>()
+        / count;
+
+    (mean, variance.sqrt())
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::observer::OptimizationRunType;
+use crate::algorithms::common::rng::UniformU8RandomGenerator;
+use crate::algorithms::common::stopping::StoppingTracker;
+use crate::algorithms::firefly::results::FireflySingleRunOptimizationResult;
+use crate::algorithms::firefly::swarm::FireflySwarmState;
+use crate::algorithms::firefly::utilities::PointValue;
+use crate::algorithms::firefly::{FireflyRunOptions, FullFireflyOptions};
+
+/// Progress of a full firefly optimization (all restarts and refinement runs) between runs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FireflyOptimizationProgress {
+    /// Generator of the seeds for each new swarm.
+    pub seed_generator: UniformU8RandomGenerator,
+
+    /// Best solution of all finished runs (or the warm start point).
+    pub best_solution: Option<PointValue>,
+
+    pub iterations_performed_per_restart: Vec<usize>,
+
+    pub refinement_runs_performed: usize,
+
+    /// Tracker of `FullFireflyOptions::stopping_criterion`, if set.
+    pub stopping: Option<StoppingTracker>,
+}
+
+impl FireflyOptimizationProgress {
+    pub fn new(
+        random_generator_seed: [u8; 16],
+        best_solution: Option<PointValue>,
+        stopping: Option<StoppingTracker>,
+    ) -> Self {
+        Self {
+            seed_generator: UniformU8RandomGenerator::new(random_generator_seed),
+            best_solution,
+            iterations_performed_per_restart: Vec::new(),
+            refinement_runs_performed: 0,
+            stopping,
+        }
+    }
+
+    /// Returns the options and type of the next run to perform
+    /// (exploration restarts first, then refinement runs), or `None` if all runs are finished.
+    pub fn next_run<'o>(
+        &self,
+        options: &'o FullFireflyOptions,
+    ) -> Option<(&'o FireflyRunOptions, OptimizationRunType)> {
+        let restarts_performed = self.iterations_performed_per_restart.len();
+
+        if let Some(run_options) =
+            options.per_restart_options.get(restarts_performed)
+        {
+            return Some((
+                run_options,
+                OptimizationRunType::Exploration {
+                    run_number: restarts_performed + 1,
+                    total_runs: options.per_restart_options.len(),
+                },
+            ));
+        }
+
+        let refinement_options = options.post_process_best_options.as_ref()?;
+        let run_options =
+            refinement_options.get(self.refinement_runs_performed)?;
+
+        Some((
+            run_options,
+            OptimizationRunType::Refinement {
+                run_number: self.refinement_runs_performed + 1,
+                total_runs: refinement_options.len(),
+                best_value_before_refinement: self
+                    .best_solution
+                    .as_ref()
+                    .expect("BUG: Refinement run without a solution!")
+                    .value,
+            },
+        ))
+    }
+
+    /// Records the result of the run returned by `next_run`, keeping the better solution.
+    pub fn finish_run(
+        &mut self,
+        run_type: &OptimizationRunType,
+        run_result: FireflySingleRunOptimizationResult,
+    ) {
+        match run_type {
+            OptimizationRunType::Exploration { .. } => self
+                .iterations_performed_per_restart
+                .push(run_result.iterations_performed),
+            OptimizationRunType::Refinement { .. } => {
+                self.refinement_runs_performed += 1
+            }
+        }
+
+        // Constrained solutions are compared by Deb's feasibility rules.
+        let is_better = match self.best_solution.as_ref() {
+            Some(best_solution) => run_result
+                .minimum
+                .constrained_value()
+                .is_better_than(&best_solution.constrained_value()),
+            None => true,
+        };
+        if is_better {
+            self.best_solution = Some(run_result.minimum);
+        }
+    }
+}
+
+/// Full state of a firefly optimization in the middle of a run, saved in checkpoints.
+#[derive(Serialize, Deserialize)]
+pub struct FireflyOptimizationState {
+    pub progress: FireflyOptimizationProgress,
+
+    /// Swarm of the run in progress.
+    pub swarm: FireflySwarmState,
+
+    /// Objective function evaluations performed on the problem so far.
+    pub evaluations: u64,
+
+    /// Noise samples drawn on the problem so far (noisy problems only).
+    /// Older checkpoints don't have it, every evaluation drew one sample there.
+    #[serde(default)]
+    pub noise_samples_drawn: Option<u64>,
+}
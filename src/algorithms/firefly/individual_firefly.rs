@@ -1,37 +1,80 @@
 use std::f64::consts::E;
 use std::iter::zip;
 
-use num::clamp;
+use serde::{Deserialize, Serialize};
 
+use crate::algorithms::common::constraints::ConstrainedValue;
 use crate::algorithms::common::rng::UniformF64BoundedRandomGenerator;
+use crate::algorithms::firefly::utilities::PointValue;
 use crate::algorithms::firefly::FireflyRunOptions;
 use crate::core::problem::BBOBProblem;
 
 /// Individual firefly in the swarm.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Firefly {
     pub position: Vec<f64>,
     pub objective_function_value: f64,
+
+    /// Penalty for leaving the bounds (see `BoundaryHandling::penalty`), added to
+    /// the objective value when comparing fireflies (`0` inside the bounds).
+    #[serde(default)]
+    pub boundary_penalty: f64,
+
+    /// Constraint violation at `position` (`0` if it is feasible or the problem is unconstrained).
+    #[serde(default)]
+    pub constraint_violation: f64,
 }
 
 impl Firefly {
-    pub fn new(position: Vec<f64>, problem: &mut BBOBProblem) -> Self {
+    /// Creates a firefly at `position` (with its integer variables rounded on mixed-integer problems),
+    /// which must be inside the bounds.
+    pub fn new(mut position: Vec<f64>, problem: &mut BBOBProblem) -> Self {
         assert_eq!(
             problem.input_dimensions,
             position.len(),
             "Input dimensions did not match!"
         );
 
+        problem.round_integer_variables(&mut position);
+
         let objective_function_value = problem.evaluate(&position);
+        let constraint_violation = problem.constraint_violation(&position);
         Self {
             position,
             objective_function_value,
+            boundary_penalty: 0f64,
+            constraint_violation,
         }
     }
 
+    /// Objective value (with the boundary penalty) and constraint violation
+    /// the firefly is compared by (see `ConstraintHandler`).
+    #[inline]
+    pub fn value(&self) -> ConstrainedValue {
+        ConstrainedValue::new(
+            self.objective_function_value + self.boundary_penalty,
+            self.constraint_violation,
+        )
+    }
+
+    /// Position and objective value of the firefly (see `PointValue`).
+    pub fn point_value(&self) -> PointValue {
+        PointValue::new(
+            self.position.clone(),
+            self.objective_function_value,
+            self.constraint_violation,
+        )
+        .with_boundary_penalty(self.boundary_penalty)
+    }
+
+    /// Moves towards the brighter `second_firefly` and evaluates the new position.
+    ///
+    /// The new position is computed in `candidate_position` (a buffer reused by all moves, so
+    /// moving doesn't allocate), which afterwards holds the previous (or the discarded) position.
     pub fn move_towards(
         &mut self,
         second_firefly: &Firefly,
+        candidate_position: &mut Vec<f64>,
         problem: &mut BBOBProblem,
         minus_half_to_half_uniform_generator: &mut UniformF64BoundedRandomGenerator,
         movement_jitter_coefficient: f64,
@@ -57,23 +100,40 @@ impl Firefly {
 
 
         // Calculate the final value in each dimension.
-        let final_position: Vec<f64> = zip(
-            self.position.iter(),
-            second_firefly.position.iter(),
-        )
-        .map(|(our_value, other_firefly_value)| {
-            let updated_value = *our_value
+        candidate_position.clear();
+        candidate_position.extend(
+            zip(
+                self.position.iter(),
+                second_firefly.position.iter(),
+            )
+            .map(|(our_value, other_firefly_value)| {
+                *our_value
                 // Move towards the brighter firefly by the attraction coefficient.
                 + attraction_coefficient * (*other_firefly_value - *our_value)
                 // Add some random jitter, uniformly sampled and multiplied by the jitter coefficient.
                 + movement_jitter_coefficient
-                    * minus_half_to_half_uniform_generator.sample();
+                    * minus_half_to_half_uniform_generator.sample()
+            }),
+        );
+
+        // Handle dimensions that left the bounds (if the move is discarded, the firefly stays).
+        if !options.boundary_handling.repair_position(
+            candidate_position,
+            &self.position,
+            problem.bounds(),
+            minus_half_to_half_uniform_generator,
+        ) {
+            return;
+        }
 
-            clamp(updated_value, -5f64, 5f64)
-        })
-        .collect();
+        // Integer variables only take the closest valid value.
+        problem.round_integer_variables(candidate_position);
 
-        self.position = final_position;
+        std::mem::swap(&mut self.position, candidate_position);
         self.objective_function_value = problem.evaluate(&self.position);
+        self.boundary_penalty = options
+            .boundary_handling
+            .penalty(problem.bounds(), &self.position);
+        self.constraint_violation = problem.constraint_violation(&self.position);
     }
 }
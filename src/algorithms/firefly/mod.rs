@@ -1,7 +1,9 @@
 use miette::Result;
+use serde::Serialize;
 
 use crate::core::problem::BBOBProblem;
 
+pub mod checkpoint;
 mod individual_firefly;
 mod options;
 pub use options::{
@@ -9,16 +11,25 @@ pub use options::{
     FireflyRunOptions,
     FullFireflyOptions,
 };
-use results::{
-    FireflyFullOptimizationResult,
-    FireflySingleRunOptimizationResult,
-};
+pub use results::FireflyFullOptimizationResult;
+use results::FireflySingleRunOptimizationResult;
 use swarm::FireflySwarm;
 
-use crate::algorithms::common::rng::UniformU8RandomGenerator;
+use crate::algorithms::common::checkpoint::CheckpointRecorder;
+use crate::algorithms::common::interrupt::is_interrupt_requested;
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    OptimizationObserver,
+    OptimizationRunType,
+    RunStart,
+};
+use crate::algorithms::common::stopping::{OptimizationStatus, StoppingTracker};
 use crate::algorithms::common::structs::Minimum;
+use crate::algorithms::firefly::checkpoint::{
+    FireflyOptimizationProgress,
+    FireflyOptimizationState,
+};
 use crate::algorithms::firefly::utilities::PointValue;
-use crate::algorithms::firefly::visualization::FireflyOptimizationMultiProgressBar;
 
 mod results;
 pub mod swarm;
@@ -26,88 +37,67 @@ pub mod utilities;
 pub mod visualization;
 
 
-pub enum OptimizationRunType {
-    Exploration {
-        run_number: usize,
-        total_runs: usize,
-    },
-    Refinement {
-        run_number: usize,
-        total_runs: usize,
-        best_value_before_refinement: f64,
-    },
-}
-
-
-fn do_one_firefly_optimization_run_with_starting_point(
-    problem: &mut BBOBProblem,
+fn do_one_firefly_optimization_run_with_swarm<C: Serialize>(
     options: &FireflyRunOptions,
-    seed_generator: &mut UniformU8RandomGenerator,
-    multi_progress_bar: &FireflyOptimizationMultiProgressBar,
-    starting_point: Vec<f64>,
     run_type: OptimizationRunType,
-) -> Result<FireflySingleRunOptimizationResult> {
-    let problem_global_minimum = problem.name.global_minimum();
-
-    let swarm = FireflySwarm::initialize_at_point(
-        problem,
-        seed_generator,
-        options,
-        starting_point,
-    );
-
-    do_one_firefly_optimization_run_with_swarm(
-        problem_global_minimum,
-        options,
-        swarm,
-        multi_progress_bar,
-        run_type,
-    )
-}
-
-fn do_one_firefly_optimization_run(
-    problem: &mut BBOBProblem,
-    options: &FireflyRunOptions,
-    seed_generator: &mut UniformU8RandomGenerator,
-    multi_progress_bar: &FireflyOptimizationMultiProgressBar,
-    run_type: OptimizationRunType,
-) -> Result<FireflySingleRunOptimizationResult> {
-    let problem_global_minimum = problem.name.global_minimum();
-
-    let random_swarm =
-        FireflySwarm::initialize_random(problem, seed_generator, options);
-
-    do_one_firefly_optimization_run_with_swarm(
-        problem_global_minimum,
-        options,
-        random_swarm,
-        multi_progress_bar,
-        run_type,
-    )
-}
-
-
-fn do_one_firefly_optimization_run_with_swarm(
-    problem_global_minimum: f64,
-    options: &FireflyRunOptions,
     mut swarm: FireflySwarm,
-    multi_progress_bar: &FireflyOptimizationMultiProgressBar,
-    run_type: OptimizationRunType,
+    progress: &mut FireflyOptimizationProgress,
+    mut checkpoint: Option<&mut CheckpointRecorder<C>>,
+    observer: &mut dyn OptimizationObserver,
 ) -> Result<FireflySingleRunOptimizationResult> {
-    // Set up progress bar for this run.
-    let progress_bar = multi_progress_bar.new_run(run_type, options)?;
-    progress_bar.start();
+    // A resumed swarm continues the run it was saved in.
+    observer.on_restart(&RunStart {
+        run_type,
+        maximum_iterations: options.maximum_iterations,
+        stuck_after_iterations: Some(options.consider_stuck_after_n_iterations),
+        reheat_after_iterations: Some(
+            options.movement_jitter_min_stuck_runs_to_reheat,
+        ),
+        iterations_performed: swarm.iterations_performed,
+    })?;
+
+    let mut interrupted = false;
+    let mut stopping_criterion_met = false;
+
+    while swarm.iterations_performed < options.maximum_iterations {
+        // Perform a single iteration of the run.
+        swarm.perform_iteration();
 
-    let mut iterations_performed: usize = 0;
+        // Report the iteration (progress bars, trajectory, ...).
+        let (population_mean, population_std) =
+            swarm.objective_value_mean_and_std();
+        let iteration = IterationStatus {
+            iteration: swarm.iterations_performed,
+            evaluations: swarm.evaluations(),
+            best_value: swarm
+                .current_best_solution
+                .as_ref()
+                .expect("BUG: Invalid swarm, no solution!")
+                .value,
+            iterations_since_improvement: swarm.iterations_since_improvement,
+            jitter: Some(swarm.current_movement_jitter_coefficient),
+            temperature: None,
+            population_mean: Some(population_mean),
+            population_std: Some(population_std),
+        };
 
-    for _ in 0..options.maximum_iterations {
-        iterations_performed += 1;
+        observer.on_iteration(&iteration)?;
+        if swarm.iterations_since_improvement == 0 {
+            observer.on_improvement(&iteration)?;
+        }
 
-        // Perform a single iteration of the run.
-        swarm.perform_iteration();
+        // Stop the entire optimization if its stopping criterion is met.
+        if let Some(stopping) = progress.stopping.as_mut() {
+            stopping_criterion_met = stopping.should_stop(&OptimizationStatus {
+                evaluations: iteration.evaluations,
+                best_value: iteration.best_value,
+                population_diversity: Some(swarm.position_diversity()),
+            });
 
-        // Update progress bar.
-        progress_bar.update(iterations_performed, options, &swarm);
+            if stopping_criterion_met {
+                break;
+            }
+        }
 
         // If stuck for `consider_stuck_after_runs` or more iterations, abort the run.
         if swarm.iterations_since_improvement
@@ -115,113 +105,186 @@ fn do_one_firefly_optimization_run_with_swarm(
         {
             break;
         }
+
+        // On Ctrl-C, stop at this iteration boundary (after saving a checkpoint to resume from).
+        interrupted = is_interrupt_requested();
+
+        // Save a checkpoint of the entire optimization, if requested and due.
+        if let Some(checkpoint) = checkpoint.as_deref_mut() {
+            if checkpoint.is_due() || interrupted {
+                checkpoint.save(
+                    &FireflyOptimizationState {
+                        progress: progress.clone(),
+                        swarm: swarm.state(),
+                        evaluations: swarm.evaluations(),
+                        noise_samples_drawn: swarm.noise_samples_drawn(),
+                    },
+                    observer,
+                )?;
+            }
+        }
+
+        if interrupted {
+            break;
+        }
     }
 
+    let iterations_performed = swarm.iterations_performed;
     let best_solution = swarm
         .current_best_solution
         .expect("BUG: Invalid swarm, no solution!");
 
-    // Clean up progress bar.
-    progress_bar.finish(
-        iterations_performed,
-        best_solution.value,
-        problem_global_minimum,
-        options,
-    )?;
-
     Ok(FireflySingleRunOptimizationResult::new(
         iterations_performed,
         best_solution,
+        interrupted,
+        stopping_criterion_met,
     ))
 }
 
-pub fn run_firefly_swarm_optimization(
+/// Runs the full firefly optimization (all restarts and refinement runs) on `problem`.
+///
+/// If `warm_start` is given, the first restart starts with the entire swarm at that point
+/// (and the point counts as the best solution so far) instead of at random positions.
+///
+/// If `resume_from` is given, the optimization continues bit-exactly from that saved state
+/// (which must have been saved with the same `options`). If `checkpoint` is given,
+/// the state is periodically saved into it.
+///
+/// On Ctrl-C (see `install_interrupt_handler`), the optimization stops at the next iteration boundary
+/// and returns the best solution found so far (marked as `interrupted`).
+/// The same happens once `FullFireflyOptions::stopping_criterion` is met
+/// (marked as `stopping_criterion_met`).
+///
+/// Progress (e.g. for progress bars or trajectories) is reported to the `observer`.
+pub fn run_firefly_swarm_optimization<C: Serialize>(
     mut problem: BBOBProblem,
     options: FullFireflyOptions,
+    warm_start: Option<&[f64]>,
+    resume_from: Option<FireflyOptimizationState>,
+    mut checkpoint: Option<&mut CheckpointRecorder<C>>,
+    observer: &mut dyn OptimizationObserver,
 ) -> Result<FireflyFullOptimizationResult> {
-    // Set up progress bar for this optimization run.
-    let multi_progress_bar = FireflyOptimizationMultiProgressBar::new();
+    observer.on_start(problem.name.into())?;
 
     // TODO We could merge the firefly algorithm with the multi-swarm optimization strategy (multiple independent swarms)
     //      See https://en.wikipedia.org/wiki/Multi-swarm_optimization
 
-    // Parse options, initialize random generator and perform runs.
-    let total_restarts = options.per_restart_options.len();
+    let problem_global_minimum = problem.name.global_minimum();
+
+    // Initialize the random generator and best solution or restore them from the saved state.
+    let (mut progress, mut resumed_swarm) = match resume_from {
+        Some(state) => {
+            problem.restore_evaluations(state.evaluations);
+            problem.restore_noise_samples_drawn(
+                state.noise_samples_drawn.unwrap_or(state.evaluations),
+            );
+            (state.progress, Some(state.swarm))
+        }
+        None => {
+            // Integer variables of the warm start point are rounded on mixed-integer problems.
+            let best_solution = warm_start.map(|warm_start_point| {
+                let mut position = warm_start_point.to_vec();
+                problem.round_integer_variables(&mut position);
+
+                PointValue {
+                    value: problem.evaluate(&position),
+                    boundary_penalty: 0f64,
+                    constraint_violation: problem
+                        .constraint_violation(&position),
+                    position,
+                }
+            });
+
+            (
+                FireflyOptimizationProgress::new(
+                    options.random_generator_seed,
+                    best_solution,
+                    options.stopping_criterion.clone().map(|criterion| {
+                        StoppingTracker::new(criterion, problem_global_minimum)
+                    }),
+                ),
+                None,
+            )
+        }
+    };
 
-    let mut seed_generator =
-        UniformU8RandomGenerator::new(options.random_generator_seed);
+    let mut interrupted = false;
+    let mut stopping_criterion_met = false;
 
-    let mut best_solution: Option<PointValue> = None;
-    let mut iterations_performed_per_restart: Vec<usize> =
-        Vec::with_capacity(options.per_restart_options.len());
+    // Perform all independent runs (restarts), followed by the refinement runs
+    // (if `post_process_best_options` is specified), which start at the best solution so far.
+    while let Some((run_options, run_type)) = progress.next_run(&options) {
+        let swarm = match (resumed_swarm.take(), run_type) {
+            (Some(swarm_state), _) => {
+                FireflySwarm::restore(&mut problem, run_options, swarm_state)
+            }
+            (None, OptimizationRunType::Exploration { run_number, .. }) => {
+                match warm_start {
+                    Some(warm_start_point) if run_number == 1 => {
+                        FireflySwarm::initialize_at_point(
+                            &mut problem,
+                            &mut progress.seed_generator,
+                            run_options,
+                            warm_start_point.to_vec(),
+                        )
+                    }
+                    _ => FireflySwarm::initialize_random(
+                        &mut problem,
+                        &mut progress.seed_generator,
+                        run_options,
+                    ),
+                }
+            }
+            (None, OptimizationRunType::Refinement { .. }) => {
+                let best_position = progress
+                    .best_solution
+                    .as_ref()
+                    .expect("Invalid firefly optimization run: no solution!")
+                    .position
+                    .clone();
+
+                FireflySwarm::initialize_at_point(
+                    &mut problem,
+                    &mut progress.seed_generator,
+                    run_options,
+                    best_position,
+                )
+            }
+        };
 
-    // Perform `restart_count` independent runs (restarts).
-    for (run_index, run_options) in
-        options.per_restart_options.iter().enumerate()
-    {
-        let run_result = do_one_firefly_optimization_run(
-            &mut problem,
+        let run_result = do_one_firefly_optimization_run_with_swarm(
             run_options,
-            &mut seed_generator,
-            &multi_progress_bar,
-            OptimizationRunType::Exploration {
-                run_number: run_index + 1,
-                total_runs: total_restarts,
-            },
+            run_type,
+            swarm,
+            &mut progress,
+            checkpoint.as_deref_mut(),
+            observer,
         )?;
 
-        iterations_performed_per_restart.push(run_result.iterations_performed);
+        interrupted = run_result.interrupted;
+        stopping_criterion_met = run_result.stopping_criterion_met;
+        progress.finish_run(&run_type, run_result);
 
-        if let Some(full_best_solution) = best_solution.as_ref() {
-            if run_result.minimum.value < full_best_solution.value {
-                best_solution = Some(run_result.minimum);
-            }
-        } else {
-            best_solution = Some(run_result.minimum);
+        if interrupted || stopping_criterion_met {
+            break;
         }
     }
 
-    let best_solution =
-        best_solution.expect("Invalid firefly optimization run: no solution!");
+    let final_optimization_solution: Minimum = progress
+        .best_solution
+        .expect("Invalid firefly optimization run: no solution!")
+        .into();
 
-    // If `post_process_best_options` is specified, perform refinement runs on the best solution so far,
-    // updating the previous best if we found something better.
-    let final_optimization_solution: Minimum =
-        if let Some(post_processing_options) = options.post_process_best_options
-        {
-            let total_postprocessing_runs = post_processing_options.len();
-            let mut best_so_far = best_solution;
-
-            for (post_processing_run_index, post_processing_options) in
-                post_processing_options.into_iter().enumerate()
-            {
-                let run_result =
-                    do_one_firefly_optimization_run_with_starting_point(
-                        &mut problem,
-                        &post_processing_options,
-                        &mut seed_generator,
-                        &multi_progress_bar,
-                        best_so_far.position.clone(),
-                        OptimizationRunType::Refinement {
-                            run_number: post_processing_run_index + 1,
-                            total_runs: total_postprocessing_runs,
-                            best_value_before_refinement: best_so_far.value,
-                        },
-                    )?;
-
-                if run_result.minimum.value < best_so_far.value {
-                    best_so_far = run_result.minimum;
-                }
-            }
-
-            best_so_far.into()
-        } else {
-            best_solution.into()
-        };
+    observer.on_finish(final_optimization_solution.value)?;
 
     // Return the final result.
     Ok(FireflyFullOptimizationResult::new(
-        iterations_performed_per_restart,
+        progress.iterations_performed_per_restart,
         final_optimization_solution,
+        problem.evaluations(),
+        interrupted,
+        stopping_criterion_met,
+        problem.evaluation_cache_statistics(),
     ))
 }
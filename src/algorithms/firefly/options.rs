@@ -1,10 +1,15 @@
 use rand::distributions::{Distribution, Uniform};
 use rand::SeedableRng;
 use rand_pcg::{Mcg128Xsl64, Pcg64Mcg};
+use serde::Serialize;
 
+use crate::algorithms::common::boundary::BoundaryHandling;
+use crate::algorithms::common::constraints::ConstraintHandling;
+use crate::algorithms::common::initialization::InitializationStrategy;
+use crate::algorithms::common::stopping::StoppingCriterion;
 use crate::core::functions::BBOBFunctionType;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FullFireflyOptions {
     /// Main random generator seed. This is used to generate other seeds
     /// used in various parts of the firefly algorithm.
@@ -14,11 +19,14 @@ pub struct FullFireflyOptions {
     pub per_restart_options: Vec<FireflyRunOptions>,
 
     pub post_process_best_options: Option<Vec<FireflyRunOptions>>,
+
+    /// Criterion for stopping the entire optimization early (all restarts and refinement runs).
+    pub stopping_criterion: Option<StoppingCriterion>,
 }
 
 /// References:
 ///  - [1: Firefly Algorithm: Recent Advances and Applications](https://arxiv.org/abs/1308.3898)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct FireflyRunOptions {
     /// Specified the amount of fireflies in the swarm. In FA, the swarm size is constant.
     /// According to [1], the optimal swarm size is between 15 to 100 (or 25 to 40).
@@ -67,6 +75,16 @@ pub struct FireflyRunOptions {
     /// Upper bound for the movement jitter coefficient, meaning the heating factor
     /// cannot heat the jitter more than this.
     pub movement_jitter_maximum_coefficient: f64,
+
+    /// How the initial positions of the swarm are chosen.
+    pub initialization: InitializationStrategy,
+
+    /// How firefly moves that leave the problem bounds are handled.
+    pub boundary_handling: BoundaryHandling,
+
+    /// How the brightness of fireflies violating the constraints is compared
+    /// (constrained problems only).
+    pub constraint_handling: ConstraintHandling,
 }
 
 #[allow(dead_code)]
@@ -171,6 +189,36 @@ impl FireflyRunOptions {
             ..self
         }
     }
+
+    pub fn with_initialization(
+        self,
+        initialization: InitializationStrategy,
+    ) -> Self {
+        Self {
+            initialization,
+            ..self
+        }
+    }
+
+    pub fn with_boundary_handling(
+        self,
+        boundary_handling: BoundaryHandling,
+    ) -> Self {
+        Self {
+            boundary_handling,
+            ..self
+        }
+    }
+
+    pub fn with_constraint_handling(
+        self,
+        constraint_handling: ConstraintHandling,
+    ) -> Self {
+        Self {
+            constraint_handling,
+            ..self
+        }
+    }
 }
 
 impl Default for FireflyRunOptions {
@@ -187,6 +235,9 @@ impl Default for FireflyRunOptions {
             movement_jitter_heating_factor: 1.1,
             movement_jitter_minimum_coefficient: 0.005,
             movement_jitter_maximum_coefficient: 0.5,
+            initialization: InitializationStrategy::Uniform,
+            boundary_handling: BoundaryHandling::Clamp,
+            constraint_handling: ConstraintHandling::FeasibilityRules,
         }
     }
 }
@@ -335,6 +386,9 @@ fn mutate_each_option_as_duplicate(
                 option.movement_jitter_maximum_coefficient,
                 &mut preprocessing_random_generator,
             ),
+            initialization: option.initialization,
+            boundary_handling: option.boundary_handling,
+            constraint_handling: option.constraint_handling,
         })
         .collect();
 
@@ -357,6 +411,9 @@ pub fn get_optimized_hyperparameters(
         movement_jitter_heating_factor: 1.01,
         movement_jitter_minimum_coefficient: 0.005,
         movement_jitter_maximum_coefficient: 0.115,
+        initialization: InitializationStrategy::Uniform,
+        boundary_handling: BoundaryHandling::Clamp,
+        constraint_handling: ConstraintHandling::FeasibilityRules,
     };
 
     let base_postprocessing_run_high_jitter = FireflyRunOptions {
@@ -371,6 +428,9 @@ pub fn get_optimized_hyperparameters(
         movement_jitter_heating_factor: 1.01,
         movement_jitter_minimum_coefficient: 0.05,
         movement_jitter_maximum_coefficient: 0.6,
+        initialization: InitializationStrategy::Uniform,
+        boundary_handling: BoundaryHandling::Clamp,
+        constraint_handling: ConstraintHandling::FeasibilityRules,
     };
 
     let base_postprocessing_run_low_jitter = FireflyRunOptions {
@@ -385,6 +445,9 @@ pub fn get_optimized_hyperparameters(
         movement_jitter_heating_factor: 1.0001,
         movement_jitter_minimum_coefficient: 0.0002,
         movement_jitter_maximum_coefficient: 0.01,
+        initialization: InitializationStrategy::Uniform,
+        boundary_handling: BoundaryHandling::Clamp,
+        constraint_handling: ConstraintHandling::FeasibilityRules,
     };
 
     let full_defaults = FullFireflyOptions {
@@ -398,6 +461,7 @@ pub fn get_optimized_hyperparameters(
             base_postprocessing_run_high_jitter,
             base_postprocessing_run_low_jitter,
         ]),
+        stopping_criterion: None,
     };
 
     match problem {
@@ -419,11 +483,15 @@ pub fn get_optimized_hyperparameters(
                 movement_jitter_heating_factor: 1.0001,
                 movement_jitter_minimum_coefficient: 0.0004,
                 movement_jitter_maximum_coefficient: 0.01,
+                initialization: InitializationStrategy::Uniform,
+                boundary_handling: BoundaryHandling::Clamp,
+                constraint_handling: ConstraintHandling::FeasibilityRules,
             }],
             post_process_best_options: Some(vec![
                 base_postprocessing_run_high_jitter,
                 base_postprocessing_run_low_jitter,
             ]),
+            stopping_criterion: None,
         },
         // NOT OK (delta=516.37660)
         BBOBFunctionType::Rastrigin => full_defaults,
@@ -484,6 +552,9 @@ pub fn get_optimized_hyperparameters(
                     movement_jitter_heating_factor: 1.008,
                     movement_jitter_minimum_coefficient: 0.009,
                     movement_jitter_maximum_coefficient: 0.6,
+                    initialization: InitializationStrategy::Uniform,
+                    boundary_handling: BoundaryHandling::Clamp,
+                    constraint_handling: ConstraintHandling::FeasibilityRules,
                 }),
                 PREPROCESSING_RNG_SEED,
                 Some(0.09),
@@ -492,6 +563,7 @@ pub fn get_optimized_hyperparameters(
                 base_postprocessing_run_high_jitter,
                 base_postprocessing_run_low_jitter,
             ]),
+            stopping_criterion: None,
         },
     }
 }
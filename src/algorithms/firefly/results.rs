@@ -1,5 +1,6 @@
 use crate::algorithms::common::structs::Minimum;
 use crate::algorithms::firefly::utilities::PointValue;
+use crate::core::cache::EvaluationCacheStatistics;
 
 pub struct FireflyFullOptimizationResult {
     // Iterations performed per-restart.
@@ -7,6 +8,18 @@ pub struct FireflyFullOptimizationResult {
 
     // Minimum found throughout all restarts.
     pub minimum: Minimum,
+
+    // Objective function evaluations performed throughout all runs.
+    pub evaluations_performed: u64,
+
+    // Whether the optimization was interrupted (Ctrl-C) before performing all runs.
+    pub interrupted: bool,
+
+    // Whether the optimization stopped early because its stopping criterion was met.
+    pub stopping_criterion_met: bool,
+
+    // Hit and miss counts of the evaluation cache, if it was enabled.
+    pub evaluation_cache_statistics: Option<EvaluationCacheStatistics>,
 }
 
 impl FireflyFullOptimizationResult {
@@ -14,10 +27,18 @@ impl FireflyFullOptimizationResult {
     pub fn new(
         iterations_performed_per_restart: Vec<usize>,
         minimum: Minimum,
+        evaluations_performed: u64,
+        interrupted: bool,
+        stopping_criterion_met: bool,
+        evaluation_cache_statistics: Option<EvaluationCacheStatistics>,
     ) -> Self {
         Self {
             iterations_performed_per_restart,
             minimum,
+            evaluations_performed,
+            interrupted,
+            stopping_criterion_met,
+            evaluation_cache_statistics,
         }
     }
 }
@@ -25,13 +46,26 @@ impl FireflyFullOptimizationResult {
 pub struct FireflySingleRunOptimizationResult {
     pub iterations_performed: usize,
     pub minimum: PointValue,
+
+    /// Whether the run was interrupted (Ctrl-C) before finishing.
+    pub interrupted: bool,
+
+    /// Whether the run stopped because the stopping criterion of the optimization was met.
+    pub stopping_criterion_met: bool,
 }
 
 impl FireflySingleRunOptimizationResult {
-    pub fn new(iterations_performed: usize, minimum: PointValue) -> Self {
+    pub fn new(
+        iterations_performed: usize,
+        minimum: PointValue,
+        interrupted: bool,
+        stopping_criterion_met: bool,
+    ) -> Self {
         Self {
             iterations_performed,
             minimum,
+            interrupted,
+            stopping_criterion_met,
         }
     }
 }
@@ -1,7 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::constraints::{
+    ConstrainedValue,
+    ConstraintHandler,
+};
+use crate::algorithms::common::initialization::initialize_population;
 use crate::algorithms::common::rng::{
     UniformF64BoundedRandomGenerator,
     UniformU8RandomGenerator,
 };
+use crate::algorithms::common::trajectory::mean_and_standard_deviation;
 use crate::algorithms::firefly::individual_firefly::Firefly;
 use crate::algorithms::firefly::utilities::PointValue;
 use crate::algorithms::firefly::FireflyRunOptions;
@@ -18,12 +26,36 @@ pub struct FireflySwarm<'p: 'pref, 'pref, 'options> {
     /// Vector of fireflies - this is the swarm.
     fireflies: Vec<Firefly>,
 
+    /// Compares the brightness of fireflies on constrained problems
+    /// (see `FireflyRunOptions::constraint_handling`).
+    constraint_handler: ConstraintHandler,
+
     /// Current best solution from all iterations up to this point.
     pub current_best_solution: Option<PointValue>,
 
     pub current_movement_jitter_coefficient: f64,
 
     pub iterations_since_improvement: usize,
+
+    /// Iterations performed on this swarm so far.
+    pub iterations_performed: usize,
+}
+
+/// Full state of a `FireflySwarm` (everything except the problem and options), saved in checkpoints.
+#[derive(Serialize, Deserialize)]
+pub struct FireflySwarmState {
+    minus_half_to_half_uniform_generator: UniformF64BoundedRandomGenerator,
+    fireflies: Vec<Firefly>,
+
+    /// Missing in checkpoints saved before constraint handling was added
+    /// (a new handler is then created from the options).
+    #[serde(default)]
+    constraint_handler: Option<ConstraintHandler>,
+
+    current_best_solution: Option<PointValue>,
+    current_movement_jitter_coefficient: f64,
+    iterations_since_improvement: usize,
+    iterations_performed: usize,
 }
 
 impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
@@ -33,36 +65,35 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
         seed_generator: &mut UniformU8RandomGenerator,
         options: &'options FireflyRunOptions,
     ) -> Self {
-        let input_dimensions = problem.input_dimensions;
-
-        // Generate seeds and RNGs for in-bounds and -5-to-5 random generators (using the main seed).
-        let mut in_bounds_uniform_generator =
-            UniformF64BoundedRandomGenerator::new(
-                problem.bounds(),
-                seed_generator.sample_multiple::<16>(),
-            );
+        // Generate seeds for the initial population and the -0.5-to-0.5 random generator
+        // (using the main seed).
+        let initialization_seed = seed_generator.sample_multiple::<16>();
 
         let minus_half_to_half_uniform_generator =
             UniformF64BoundedRandomGenerator::new(
-                Bounds::new(-0.5f64, 0.5f64),
+                Bounds::uniform(-0.5f64, 0.5f64, 1),
                 seed_generator.sample_multiple::<16>(),
             );
 
         // Generate initial population
-        let mut fireflies: Vec<Firefly> = (0..options.swarm_size)
-            .map(|_| {
-                let initial_position: Vec<f64> = in_bounds_uniform_generator
-                    .sample_multiple(input_dimensions);
-
-                Firefly::new(initial_position, problem)
-            })
-            .collect();
-
-        fireflies.sort_unstable_by(|first, second| {
-            second
-                .objective_function_value
-                .total_cmp(&first.objective_function_value)
-        });
+        let mut fireflies: Vec<Firefly> = initialize_population(
+            problem,
+            options.initialization,
+            options.swarm_size,
+            initialization_seed,
+        )
+        .into_iter()
+        .map(|initial_state| Firefly {
+            position: initial_state.vector,
+            objective_function_value: initial_state.objective_value,
+            boundary_penalty: 0f64,
+            constraint_violation: initial_state.constraint_violation,
+        })
+        .collect();
+
+        let constraint_handler =
+            ConstraintHandler::new(options.constraint_handling);
+        sort_by_brightness(&mut fireflies, &constraint_handler);
 
         Self {
             problem,
@@ -70,9 +101,11 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
             current_best_solution: None,
             options,
             fireflies,
+            constraint_handler,
             current_movement_jitter_coefficient: options
                 .movement_jitter_starting_coefficient,
             iterations_since_improvement: 0,
+            iterations_performed: 0,
         }
     }
 
@@ -84,7 +117,7 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
     ) -> Self {
         let minus_half_to_half_uniform_generator =
             UniformF64BoundedRandomGenerator::new(
-                Bounds::new(-0.5f64, 0.5f64),
+                Bounds::uniform(-0.5f64, 0.5f64, 1),
                 seed_generator.sample_multiple::<16>(),
             );
 
@@ -92,11 +125,9 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
             .map(|_| Firefly::new(initial_point.clone(), problem))
             .collect();
 
-        fireflies.sort_unstable_by(|first, second| {
-            second
-                .objective_function_value
-                .total_cmp(&first.objective_function_value)
-        });
+        let constraint_handler =
+            ConstraintHandler::new(options.constraint_handling);
+        sort_by_brightness(&mut fireflies, &constraint_handler);
 
         Self {
             problem,
@@ -104,56 +135,150 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
             current_best_solution: None,
             options,
             fireflies,
+            constraint_handler,
             current_movement_jitter_coefficient: options
                 .movement_jitter_starting_coefficient,
             iterations_since_improvement: 0,
+            iterations_performed: 0,
         }
     }
 
-    #[inline]
-    fn is_better_than_minimum(&self, value: f64) -> bool {
-        self.current_best_solution.is_none()
-            || value < self.current_best_solution.as_ref().unwrap().value
+    /// Restores a swarm from a saved `state` (see `FireflySwarm::state`).
+    pub fn restore(
+        problem: &'pref mut BBOBProblem<'p>,
+        options: &'options FireflyRunOptions,
+        state: FireflySwarmState,
+    ) -> Self {
+        Self {
+            problem,
+            minus_half_to_half_uniform_generator: state
+                .minus_half_to_half_uniform_generator,
+            options,
+            fireflies: state.fireflies,
+            constraint_handler: state.constraint_handler.unwrap_or_else(|| {
+                ConstraintHandler::new(options.constraint_handling)
+            }),
+            current_best_solution: state.current_best_solution,
+            current_movement_jitter_coefficient: state
+                .current_movement_jitter_coefficient,
+            iterations_since_improvement: state.iterations_since_improvement,
+            iterations_performed: state.iterations_performed,
+        }
     }
 
+    /// Returns the full state of the swarm, from which it can be restored bit-exactly.
+    pub fn state(&self) -> FireflySwarmState {
+        FireflySwarmState {
+            minus_half_to_half_uniform_generator: self
+                .minus_half_to_half_uniform_generator
+                .clone(),
+            fireflies: self.fireflies.clone(),
+            constraint_handler: Some(self.constraint_handler.clone()),
+            current_best_solution: self.current_best_solution.clone(),
+            current_movement_jitter_coefficient: self
+                .current_movement_jitter_coefficient,
+            iterations_since_improvement: self.iterations_since_improvement,
+            iterations_performed: self.iterations_performed,
+        }
+    }
+
+    /// Total objective function evaluations performed on the problem so far.
+    pub fn evaluations(&self) -> u64 {
+        self.problem.evaluations()
+    }
+
+    /// Noise samples drawn on the problem so far (`None` on noiseless problems).
+    pub fn noise_samples_drawn(&self) -> Option<u64> {
+        self.problem.noise_samples_drawn()
+    }
+
+    /// Mean and standard deviation of the objective values of all fireflies.
+    pub fn objective_value_mean_and_std(&self) -> (f64, f64) {
+        let objective_values: Vec<f64> = self
+            .fireflies
+            .iter()
+            .map(|firefly| firefly.objective_function_value)
+            .collect();
+
+        mean_and_standard_deviation(&objective_values)
+    }
+
+    /// Mean Euclidean distance of the fireflies from the centroid of the swarm.
+    pub fn position_diversity(&self) -> f64 {
+        let swarm_size = self.fireflies.len() as f64;
+
+        let mut centroid = vec![0f64; self.problem.input_dimensions];
+        for firefly in &self.fireflies {
+            for (centroid_value, value) in
+                centroid.iter_mut().zip(firefly.position.iter())
+            {
+                *centroid_value += value / swarm_size;
+            }
+        }
+
+        self.fireflies
+            .iter()
+            .map(|firefly| {
+                firefly
+                    .position
+                    .iter()
+                    .zip(centroid.iter())
+                    .map(|(value, centroid_value)| {
+                        (value - centroid_value).powi(2)
+                    })
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .sum::<f64>()
+            / swarm_size
+    }
+
+    /// Whether `value` is better than the best solution so far (by Deb's feasibility rules,
+    /// regardless of the constraint handling used to compare the fireflies).
     #[inline]
-    fn update_minimum_value_unchecked(
-        &mut self,
-        value: f64,
-        position: Vec<f64>,
-    ) {
-        self.current_best_solution = Some(PointValue::new(position, value));
+    fn is_better_than_minimum(&self, value: &ConstrainedValue) -> bool {
+        match self.current_best_solution.as_ref() {
+            Some(best_solution) => {
+                value.is_better_than(&best_solution.constrained_value())
+            }
+            None => true,
+        }
     }
 
+
     pub fn perform_iteration(&mut self) {
         assert_eq!(self.fireflies.len(), self.options.swarm_size);
 
         // Whether a better (smaller) value than the current best has been found in this iteration.
         let mut has_found_better = false;
 
-        let mut new_firefly_swarm: Vec<Firefly> =
-            Vec::with_capacity(self.fireflies.len());
+        // Buffer for the new positions of moving fireflies (see `Firefly::move_towards`).
+        let mut candidate_position: Vec<f64> =
+            Vec::with_capacity(self.problem.input_dimensions);
 
-        // For each firefly `new_main_firefly` in the swarm, compare it with each other firefly `brighter_firefly`.
+        // For each firefly `main_firefly` in the swarm, compare it with each other firefly `brighter_firefly`.
         // If `brighter_firefly` is brighter (i.e. more fit, smaller objective value (we're minimizing)),
-        // then `new_main_firefly` moves towards `brighter_firefly` (with some light falloff and other factors).
+        // then `main_firefly` moves towards `brighter_firefly` (with some light falloff and other factors).
 
         // Optimization: as we'd sorted the array previously, we skip all the worse fireflies.
+        // The fireflies are moved in place: each one only moves towards the ones after it,
+        // which haven't moved yet in this iteration.
 
         for main_firefly_index in 0..self.fireflies.len() {
-            let mut new_main_firefly =
-                self.fireflies[main_firefly_index].clone();
+            let (fireflies_up_to_main, brighter_fireflies) =
+                self.fireflies.split_at_mut(main_firefly_index + 1);
+            let main_firefly = &mut fireflies_up_to_main[main_firefly_index];
 
-            for brighter_firefly in
-                self.fireflies.iter().skip(main_firefly_index + 1)
-            {
+            for brighter_firefly in brighter_fireflies.iter() {
                 // The main firefly still moves, so all the fireflies that were brighter at the start
                 // of the iteration might not always be brighter than the moving (main) firefly.
-                if brighter_firefly.objective_function_value
-                    < new_main_firefly.objective_function_value
+                if self
+                    .constraint_handler
+                    .is_better(&brighter_firefly.value(), &main_firefly.value())
                 {
-                    new_main_firefly.move_towards(
+                    main_firefly.move_towards(
                         brighter_firefly,
+                        &mut candidate_position,
                         self.problem,
                         &mut self.minus_half_to_half_uniform_generator,
                         self.current_movement_jitter_coefficient,
@@ -163,29 +288,25 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
             }
 
             // Update minimum value if improved.
-            if self.is_better_than_minimum(
-                new_main_firefly.objective_function_value,
-            ) {
-                self.update_minimum_value_unchecked(
-                    new_main_firefly.objective_function_value,
-                    new_main_firefly.position.clone(),
-                );
+            let main_firefly = &self.fireflies[main_firefly_index];
+            if self.is_better_than_minimum(&main_firefly.value()) {
+                self.current_best_solution = Some(main_firefly.point_value());
 
                 has_found_better = true;
             }
-
-            new_firefly_swarm.push(new_main_firefly);
         }
 
-        // Re-sort the swarm and update self.fireflies in preparation of the next iteration.
-        assert_eq!(new_firefly_swarm.len(), self.options.swarm_size);
-        new_firefly_swarm.sort_unstable_by(|first, second| {
-            second
-                .objective_function_value
-                .total_cmp(&first.objective_function_value)
-        });
+        // Re-sort the swarm in preparation of the next iteration.
+        assert_eq!(self.fireflies.len(), self.options.swarm_size);
+        sort_by_brightness(&mut self.fireflies, &self.constraint_handler);
 
-        self.fireflies = new_firefly_swarm;
+        // Adapt the constraint handling to the brightest firefly of this iteration.
+        if let Some(brightest_firefly) = self.fireflies.last() {
+            self.constraint_handler
+                .finish_iteration(&brightest_firefly.value());
+        }
+
+        self.iterations_performed += 1;
 
         if has_found_better {
             self.iterations_since_improvement = 0;
@@ -211,3 +332,14 @@ impl<'p: 'pref, 'pref, 'options> FireflySwarm<'p, 'pref, 'options> {
         }
     }
 }
+
+/// Sorts the `fireflies` from the dimmest to the brightest one
+/// (brightest meaning the best one by `constraint_handler`).
+fn sort_by_brightness(
+    fireflies: &mut [Firefly],
+    constraint_handler: &ConstraintHandler,
+) {
+    fireflies.sort_unstable_by(|first, second| {
+        constraint_handler.compare(&second.value(), &first.value())
+    });
+}
@@ -1,20 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::constraints::ConstrainedValue;
 use crate::algorithms::common::structs::Minimum;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PointValue {
     pub position: Vec<f64>,
     pub value: f64,
+
+    /// Penalty for leaving the bounds (see `BoundaryHandling::penalty`), only added
+    /// to `value` when comparing solutions, so `value` is always the objective value.
+    #[serde(default)]
+    pub boundary_penalty: f64,
+
+    /// Constraint violation at `position` (`0` if it is feasible or the problem is unconstrained).
+    #[serde(default)]
+    pub constraint_violation: f64,
 }
 
 impl PointValue {
     #[inline]
-    pub fn new(position: Vec<f64>, value: f64) -> Self {
-        Self { position, value }
+    pub fn new(
+        position: Vec<f64>,
+        value: f64,
+        constraint_violation: f64,
+    ) -> Self {
+        Self {
+            position,
+            value,
+            boundary_penalty: 0f64,
+            constraint_violation,
+        }
+    }
+
+    pub fn with_boundary_penalty(mut self, boundary_penalty: f64) -> Self {
+        self.boundary_penalty = boundary_penalty;
+        self
+    }
+
+    #[inline]
+    pub fn constrained_value(&self) -> ConstrainedValue {
+        ConstrainedValue::new(
+            self.value + self.boundary_penalty,
+            self.constraint_violation,
+        )
     }
 }
 
 impl From<PointValue> for Minimum {
     fn from(point: PointValue) -> Self {
-        Minimum::new(point.value, point.position)
+        Minimum::new(
+            point.value,
+            point.position,
+            point.constraint_violation,
+        )
     }
 }
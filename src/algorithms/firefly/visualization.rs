@@ -4,11 +4,19 @@ use colored::Colorize;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use miette::{IntoDiagnostic, Result};
 
-use crate::algorithms::firefly::swarm::FireflySwarm;
-use crate::algorithms::firefly::{FireflyRunOptions, OptimizationRunType};
-
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    ObservedFunction,
+    OptimizationObserver,
+    OptimizationRunType,
+    RunStart,
+};
+
+/// Observer showing a progress bar for each run (restart or refinement run) of the optimization.
 pub struct FireflyOptimizationMultiProgressBar {
     multi_bar: MultiProgress,
+    global_minimum: f64,
+    current_run: Option<FireflySingleRunProgressBar>,
 }
 
 impl FireflyOptimizationMultiProgressBar {
@@ -16,19 +24,50 @@ impl FireflyOptimizationMultiProgressBar {
     pub fn new() -> Self {
         Self {
             multi_bar: MultiProgress::new(),
+            global_minimum: 0f64,
+            current_run: None,
+        }
+    }
+
+    fn finish_current_run(&mut self) -> Result<()> {
+        match self.current_run.take() {
+            Some(run) => run.finish(self.global_minimum),
+            None => Ok(()),
+        }
+    }
+}
+
+impl OptimizationObserver for FireflyOptimizationMultiProgressBar {
+    fn on_start(&mut self, function: ObservedFunction) -> Result<()> {
+        // Only single-objective functions are optimized with the firefly algorithm.
+        self.global_minimum = function.global_minimum().unwrap_or_default();
+        Ok(())
+    }
+
+    fn on_restart(&mut self, run: &RunStart) -> Result<()> {
+        self.finish_current_run()?;
+
+        let run_progress_bar =
+            FireflySingleRunProgressBar::from_multi_progress_bar(
+                &self.multi_bar,
+                run,
+            )?;
+        run_progress_bar.start();
+
+        self.current_run = Some(run_progress_bar);
+        Ok(())
+    }
+
+    fn on_iteration(&mut self, iteration: &IterationStatus) -> Result<()> {
+        if let Some(run) = self.current_run.as_mut() {
+            run.update(iteration);
         }
+
+        Ok(())
     }
 
-    pub fn new_run(
-        &self,
-        run_type: OptimizationRunType,
-        options: &FireflyRunOptions,
-    ) -> Result<FireflySingleRunProgressBar> {
-        FireflySingleRunProgressBar::from_multi_progress_bar(
-            &self.multi_bar,
-            run_type,
-            options,
-        )
+    fn on_finish(&mut self, _best_value: f64) -> Result<()> {
+        self.finish_current_run()
     }
 }
 
@@ -36,15 +75,20 @@ impl FireflyOptimizationMultiProgressBar {
 pub struct FireflySingleRunProgressBar {
     progress_bar: ProgressBar,
     run_type: OptimizationRunType,
+    maximum_iterations: usize,
+    stuck_after_iterations: Option<usize>,
+    reheat_after_iterations: Option<usize>,
+
+    /// Iterations performed and best value of the run after the last update.
+    last_update: Option<(usize, f64)>,
 }
 
 impl FireflySingleRunProgressBar {
     pub fn from_multi_progress_bar(
         multi_progress_bar: &MultiProgress,
-        run_type: OptimizationRunType,
-        options: &FireflyRunOptions,
+        run: &RunStart,
     ) -> Result<Self> {
-        let running_style = match run_type {
+        let running_style = match run.run_type {
             OptimizationRunType::Exploration { run_number, total_runs } => {
                 ProgressStyle::with_template(&format!(
                     "[ {} | run {}/{}] |{{bar:40}}| iteration {{pos}}/{{len}}: {{msg}}",
@@ -62,13 +106,18 @@ impl FireflySingleRunProgressBar {
         };
 
         let progress_bar = multi_progress_bar.add(
-            ProgressBar::new(options.maximum_iterations as u64)
+            ProgressBar::new(run.maximum_iterations as u64)
                 .with_style(running_style),
         );
+        progress_bar.set_position(run.iterations_performed as u64);
 
         Ok(Self {
             progress_bar,
-            run_type,
+            run_type: run.run_type,
+            maximum_iterations: run.maximum_iterations,
+            stuck_after_iterations: run.stuck_after_iterations,
+            reheat_after_iterations: run.reheat_after_iterations,
+            last_update: None,
         })
     }
 
@@ -77,57 +126,58 @@ impl FireflySingleRunProgressBar {
             .enable_steady_tick(Duration::from_secs_f64(1f64 / 5f64));
     }
 
-    pub fn update(
-        &self,
-        iterations_performed: usize,
-        options: &FireflyRunOptions,
-        swarm: &FireflySwarm,
-    ) {
-        self.progress_bar.set_position(iterations_performed as u64);
+    pub fn update(&mut self, iteration: &IterationStatus) {
+        self.progress_bar.set_position(iteration.iteration as u64);
+        self.last_update = Some((iteration.iteration, iteration.best_value));
 
         let jitter_str = format!(
             "jitter={:.4}",
-            swarm.current_movement_jitter_coefficient,
+            iteration.jitter.unwrap_or(f64::NAN)
         );
         let iterations_since_improvement_str = format!(
             "iterations_since_improvement={:04}/{}",
-            swarm.iterations_since_improvement,
-            options.consider_stuck_after_n_iterations,
+            iteration.iterations_since_improvement,
+            self.stuck_after_iterations
+                .map_or("-".to_string(), |stuck_after| stuck_after
+                    .to_string()),
         );
 
         self.progress_bar.set_message(format!(
             "{} {} value={:.6}",
             // Colour the jitter value red when heating up and green when cooling down.
-            if swarm.iterations_since_improvement
-                > options.movement_jitter_min_stuck_runs_to_reheat
-            {
+            if matches!(
+                self.reheat_after_iterations,
+                Some(reheat_after) if iteration.iterations_since_improvement > reheat_after
+            ) {
                 jitter_str.bright_red()
             } else {
                 jitter_str.bright_green()
             },
             // Colour the stuck iteration count red when at 80%+ of run abort condition.
-            if swarm.iterations_since_improvement as f32
-                > (options.consider_stuck_after_n_iterations as f32 * 0.8)
-            {
+            if matches!(
+                self.stuck_after_iterations,
+                Some(stuck_after) if iteration.iterations_since_improvement as f32
+                    > (stuck_after as f32 * 0.8)
+            ) {
                 iterations_since_improvement_str.red()
             } else {
                 iterations_since_improvement_str.white()
             },
-            swarm
-                .current_best_solution
-                .as_ref()
-                .expect("BUG: Invalid swarm, no solution at all.")
-                .value,
+            iteration.best_value,
         ));
     }
 
-    pub fn finish(
-        &self,
-        iterations_performed: usize,
-        minimum_value: f64,
-        global_minimum: f64,
-        options: &FireflyRunOptions,
-    ) -> Result<()> {
+    pub fn finish(self, global_minimum: f64) -> Result<()> {
+        // The run continued from a checkpoint, but was already at its end.
+        let (iterations_performed, minimum_value) = match self.last_update {
+            Some(last_update) => last_update,
+            None => {
+                // The run continued from a checkpoint, but was already at its end.
+                self.progress_bar.finish_and_clear();
+                return Ok(());
+            }
+        };
+
         let finished_style =
             ProgressStyle::with_template("{msg}").into_diagnostic()?;
         self.progress_bar.set_style(finished_style);
@@ -143,7 +193,7 @@ impl FireflySingleRunProgressBar {
                     run_number,
                     total_runs,
                     iterations_performed,
-                    options.maximum_iterations,
+                    self.maximum_iterations,
                     minimum_value,
                     minimum_value - global_minimum
                 )
@@ -175,7 +225,7 @@ impl FireflySingleRunProgressBar {
                     run_number,
                     total_runs,
                     iterations_performed,
-                    options.maximum_iterations,
+                    self.maximum_iterations,
                     best_value_before_refinement,
                     updated_minimum_str,
                     best_value_before_refinement - global_minimum,
@@ -1,3 +1,4 @@
 pub mod simulated_annealing;
 pub mod firefly;
-pub mod common;
\ No newline at end of file
+pub mod common;
+pub mod nsga2;
\ No newline at end of file
@@ -0,0 +1,410 @@
+use miette::{miette, Result};
+use rand::distributions::Distribution;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+
+use crate::algorithms::common::initialization::{
+    generate_initial_points,
+    InitializationStrategy,
+};
+use crate::algorithms::common::interrupt::is_interrupt_requested;
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    ObservedFunction,
+    OptimizationObserver,
+    OptimizationRunType,
+    RunStart,
+};
+use crate::algorithms::common::rng::derive_seeds;
+use crate::algorithms::common::stopping::{
+    OptimizationStatus,
+    StoppingCriterion,
+    StoppingTracker,
+};
+use crate::core::pareto::{ObjectiveVector, ParetoArchive};
+use crate::core::problem::BiObjectiveProblem;
+
+mod operators;
+mod options;
+mod sorting;
+
+use operators::{polynomial_mutation, simulated_binary_crossover};
+pub use options::NSGA2Options;
+use sorting::{crowding_distances, non_dominated_fronts};
+
+struct Individual {
+    vector: Vec<f64>,
+    objectives: ObjectiveVector,
+
+    /// Index of the non-dominated front the individual is in (0 is the best one).
+    rank: usize,
+
+    crowding_distance: f64,
+}
+
+pub struct NSGA2Result {
+    /// All non-dominated solutions evaluated during the run
+    /// (not just the ones in the final population).
+    pub archive: ParetoArchive,
+
+    pub evaluations: u64,
+
+    pub generations_performed: usize,
+
+    /// Whether the run was stopped early by an interrupt (Ctrl+C).
+    pub interrupted: bool,
+
+    /// Whether the run was stopped early because its stopping criterion was met.
+    pub stopping_criterion_met: bool,
+}
+
+/// Nadir point of the `archive`, moved away from the front by 10 % of its extent
+/// (so the extreme solutions also contribute to the hypervolume).
+pub fn default_reference_point(archive: &ParetoArchive) -> ObjectiveVector {
+    match (archive.ideal_point(), archive.nadir_point()) {
+        (Some(ideal), Some(nadir)) => [0, 1].map(|objective| {
+            let extent = nadir[objective] - ideal[objective];
+            if extent > 0f64 {
+                nadir[objective] + 0.1 * extent
+            } else {
+                nadir[objective] + 1f64
+            }
+        }),
+        _ => [0f64; 2],
+    }
+}
+
+fn evaluate_into_archive(
+    problem: &mut BiObjectiveProblem,
+    archive: &mut ParetoArchive,
+    vector: Vec<f64>,
+) -> Individual {
+    let objectives = problem.evaluate(&vector);
+    archive.insert(&vector, objectives);
+
+    Individual {
+        vector,
+        objectives,
+        rank: 0,
+        crowding_distance: 0f64,
+    }
+}
+
+/// Keeps the best `population_size` of `individuals` (by rank, then by crowding distance)
+/// and updates their ranks and crowding distances.
+fn select_survivors(
+    mut individuals: Vec<Individual>,
+    population_size: usize,
+) -> Vec<Individual> {
+    let objectives: Vec<ObjectiveVector> = individuals
+        .iter()
+        .map(|individual| individual.objectives)
+        .collect();
+
+    let mut survivor_indices: Vec<usize> = Vec::with_capacity(population_size);
+    for (rank, front) in
+        non_dominated_fronts(&objectives).into_iter().enumerate()
+    {
+        let distances = crowding_distances(&objectives, &front);
+        for (index, distance) in front.iter().zip(distances) {
+            individuals[*index].rank = rank;
+            individuals[*index].crowding_distance = distance;
+        }
+
+        let remaining_places = population_size - survivor_indices.len();
+        if front.len() <= remaining_places {
+            survivor_indices.extend(front);
+        } else {
+            // The last front that fits only partially: prefer the less crowded individuals.
+            let mut front = front;
+            front.sort_by(|first, second| {
+                individuals[*second]
+                    .crowding_distance
+                    .total_cmp(&individuals[*first].crowding_distance)
+            });
+            survivor_indices.extend(&front[..remaining_places]);
+        }
+
+        if survivor_indices.len() == population_size {
+            break;
+        }
+    }
+
+    let mut individuals: Vec<Option<Individual>> =
+        individuals.into_iter().map(Some).collect();
+
+    survivor_indices
+        .into_iter()
+        .map(|index| {
+            individuals[index]
+                .take()
+                .expect("BUG: Individual selected twice.")
+        })
+        .collect()
+}
+
+/// Mean Euclidean distance of the individuals from the centroid of the population.
+fn position_diversity(population: &[Individual]) -> f64 {
+    let population_size = population.len() as f64;
+    let input_dimensions = population
+        .first()
+        .map(|individual| individual.vector.len())
+        .unwrap_or_default();
+
+    let mut centroid = vec![0f64; input_dimensions];
+    for individual in population {
+        for (centroid_value, value) in
+            centroid.iter_mut().zip(individual.vector.iter())
+        {
+            *centroid_value += value / population_size;
+        }
+    }
+
+    population
+        .iter()
+        .map(|individual| {
+            individual
+                .vector
+                .iter()
+                .zip(centroid.iter())
+                .map(|(value, centroid_value)| (value - centroid_value).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .sum::<f64>()
+        / population_size
+}
+
+/// Binary tournament: the individual with the better rank wins,
+/// ties are broken by the larger crowding distance.
+fn tournament_selection<'population, R: Rng>(
+    population: &'population [Individual],
+    rng: &mut R,
+) -> &'population Individual {
+    let first = &population[rng.gen_range(0..population.len())];
+    let second = &population[rng.gen_range(0..population.len())];
+
+    if first.rank != second.rank {
+        if first.rank < second.rank {
+            first
+        } else {
+            second
+        }
+    } else if first.crowding_distance >= second.crowding_distance {
+        first
+    } else {
+        second
+    }
+}
+
+/// Runs NSGA-II (Deb et al., 2002) with simulated binary crossover and polynomial mutation
+/// on `problem`. Every evaluated point is offered to the returned Pareto archive.
+///
+/// Progress is reported to `observer` as the hypervolume of the archive with respect to
+/// `reference_point` (by default the `default_reference_point` of the initial population),
+/// which stays fixed for the entire run. The `stopping_criterion` sees the negated hypervolume,
+/// so `Stagnation` stops the run once the hypervolume hasn't grown in that many evaluations.
+/// `TargetReached` is never met, as bi-objective functions have no single global minimum.
+///
+/// Stops after `options.generations` generations, once the stopping criterion is met
+/// or once an interrupt is requested.
+pub fn run_nsga2(
+    problem: &mut BiObjectiveProblem,
+    options: NSGA2Options,
+    reference_point: Option<ObjectiveVector>,
+    stopping_criterion: Option<StoppingCriterion>,
+    observer: &mut dyn OptimizationObserver,
+) -> Result<NSGA2Result> {
+    if options.population_size < 2 {
+        return Err(miette!(
+            "Population needs at least two individuals."
+        ));
+    }
+
+    observer.on_start(ObservedFunction::BiObjective(problem.name))?;
+    observer.on_restart(&RunStart {
+        run_type: OptimizationRunType::Exploration {
+            run_number: 1,
+            total_runs: 1,
+        },
+        maximum_iterations: options.generations,
+        stuck_after_iterations: None,
+        reheat_after_iterations: None,
+        iterations_performed: 0,
+    })?;
+
+    let mut rng = Pcg64Mcg::from_seed(options.seed);
+    let mut archive = ParetoArchive::new();
+
+    let initial_population: Vec<Individual> = match options.initialization {
+        InitializationStrategy::Uniform => {
+            let distributions = problem.bounds().uniform_random_generators();
+
+            (0..options.population_size)
+                .map(|_| {
+                    let vector: Vec<f64> = distributions
+                        .iter()
+                        .map(|distribution| distribution.sample(&mut rng))
+                        .collect();
+
+                    evaluate_into_archive(problem, &mut archive, vector)
+                })
+                .collect()
+        }
+        strategy => {
+            // Other strategies get their own seed, so they don't change the sequence
+            // of the main generator (used for the variation operators).
+            let bounds = problem.bounds().clone();
+            let candidates: Vec<Individual> = generate_initial_points(
+                strategy,
+                &bounds,
+                options.population_size,
+                derive_seeds(options.seed, 2)[1],
+            )
+            .into_iter()
+            .map(|vector| evaluate_into_archive(problem, &mut archive, vector))
+            .collect();
+
+            match strategy {
+                InitializationStrategy::Opposition => {
+                    select_survivors(candidates, options.population_size)
+                }
+                InitializationStrategy::BestOfCandidates(candidate_count) => {
+                    let mut candidates = candidates.into_iter();
+
+                    (0..options.population_size)
+                        .map(|_| {
+                            select_survivors(
+                                candidates
+                                    .by_ref()
+                                    .take(candidate_count)
+                                    .collect(),
+                                1,
+                            )
+                            .remove(0)
+                        })
+                        .collect()
+                }
+                _ => candidates,
+            }
+        }
+    };
+    let mut population =
+        select_survivors(initial_population, options.population_size);
+
+    let reference_point =
+        reference_point.unwrap_or_else(|| default_reference_point(&archive));
+    let mut best_hypervolume = archive.hypervolume(reference_point);
+    let mut generations_since_improvement = 0;
+
+    let mut stopping = stopping_criterion.map(|criterion| {
+        // Bi-objective functions have no global minimum to reach.
+        StoppingTracker::new(criterion, f64::NEG_INFINITY)
+    });
+    let mut stopping_criterion_met = false;
+
+    let mut generations_performed = 0;
+    let mut interrupted = false;
+
+    while generations_performed < options.generations {
+        if is_interrupt_requested() {
+            interrupted = true;
+            break;
+        }
+
+        let bounds = problem.bounds().clone();
+        let mut offspring: Vec<Individual> =
+            Vec::with_capacity(options.population_size);
+
+        while offspring.len() < options.population_size {
+            let first_parent = tournament_selection(&population, &mut rng);
+            let second_parent = tournament_selection(&population, &mut rng);
+
+            let (first_child, second_child) =
+                if rng.gen::<f64>() < options.crossover_probability {
+                    simulated_binary_crossover(
+                        &first_parent.vector,
+                        &second_parent.vector,
+                        &bounds,
+                        options.crossover_distribution_index,
+                        &mut rng,
+                    )
+                } else {
+                    (
+                        first_parent.vector.clone(),
+                        second_parent.vector.clone(),
+                    )
+                };
+
+            for mut child in [first_child, second_child] {
+                if offspring.len() == options.population_size {
+                    break;
+                }
+
+                polynomial_mutation(
+                    &mut child,
+                    &bounds,
+                    options.mutation_probability,
+                    options.mutation_distribution_index,
+                    &mut rng,
+                );
+                offspring.push(evaluate_into_archive(
+                    problem,
+                    &mut archive,
+                    child,
+                ));
+            }
+        }
+
+        population.extend(offspring);
+        population = select_survivors(population, options.population_size);
+        generations_performed += 1;
+
+        let hypervolume = archive.hypervolume(reference_point);
+        if hypervolume > best_hypervolume {
+            best_hypervolume = hypervolume;
+            generations_since_improvement = 0;
+        } else {
+            generations_since_improvement += 1;
+        }
+
+        let iteration = IterationStatus {
+            iteration: generations_performed,
+            evaluations: problem.evaluations(),
+            best_value: hypervolume,
+            iterations_since_improvement: generations_since_improvement,
+            jitter: None,
+            temperature: None,
+            population_mean: None,
+            population_std: None,
+        };
+
+        observer.on_iteration(&iteration)?;
+        if generations_since_improvement == 0 {
+            observer.on_improvement(&iteration)?;
+        }
+
+        // The tracker minimizes, so it gets the negated hypervolume.
+        if let Some(stopping) = stopping.as_mut() {
+            stopping_criterion_met = stopping.should_stop(&OptimizationStatus {
+                evaluations: iteration.evaluations,
+                best_value: -hypervolume,
+                population_diversity: Some(position_diversity(&population)),
+            });
+
+            if stopping_criterion_met {
+                break;
+            }
+        }
+    }
+
+    observer.on_finish(best_hypervolume)?;
+
+    Ok(NSGA2Result {
+        archive,
+        evaluations: problem.evaluations(),
+        generations_performed,
+        interrupted,
+        stopping_criterion_met,
+    })
+}
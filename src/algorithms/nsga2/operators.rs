@@ -0,0 +1,120 @@
+use rand::Rng;
+
+use crate::core::problem::Bounds;
+
+/// Bounded simulated binary crossover (SBX, Deb and Agrawal) of two parents.
+/// Each coordinate is recombined with probability 0.5, the offspring stay within `bounds`.
+pub fn simulated_binary_crossover<R: Rng>(
+    first_parent: &[f64],
+    second_parent: &[f64],
+    bounds: &Bounds,
+    distribution_index: f64,
+    rng: &mut R,
+) -> (Vec<f64>, Vec<f64>) {
+    let mut first_child = first_parent.to_vec();
+    let mut second_child = second_parent.to_vec();
+
+    for dimension in 0..first_parent.len() {
+        if rng.gen::<f64>() > 0.5 {
+            continue;
+        }
+
+        let smaller = first_parent[dimension].min(second_parent[dimension]);
+        let larger = first_parent[dimension].max(second_parent[dimension]);
+        if larger - smaller < 1e-14 {
+            continue;
+        }
+
+        let lower_bound = bounds.lower_bound(dimension);
+        let upper_bound = bounds.upper_bound(dimension);
+        let random_value = rng.gen::<f64>();
+
+        // Spread factors, limited so the children can't leave the bounds.
+        let spread_towards_lower = sbx_spread_factor(
+            1f64 + 2f64 * (smaller - lower_bound) / (larger - smaller),
+            distribution_index,
+            random_value,
+        );
+        let spread_towards_upper = sbx_spread_factor(
+            1f64 + 2f64 * (upper_bound - larger) / (larger - smaller),
+            distribution_index,
+            random_value,
+        );
+
+        let mut lower_child = (0.5
+            * (smaller + larger - spread_towards_lower * (larger - smaller)))
+            .clamp(lower_bound, upper_bound);
+        let mut upper_child = (0.5
+            * (smaller + larger + spread_towards_upper * (larger - smaller)))
+            .clamp(lower_bound, upper_bound);
+
+        if rng.gen::<f64>() < 0.5 {
+            std::mem::swap(&mut lower_child, &mut upper_child);
+        }
+
+        first_child[dimension] = lower_child;
+        second_child[dimension] = upper_child;
+    }
+
+    (first_child, second_child)
+}
+
+fn sbx_spread_factor(
+    beta: f64,
+    distribution_index: f64,
+    random_value: f64,
+) -> f64 {
+    let exponent = 1f64 / (distribution_index + 1f64);
+    let alpha = 2f64 - beta.powf(-(distribution_index + 1f64));
+
+    if random_value <= 1f64 / alpha {
+        (random_value * alpha).powf(exponent)
+    } else {
+        (1f64 / (2f64 - random_value * alpha)).powf(exponent)
+    }
+}
+
+/// Bounded polynomial mutation (Deb). Each coordinate is mutated with `mutation_probability`,
+/// the mutated coordinates stay within `bounds`.
+pub fn polynomial_mutation<R: Rng>(
+    vector: &mut [f64],
+    bounds: &Bounds,
+    mutation_probability: f64,
+    distribution_index: f64,
+    rng: &mut R,
+) {
+    let exponent = 1f64 / (distribution_index + 1f64);
+
+    for (dimension, value) in vector.iter_mut().enumerate() {
+        if rng.gen::<f64>() >= mutation_probability {
+            continue;
+        }
+
+        let lower_bound = bounds.lower_bound(dimension);
+        let upper_bound = bounds.upper_bound(dimension);
+        let width = upper_bound - lower_bound;
+        if width <= 0f64 {
+            continue;
+        }
+
+        let random_value = rng.gen::<f64>();
+        let perturbation = if random_value < 0.5 {
+            let distance_factor = 1f64 - (*value - lower_bound) / width;
+            let base = 2f64 * random_value
+                + (1f64 - 2f64 * random_value)
+                    * distance_factor.powf(distribution_index + 1f64);
+
+            base.powf(exponent) - 1f64
+        } else {
+            let distance_factor = 1f64 - (upper_bound - *value) / width;
+            let base = 2f64 * (1f64 - random_value)
+                + 2f64
+                    * (random_value - 0.5)
+                    * distance_factor.powf(distribution_index + 1f64);
+
+            1f64 - base.powf(exponent)
+        };
+
+        *value = (*value + perturbation * width).clamp(lower_bound, upper_bound);
+    }
+}
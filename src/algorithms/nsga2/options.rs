@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+use crate::algorithms::common::initialization::InitializationStrategy;
+
+#[derive(Copy, Clone, Serialize)]
+pub struct NSGA2Options {
+    /// Amount of individuals in the population (and offspring created per generation).
+    pub population_size: usize,
+
+    /// Amount of generations after the initial population.
+    pub generations: usize,
+
+    /// Probability of recombining two parents with simulated binary crossover
+    /// (otherwise the offspring are copies of the parents).
+    pub crossover_probability: f64,
+
+    /// Distribution index of the simulated binary crossover
+    /// (larger values create offspring closer to their parents).
+    pub crossover_distribution_index: f64,
+
+    /// Probability of mutating each coordinate of an offspring.
+    pub mutation_probability: f64,
+
+    /// Distribution index of the polynomial mutation
+    /// (larger values create smaller mutations).
+    pub mutation_distribution_index: f64,
+
+    /// How the initial population is chosen. With `Opposition` and `BestOfCandidates`,
+    /// the candidates are selected by their non-dominated rank and crowding distance.
+    pub initialization: InitializationStrategy,
+
+    pub seed: [u8; 16],
+}
+
+impl Default for NSGA2Options {
+    fn default() -> Self {
+        Self {
+            population_size: 100,
+            generations: 250,
+            crossover_probability: 0.9,
+            crossover_distribution_index: 20f64,
+            // One mutated coordinate per offspring on average (all problems have 40 dimensions).
+            mutation_probability: 1f64 / 40f64,
+            mutation_distribution_index: 20f64,
+            initialization: InitializationStrategy::Uniform,
+            seed: [
+                178, 22, 91, 240, 7, 133, 64, 19, 201, 88, 152, 36, 117, 250, 3,
+                71,
+            ],
+        }
+    }
+}
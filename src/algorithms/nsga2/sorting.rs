@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+
+use crate::core::pareto::{dominates, ObjectiveVector};
+
+/// Splits the points with the given `objectives` into non-dominated fronts (fast non-dominated
+/// sorting). Returns the indices of the points of each front, the best front first.
+pub fn non_dominated_fronts(objectives: &[ObjectiveVector]) -> Vec<Vec<usize>> {
+    // Indices of the points each point dominates and the amount of points dominating it.
+    let mut dominated_points: Vec<Vec<usize>> =
+        vec![Vec::new(); objectives.len()];
+    let mut domination_counts: Vec<usize> = vec![0; objectives.len()];
+
+    for first in 0..objectives.len() {
+        for second in (first + 1)..objectives.len() {
+            if dominates(&objectives[first], &objectives[second]) {
+                dominated_points[first].push(second);
+                domination_counts[second] += 1;
+            } else if dominates(&objectives[second], &objectives[first]) {
+                dominated_points[second].push(first);
+                domination_counts[first] += 1;
+            }
+        }
+    }
+
+    let mut fronts: Vec<Vec<usize>> = Vec::new();
+    let mut current_front: Vec<usize> = (0..objectives.len())
+        .filter(|index| domination_counts[*index] == 0)
+        .collect();
+
+    while !current_front.is_empty() {
+        let mut next_front: Vec<usize> = Vec::new();
+
+        for point in current_front.iter() {
+            for dominated_point in dominated_points[*point].iter() {
+                domination_counts[*dominated_point] -= 1;
+                if domination_counts[*dominated_point] == 0 {
+                    next_front.push(*dominated_point);
+                }
+            }
+        }
+
+        fronts.push(current_front);
+        current_front = next_front;
+    }
+
+    fronts
+}
+
+/// Crowding distance of each point of a single `front` (in the same order): the sum of the
+/// normalized distances between its neighbours along each objective. Points at the boundaries
+/// of the front get an infinite distance, so they are always preferred.
+pub fn crowding_distances(
+    objectives: &[ObjectiveVector],
+    front: &[usize],
+) -> Vec<f64> {
+    let mut distances = vec![0f64; front.len()];
+    if front.len() <= 2 {
+        distances.fill(f64::INFINITY);
+        return distances;
+    }
+
+    for objective in [0, 1] {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|first, second| {
+            objectives[front[*first]][objective]
+                .partial_cmp(&objectives[front[*second]][objective])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let minimum = objectives[front[order[0]]][objective];
+        let maximum = objectives[front[order[order.len() - 1]]][objective];
+
+        distances[order[0]] = f64::INFINITY;
+        distances[order[order.len() - 1]] = f64::INFINITY;
+
+        let range = maximum - minimum;
+        if range <= 0f64 {
+            continue;
+        }
+
+        for position in 1..(order.len() - 1) {
+            let previous = objectives[front[order[position - 1]]][objective];
+            let next = objectives[front[order[position + 1]]][objective];
+
+            distances[order[position]] += (next - previous) / range;
+        }
+    }
+
+    distances
+}
@@ -1,28 +1,35 @@
 use std::slice::Iter;
 
 use self::Option::*;
+use crate::algorithms::common::observer::SilentObserver;
 use crate::algorithms::simulated_annealing::options::SAOptions;
 use crate::algorithms::simulated_annealing::simulated_annealing::run_sa;
 use crate::core::problem::BBOBProblem;
 
+/// Options tuned by `get_optimal_params`.
+pub struct TunedOptions {
+    pub options: SAOptions,
+
+    /// All evaluated option changes, the most impactful (applied) one first.
+    pub evaluated_changes: Vec<OptionValue>,
+}
+
 pub fn get_optimal_params(
     problem: &mut BBOBProblem,
     current_options: SAOptions,
-) -> SAOptions {
-    run_sa(problem, current_options).unwrap();
+) -> TunedOptions {
+    run_sa(
+        problem,
+        current_options,
+        None,
+        None,
+        &mut SilentObserver,
+    )
+    .unwrap();
 
     let value_changes = get_value_changes(current_options, problem);
     let mut new_options = current_options;
 
-    for ch in value_changes.iter() {
-        println!(
-            "Option: {}, Diff: {}, Direction: {}",
-            ch.option.name(),
-            ch.option_diff,
-            ch.direction.name()
-        );
-    }
-
     let value_change = value_changes[0];
     match value_change.option {
         InitialTemperature => match value_change.direction {
@@ -163,9 +170,12 @@ pub fn get_optimal_params(
         },
     };
 
-    SAOptions {
-        function: problem.name,
-        ..new_options
+    TunedOptions {
+        options: SAOptions {
+            function: problem.name,
+            ..new_options
+        },
+        evaluated_changes: value_changes,
     }
 }
 
@@ -209,6 +219,9 @@ fn get_initial_step_change_ls(
             initial_step_size_ls: initial_step * 0.1,
             ..SAOptions::default()
         },
+        None,
+        None,
+        &mut SilentObserver,
     );
     let mut direction = Direction::Negative;
 
@@ -223,6 +236,9 @@ fn get_initial_step_change_ls(
             initial_step_size_ls: initial_step + 0.1,
             ..SAOptions::default()
         },
+        None,
+        None,
+        &mut SilentObserver,
     );
 
     let pos_val = match res {
@@ -236,14 +252,6 @@ fn get_initial_step_change_ls(
         direction = Direction::Positive
     }
 
-    println!(
-        "Base: {}, Positive: {}, negative: {}, pos_diff: {}, neg_diff: {}",
-        problem.name.global_minimum(),
-        pos_val,
-        neg_val,
-        problem.name.global_minimum() - pos_val,
-        problem.name.global_minimum() - neg_val
-    );
     let option_diff = f64::max(
         (problem.name.global_minimum() - pos_val).abs(),
         (problem.name.global_minimum() - neg_val).abs(),
@@ -252,6 +260,8 @@ fn get_initial_step_change_ls(
         option: InitialStepSizeLs,
         option_diff,
         direction,
+        positive_value: pos_val,
+        negative_value: neg_val,
     }
 }
 
@@ -315,9 +325,27 @@ impl Option {
     }
 }
 
+/// Evaluated change of a single option.
 #[derive(Clone, Copy)]
-struct OptionValue {
-    pub option: Option,
-    pub option_diff: f64,
-    pub direction: Direction,
+pub struct OptionValue {
+    option: Option,
+    option_diff: f64,
+    direction: Direction,
+
+    /// Best values found with the option moved in the positive and negative direction.
+    positive_value: f64,
+    negative_value: f64,
+}
+
+impl OptionValue {
+    pub fn describe(&self) -> String {
+        format!(
+            "Option: {}, Diff: {}, Direction: {}, Positive: {}, Negative: {}",
+            self.option.name(),
+            self.option_diff,
+            self.direction.name(),
+            self.positive_value,
+            self.negative_value
+        )
+    }
 }
@@ -1,6 +1,7 @@
 use itertools::Itertools;
 use num::abs;
 
+use crate::algorithms::common::rng::UniformF64BoundedRandomGenerator;
 use crate::algorithms::common::structs::State;
 use crate::algorithms::simulated_annealing::options::SAOptions;
 use crate::core::problem::BBOBProblem;
@@ -20,52 +21,67 @@ impl SANeighborhood {
 
     pub fn generate_neighborhood(
         &mut self,
-        current_state: State,
+        current_state: &State,
         problem: &mut BBOBProblem,
         options: SAOptions,
+        generator: &mut UniformF64BoundedRandomGenerator,
     ) {
-        let changes =
-            self.find_biggest_change(current_state.clone().vector, problem);
+        let changes = self.find_biggest_change(&current_state.vector, problem);
+        let bounds = problem.bounds();
 
+        // Integer variables are moved by whole steps (see `VariableType::discrete_step`).
         self.states = Vec::new();
 
-        for el in changes[0..options.n_best_ls].to_vec().iter() {
+        for el in changes[0..options.n_best_ls].iter() {
             for j in 0..10 {
-                let mut new_state = current_state.clone().vector;
-                let step = options.initial_step_size_ls * j as f64;
-                if el.value_diff > 0f64 {
-                    if new_state[el.index] + step <= 5f64 {
-                        new_state[el.index] += step;
-                        self.states.push(State {
-                            vector: new_state,
-                            ..Default::default()
-                        });
-                    }
-                } else if new_state[el.index] - step >= -5f64 {
-                    new_state[el.index] -= step;
-                    self.states.push(State {
-                        vector: new_state,
-                        ..Default::default()
-                    });
-                }
+                let current_value = current_state.vector[el.index];
+                let variable_type = problem.variable_types[el.index];
+                let step = variable_type
+                    .discrete_step(options.initial_step_size_ls * j as f64);
+                let moved_value = if el.value_diff > 0f64 {
+                    current_value + step
+                } else {
+                    current_value - step
+                };
+
+                // Neighbours outside the bounds are either repaired or discarded.
+                let Some(moved_value) = options.boundary_handling.repair(
+                    moved_value,
+                    current_value,
+                    el.index,
+                    bounds,
+                    generator,
+                ) else {
+                    continue;
+                };
+
+                let mut new_state = current_state.vector.clone();
+                new_state[el.index] =
+                    variable_type.nearest_valid_value(moved_value);
+                self.states.push(State {
+                    vector: new_state,
+                    ..Default::default()
+                });
             }
         }
     }
 
     fn find_biggest_change(
         &mut self,
-        current_neighborhood: Vec<f64>,
+        current_neighborhood: &[f64],
         problem: &mut BBOBProblem,
     ) -> Vec<VectorElement> {
-        let base_value = problem.evaluate(&current_neighborhood);
+        let base_value = problem.evaluate(current_neighborhood);
 
-        let vec_elements: Vec<VectorElement> = (0..40)
-            .map(|i| {
-                let mut new_vec = current_neighborhood.clone();
+        // A single copy is moved one dimension at a time (and restored after each one),
+        // so large problems don't allocate a new vector per dimension.
+        let mut new_vec = current_neighborhood.to_vec();
 
-                new_vec[i] += 0.01f64;
+        let vec_elements: Vec<VectorElement> = (0..current_neighborhood.len())
+            .map(|i| {
+                new_vec[i] += problem.variable_types[i].discrete_step(0.01f64);
                 let value = problem.evaluate(&new_vec);
-                new_vec[i] -= 0.02f64;
+                new_vec[i] = current_neighborhood[i];
 
                 VectorElement::new(i, base_value - value)
             })
@@ -83,55 +99,73 @@ impl LocalSearchNeighborhood {
 
     pub fn generate_neighborhood(
         &mut self,
-        current_state: State,
+        current_state: &State,
         problem: &mut BBOBProblem,
         options: SAOptions,
+        generator: &mut UniformF64BoundedRandomGenerator,
     ) {
         let changes = self.find_biggest_change(
-            current_state.clone().vector,
+            &current_state.vector,
             problem,
             options.initial_step_size_ls,
         );
+        let bounds = problem.bounds();
+
+        // Integer variables are moved by whole steps (see `VariableType::discrete_step`).
         self.states = Vec::new();
         for el in changes[0..options.n_best_ls].iter() {
             for j in 0..20 {
-                let mut new_state = current_state.clone().vector;
-                let step = options.initial_step_size_ls * j as f64;
-                if el.value_diff > 0f64 {
-                    if new_state[el.index] + step <= 5f64 {
-                        new_state[el.index] += step;
-                        self.states.push(State {
-                            vector: new_state,
-                            ..Default::default()
-                        });
-                    }
-                } else if new_state[el.index] - step >= -5f64 {
-                    new_state[el.index] -= step;
-                    self.states.push(State {
-                        vector: new_state,
-                        ..Default::default()
-                    });
-                }
+                let current_value = current_state.vector[el.index];
+                let variable_type = problem.variable_types[el.index];
+                let step = variable_type
+                    .discrete_step(options.initial_step_size_ls * j as f64);
+                let moved_value = if el.value_diff > 0f64 {
+                    current_value + step
+                } else {
+                    current_value - step
+                };
+
+                // Neighbours outside the bounds are either repaired or discarded.
+                let Some(moved_value) = options.boundary_handling.repair(
+                    moved_value,
+                    current_value,
+                    el.index,
+                    bounds,
+                    generator,
+                ) else {
+                    continue;
+                };
+
+                let mut new_state = current_state.vector.clone();
+                new_state[el.index] =
+                    variable_type.nearest_valid_value(moved_value);
+                self.states.push(State {
+                    vector: new_state,
+                    ..Default::default()
+                });
             }
         }
     }
     fn find_biggest_change(
         &mut self,
-        current_neighborhood: Vec<f64>,
+        current_neighborhood: &[f64],
         problem: &mut BBOBProblem,
         step_size: f64,
     ) -> Vec<VectorElement> {
-        let base_value = problem.evaluate(&current_neighborhood);
+        let base_value = problem.evaluate(current_neighborhood);
 
-        let vec_elements: Vec<VectorElement> = (0..40)
-            .map(|i| {
-                let mut new_vec = current_neighborhood.clone();
+        // A single copy is moved one dimension at a time (see `SANeighborhood`).
+        let mut new_vec = current_neighborhood.to_vec();
 
-                new_vec[i] += step_size;
+        let vec_elements: Vec<VectorElement> = (0..current_neighborhood.len())
+            .map(|i| {
+                let step = problem.variable_types[i].discrete_step(step_size);
+                new_vec[i] += step;
                 let value = problem.evaluate(&new_vec);
 
-                new_vec[i] -= 2f64 * step_size;
+                new_vec[i] -= 2f64 * step;
                 let value2 = problem.evaluate(&new_vec);
+                new_vec[i] = current_neighborhood[i];
 
                 let value_diff = if base_value - value > base_value - value2 {
                     abs(base_value - value)
@@ -1,9 +1,14 @@
-use crate::core::functions::BBOBFunctionType;
+use serde::Serialize;
 
-#[derive(Copy, Clone)]
+use crate::algorithms::common::boundary::BoundaryHandling;
+use crate::algorithms::common::constraints::ConstraintHandling;
+use crate::algorithms::common::initialization::InitializationStrategy;
+use crate::core::functions::{BBOBFunctionType, SuiteFunction};
+
+#[derive(Copy, Clone, Serialize)]
 pub struct SAOptions {
     // initial temperature
-    pub function: BBOBFunctionType,
+    pub function: SuiteFunction,
     pub initial_temperature: u8,
 
     // Cooling rate. Temperature is multiplied by this value on each iteration
@@ -17,12 +22,21 @@ pub struct SAOptions {
     pub n_best_ls: usize,
     pub ls_step_decrease: f64,
     pub seed: [u8; 16],
+
+    /// How the starting point is chosen (unless a warm start point is given).
+    pub initialization: InitializationStrategy,
+
+    /// How neighbours outside the problem bounds are handled.
+    pub boundary_handling: BoundaryHandling,
+
+    /// How neighbours violating the constraints are accepted (constrained problems only).
+    pub constraint_handling: ConstraintHandling,
 }
 
 impl Default for SAOptions {
     fn default() -> Self {
         Self {
-            function: BBOBFunctionType::AttractiveSector,
+            function: BBOBFunctionType::AttractiveSector.into(),
             initial_temperature: 100,
             annealing_schedule: 0.95,
             max_iterations_sa: 2000,
@@ -37,6 +51,9 @@ impl Default for SAOptions {
                 67, 193, 140, 181, 155, 182, 45, 146, 4, 213, 77, 160, 217, 31,
                 143, 135,
             ],
+            initialization: InitializationStrategy::Uniform,
+            boundary_handling: BoundaryHandling::Discard,
+            constraint_handling: ConstraintHandling::FeasibilityRules,
         }
     }
 }
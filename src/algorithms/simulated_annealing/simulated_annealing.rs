@@ -2,10 +2,30 @@ use miette::Result;
 use num::abs;
 
 use super::super::common::structs::Minimum;
+use crate::algorithms::common::constraints::{
+    ConstrainedValue,
+    ConstraintHandler,
+};
+use crate::algorithms::common::initialization::{
+    initialize_population,
+    InitializationStrategy,
+};
+use crate::algorithms::common::observer::{
+    IterationStatus,
+    OptimizationObserver,
+    OptimizationRunType,
+    RunStart,
+};
 use crate::algorithms::common::rng::{
     choose_random,
+    derive_seeds,
     UniformF64BoundedRandomGenerator,
 };
+use crate::algorithms::common::stopping::{
+    OptimizationStatus,
+    StoppingCriterion,
+    StoppingTracker,
+};
 use crate::algorithms::common::structs::State;
 use crate::algorithms::simulated_annealing::neighborhood_generation::{
     LocalSearchNeighborhood,
@@ -14,36 +34,118 @@ use crate::algorithms::simulated_annealing::neighborhood_generation::{
 use crate::algorithms::simulated_annealing::options::SAOptions;
 use crate::core::problem::BBOBProblem;
 
-pub fn run_sa(problem: &mut BBOBProblem, options: SAOptions) -> Result<Minimum> {
-    let mut rng =
-        UniformF64BoundedRandomGenerator::new(problem.bounds, options.seed);
+/// Runs simulated annealing followed by local search on `problem`.
+/// Starts at `start_point` if given (e.g. a previously found solution), otherwise at a point
+/// chosen by `options.initialization`.
+/// Once `stopping_criterion` (if given) is met, returns the best solution found so far.
+///
+/// Both phases are reported to the `observer` as a single run.
+pub fn run_sa(
+    problem: &mut BBOBProblem,
+    options: SAOptions,
+    start_point: Option<&[f64]>,
+    stopping_criterion: Option<StoppingCriterion>,
+    observer: &mut dyn OptimizationObserver,
+) -> Result<Minimum> {
+    let mut rng = UniformF64BoundedRandomGenerator::new(
+        problem.bounds.clone(),
+        options.seed,
+    );
+
+    // Integer variables of the starting point are rounded on mixed-integer problems.
+    let mut current_state = match (start_point, options.initialization) {
+        (Some(start_point), _) => {
+            let mut vector = start_point.to_vec();
+            problem.round_integer_variables(&mut vector);
+
+            State {
+                objective_value: problem.evaluate(&vector),
+                boundary_penalty: 0f64,
+                constraint_violation: problem.constraint_violation(&vector),
+                vector,
+            }
+        }
+        (None, InitializationStrategy::Uniform) => {
+            let mut vector = rng.sample_point();
+            problem.round_integer_variables(&mut vector);
 
-    let mut current_state = State {
-        vector: rng.sample_multiple(40),
-        ..Default::default()
+            State {
+                objective_value: problem.evaluate(&vector),
+                boundary_penalty: 0f64,
+                constraint_violation: problem.constraint_violation(&vector),
+                vector,
+            }
+        }
+        // Other strategies don't use the main generator, so its sequence
+        // is the same regardless of how many points they sample.
+        (None, strategy) => initialize_population(
+            problem,
+            strategy,
+            1,
+            derive_seeds(options.seed, 2)[1],
+        )
+        .remove(0),
     };
+    // The (evaluated) start state is the best one until a better one is found,
+    // so a warm start never ends with a worse solution than it started from.
     let mut minimal_state = current_state.clone();
-    current_state.set_objective_value(problem.evaluate(&current_state.vector));
+
+    observer.on_start(problem.name.into())?;
+    observer.on_restart(&RunStart {
+        run_type: OptimizationRunType::Exploration {
+            run_number: 1,
+            total_runs: 1,
+        },
+        maximum_iterations: (options.max_iterations_sa
+            + options.max_iterations_ls) as usize,
+        stuck_after_iterations: None,
+        reheat_after_iterations: None,
+        iterations_performed: 0,
+    })?;
+
+    let mut stopping = stopping_criterion.map(|criterion| {
+        StoppingTracker::new(criterion, problem.name.global_minimum())
+    });
+
+    // The best state is chosen by Deb's feasibility rules, the constraint handling
+    // only decides which moves are accepted (see `SAOptions::constraint_handling`).
+    let mut constraint_handler =
+        ConstraintHandler::new(options.constraint_handling);
 
     let neighborhood = &mut SANeighborhood::new();
     let mut temperature = options.initial_temperature as f64;
     let mut iters = 0;
+    let mut iterations_since_improvement = 0;
+    let mut stopping_criterion_met = false;
 
     while temperature > options.min_temp || iters < options.max_iterations_sa {
         neighborhood.generate_neighborhood(
-            current_state.clone(),
+            &current_state,
             problem,
             options,
+            &mut rng,
         );
-        let next_state = &mut choose_random(neighborhood.states.clone());
+        let next_state = &mut choose_random(&neighborhood.states);
         next_state.set_objective_value(problem.evaluate(&next_state.vector));
+        next_state.boundary_penalty = options
+            .boundary_handling
+            .penalty(problem.bounds(), &next_state.vector);
+        next_state.constraint_violation =
+            problem.constraint_violation(&next_state.vector);
 
-        if next_state.objective_value < minimal_state.objective_value {
+        if next_state
+            .constrained_value()
+            .is_better_than(&minimal_state.constrained_value())
+        {
             minimal_state = next_state.clone();
+            iterations_since_improvement = 0;
         } else {
-            let p_move = (-(next_state.objective_value
-                - current_state.objective_value)
-                / temperature)
+            iterations_since_improvement += 1;
+
+            let p_move = (-constraint_handler.worsening(
+                &next_state.constrained_value(),
+                &current_state.constrained_value(),
+            ) / temperature)
                 .exp();
             if rng.sample_0_to_1() <= p_move {
                 current_state = next_state.clone();
@@ -51,15 +153,74 @@ pub fn run_sa(problem: &mut BBOBProblem, options: SAOptions) -> Result<Minimum>
             temperature *= options.annealing_schedule;
         }
 
+        constraint_handler.finish_iteration(&current_state.constrained_value());
+
         iters += 1;
+
+        report_iteration(
+            observer,
+            &IterationStatus {
+                iteration: iters as usize,
+                evaluations: problem.evaluations(),
+                best_value: minimal_state.objective_value,
+                iterations_since_improvement,
+                jitter: None,
+                temperature: Some(temperature),
+                population_mean: None,
+                population_std: None,
+            },
+        )?;
+
+        if is_stopping_criterion_met(
+            stopping.as_mut(),
+            problem,
+            minimal_state.objective_value,
+        ) {
+            stopping_criterion_met = true;
+            break;
+        }
     }
-    local_search(problem, &minimal_state, options)
+
+    let minimum = if stopping_criterion_met {
+        Minimum::new(
+            minimal_state.objective_value,
+            minimal_state.vector,
+            minimal_state.constraint_violation,
+        )
+    } else {
+        local_search(
+            problem,
+            &minimal_state,
+            options,
+            iters,
+            &mut rng,
+            &constraint_handler,
+            stopping.as_mut(),
+            observer,
+        )?
+    };
+
+    observer.on_finish(minimum.value)?;
+
+    Ok(minimum)
 }
 
+/// Local search performed after simulated annealing. For the trajectory, iterations
+/// are numbered as a continuation of the `sa_iterations` annealing iterations.
+///
+/// Moves are compared by the `constraint_handler` (in the state annealing left it in).
+/// If the search ends in a worse state than `start_state` by Deb's feasibility rules
+/// (e.g. an infeasible one with a penalty method), `start_state` is returned instead.
+#[allow(clippy::too_many_arguments)]
 fn local_search(
     problem: &mut BBOBProblem,
     start_state: &State,
     options: SAOptions,
+    sa_iterations: u64,
+    generator: &mut UniformF64BoundedRandomGenerator,
+    constraint_handler: &ConstraintHandler,
+    mut stopping: Option<&mut StoppingTracker>,
+    observer: &mut dyn OptimizationObserver,
 ) -> Result<Minimum> {
     let current_state = start_state.clone();
     let mut minimal_state = current_state;
@@ -73,21 +234,37 @@ fn local_search(
     last_10_values.resize(10, 0f64);
 
     let mut current_options = options;
+    let mut iterations_since_improvement = 0;
 
     while iters < options.max_iterations_ls && step >= 10e-16 {
         neighborhood.generate_neighborhood(
-            minimal_state.clone(),
+            &minimal_state,
             problem,
             current_options,
+            generator,
         );
+        let previous_minimal_value = minimal_state.objective_value;
         for el in neighborhood.states.iter() {
-            let mut moved = el.clone();
             let objective_value = problem.evaluate(&el.vector);
+            let boundary_penalty = options
+                .boundary_handling
+                .penalty(problem.bounds(), &el.vector);
+            let neighbour_value = ConstrainedValue::new(
+                objective_value + boundary_penalty,
+                problem.constraint_violation(&el.vector),
+            );
 
             last_10_values[(iters % 10) as usize] = objective_value;
-            moved.set_objective_value(objective_value);
-            if moved.objective_value < minimal_state.objective_value {
-                minimal_state = moved.clone();
+            if constraint_handler.is_better(
+                &neighbour_value,
+                &minimal_state.constrained_value(),
+            ) {
+                minimal_state = State {
+                    vector: el.vector.clone(),
+                    objective_value,
+                    boundary_penalty,
+                    constraint_violation: neighbour_value.constraint_violation,
+                };
             }
         }
 
@@ -106,12 +283,78 @@ fn local_search(
         }
 
         iters += 1;
+
+        if minimal_state.objective_value < previous_minimal_value {
+            iterations_since_improvement = 0;
+        } else {
+            iterations_since_improvement += 1;
+        }
+
+        report_iteration(
+            observer,
+            &IterationStatus {
+                iteration: (sa_iterations + iters) as usize,
+                evaluations: problem.evaluations(),
+                best_value: minimal_state.objective_value,
+                iterations_since_improvement,
+                jitter: None,
+                temperature: None,
+                population_mean: None,
+                population_std: None,
+            },
+        )?;
+
+        if is_stopping_criterion_met(
+            stopping.as_deref_mut(),
+            problem,
+            minimal_state.objective_value,
+        ) {
+            break;
+        }
+    }
+
+    if start_state
+        .constrained_value()
+        .is_better_than(&minimal_state.constrained_value())
+    {
+        minimal_state = start_state.clone();
     }
 
-    Ok(Minimum {
-        vector: minimal_state.vector,
-        value: minimal_state.objective_value,
-    })
+    Ok(Minimum::new(
+        minimal_state.objective_value,
+        minimal_state.vector,
+        minimal_state.constraint_violation,
+    ))
+}
+
+/// Reports an iteration to the `observer` (and the improvement, if the best value improved).
+fn report_iteration(
+    observer: &mut dyn OptimizationObserver,
+    iteration: &IterationStatus,
+) -> Result<()> {
+    observer.on_iteration(iteration)?;
+    if iteration.iterations_since_improvement == 0 {
+        observer.on_improvement(iteration)?;
+    }
+
+    Ok(())
+}
+
+/// Updates the `stopping` tracker (if any) after an iteration
+/// and returns whether its criterion is met.
+fn is_stopping_criterion_met(
+    stopping: Option<&mut StoppingTracker>,
+    problem: &BBOBProblem,
+    best_value: f64,
+) -> bool {
+    match stopping {
+        Some(stopping) => stopping.should_stop(&OptimizationStatus {
+            evaluations: problem.evaluations(),
+            best_value,
+            population_diversity: None,
+        }),
+        None => false,
+    }
 }
 
 fn check_last_10_similar(last_10: &Vec<f64>) -> bool {
@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use itertools::Itertools;
+
+use crate::analysis::statistics::{
+    friedman_test,
+    holm_correction,
+    median,
+    wilcoxon_rank_sum_test,
+    FriedmanTestResult,
+};
+use crate::core::results::OptimizationResultRecord;
+use crate::core::suite::{SuiteType, BBOB_DIMENSIONS};
+
+/// Default significance level for the comparison tests.
+pub const DEFAULT_SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Results of a single run configuration (e.g. one result file).
+pub struct ComparedConfiguration {
+    pub label: String,
+    pub records: Vec<OptimizationResultRecord>,
+}
+
+/// Outcome of a pairwise comparison on a single function,
+/// from the perspective of the first configuration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PairwiseOutcome {
+    Win,
+    Tie,
+    Loss,
+}
+
+/// Final gaps of all configurations on a single function.
+///
+/// Functions are identified by their suite, dimension and index,
+/// so results of different suites or dimensions are never pooled.
+pub struct FunctionComparison {
+    pub suite: SuiteType,
+    pub dimension: usize,
+    pub function_index: usize,
+    pub function_name: String,
+
+    /// Final gaps of each configuration (same order as the configurations).
+    /// Empty if the configuration has no results for this function.
+    pub gaps: Vec<Vec<f64>>,
+}
+
+impl FunctionComparison {
+    /// Function index and name, followed by the suite and dimension
+    /// if they differ from the default 40-dimensional bbob suite.
+    pub fn label(&self) -> String {
+        let label = format!(
+            "{:02} {}",
+            self.function_index, self.function_name
+        );
+
+        if self.suite == SuiteType::Bbob && self.dimension == BBOB_DIMENSIONS {
+            label
+        } else {
+            format!(
+                "{} ({}, {}-D)",
+                label,
+                self.suite.name(),
+                self.dimension
+            )
+        }
+    }
+
+    /// Median gap of each configuration (`None` if it has no results for this function).
+    pub fn medians(&self) -> Vec<Option<f64>> {
+        self.gaps
+            .iter()
+            .map(|gaps| (!gaps.is_empty()).then(|| median(gaps)))
+            .collect()
+    }
+}
+
+/// Comparison of two configurations across all functions both have results for.
+pub struct PairwiseComparison {
+    pub first: usize,
+    pub second: usize,
+
+    /// Tuples of (position in [`ComparisonResult::functions`],
+    /// Holm-adjusted p-value, outcome).
+    pub function_outcomes: Vec<(usize, f64, PairwiseOutcome)>,
+}
+
+impl PairwiseComparison {
+    fn count(&self, outcome: PairwiseOutcome) -> usize {
+        self.function_outcomes
+            .iter()
+            .filter(|(_, _, function_outcome)| *function_outcome == outcome)
+            .count()
+    }
+
+    pub fn wins(&self) -> usize {
+        self.count(PairwiseOutcome::Win)
+    }
+
+    pub fn ties(&self) -> usize {
+        self.count(PairwiseOutcome::Tie)
+    }
+
+    pub fn losses(&self) -> usize {
+        self.count(PairwiseOutcome::Loss)
+    }
+}
+
+pub struct ComparisonResult {
+    pub labels: Vec<String>,
+    pub significance_level: f64,
+
+    pub functions: Vec<FunctionComparison>,
+    pub pairwise_comparisons: Vec<PairwiseComparison>,
+
+    /// Friedman test over the median gaps on functions all configurations
+    /// have results for (`None` if there are no such functions).
+    pub friedman: Option<FriedmanTestResult>,
+    pub friedman_function_count: usize,
+}
+
+impl ComparisonResult {
+    fn pairwise_comparison(
+        &self,
+        first: usize,
+        second: usize,
+    ) -> Option<(&PairwiseComparison, bool)> {
+        self.pairwise_comparisons.iter().find_map(|comparison| {
+            if comparison.first == first && comparison.second == second {
+                Some((comparison, false))
+            } else if comparison.first == second && comparison.second == first {
+                Some((comparison, true))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Compares the final gaps of multiple configurations.
+///
+/// For each pair of configurations, a Wilcoxon rank-sum test is performed on every
+/// function both have results for, and the p-values are Holm-corrected across
+/// those functions. A significant difference is a win for the configuration
+/// with the lower median gap, otherwise the function counts as a tie.
+pub fn compare_configurations(
+    configurations: &[ComparedConfiguration],
+    significance_level: f64,
+) -> ComparisonResult {
+    let mut functions: BTreeMap<(SuiteType, usize, usize), FunctionComparison> =
+        BTreeMap::new();
+
+    for (configuration_index, configuration) in configurations.iter().enumerate()
+    {
+        for record in &configuration.records {
+            let suite = record.suite();
+
+            let function = functions
+                .entry((suite, record.dimension, record.function_index))
+                .or_insert_with(|| FunctionComparison {
+                    suite,
+                    dimension: record.dimension,
+                    function_index: record.function_index,
+                    function_name: record.function_name.clone(),
+                    gaps: vec![Vec::new(); configurations.len()],
+                });
+
+            function.gaps[configuration_index].push(record.gap_to_optimum);
+        }
+    }
+
+    let functions: Vec<FunctionComparison> = functions.into_values().collect();
+
+    let pairwise_comparisons = (0..configurations.len())
+        .tuple_combinations()
+        .map(|(first, second)| {
+            let shared_functions: Vec<(usize, &FunctionComparison)> = functions
+                .iter()
+                .enumerate()
+                .filter(|(_, function)| {
+                    !function.gaps[first].is_empty()
+                        && !function.gaps[second].is_empty()
+                })
+                .collect();
+
+            let p_values: Vec<f64> = shared_functions
+                .iter()
+                .map(|(_, function)| {
+                    wilcoxon_rank_sum_test(
+                        &function.gaps[first],
+                        &function.gaps[second],
+                    )
+                })
+                .collect();
+            let adjusted_p_values = holm_correction(&p_values);
+
+            let function_outcomes = shared_functions
+                .iter()
+                .zip(adjusted_p_values)
+                .map(|((position, function), adjusted_p_value)| {
+                    let first_median = median(&function.gaps[first]);
+                    let second_median = median(&function.gaps[second]);
+
+                    let outcome = if adjusted_p_value >= significance_level
+                        || first_median == second_median
+                    {
+                        PairwiseOutcome::Tie
+                    } else if first_median < second_median {
+                        PairwiseOutcome::Win
+                    } else {
+                        PairwiseOutcome::Loss
+                    };
+
+                    (*position, adjusted_p_value, outcome)
+                })
+                .collect();
+
+            PairwiseComparison {
+                first,
+                second,
+                function_outcomes,
+            }
+        })
+        .collect();
+
+    let friedman_blocks: Vec<Vec<f64>> = functions
+        .iter()
+        .filter_map(|function| function.medians().into_iter().collect())
+        .collect();
+
+    ComparisonResult {
+        labels: configurations
+            .iter()
+            .map(|configuration| configuration.label.clone())
+            .collect(),
+        significance_level,
+        friedman: friedman_test(&friedman_blocks),
+        friedman_function_count: friedman_blocks.len(),
+        functions,
+        pairwise_comparisons,
+    }
+}
+
+/// Prints the per-function median gaps, the win/tie/loss table
+/// and the Friedman test results to stdout.
+pub fn print_comparison(result: &ComparisonResult) {
+    let label_width = result
+        .labels
+        .iter()
+        .map(|label| label.len())
+        .max()
+        .unwrap_or(0)
+        .max(12);
+    let function_width = result
+        .functions
+        .iter()
+        .map(|function| function.label().len())
+        .max()
+        .unwrap_or(0)
+        .max(36);
+
+    println!("{}", "Median gaps".bold());
+    println!(
+        "{}",
+        format!(
+            "{:<function_width$} {}",
+            "Function",
+            result
+                .labels
+                .iter()
+                .map(|label| format!("{:>label_width$}", label))
+                .join(" ")
+        )
+        .bold()
+    );
+
+    for function in &result.functions {
+        let medians = function.medians();
+        let best_median = medians
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+
+        let median_columns = medians
+            .iter()
+            .map(|median| match median {
+                Some(median) => {
+                    let column = format!("{:>label_width$.5e}", median);
+                    if *median == best_median {
+                        column.green().to_string()
+                    } else {
+                        column
+                    }
+                }
+                None => format!("{:>label_width$}", "-"),
+            })
+            .join(" ");
+
+        println!(
+            "{:<function_width$} {}",
+            function.label(),
+            median_columns
+        );
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Wins/ties/losses of row versus column (Wilcoxon rank-sum, Holm-corrected, alpha = {})",
+            result.significance_level
+        )
+        .bold()
+    );
+    println!(
+        "{}",
+        format!(
+            "{:<label_width$} {}",
+            "",
+            result
+                .labels
+                .iter()
+                .map(|label| format!("{:>label_width$}", label))
+                .join(" ")
+        )
+        .bold()
+    );
+
+    for (row, row_label) in result.labels.iter().enumerate() {
+        let cells = (0..result.labels.len())
+            .map(
+                |column| match result.pairwise_comparison(row, column) {
+                    Some((comparison, swapped)) => {
+                        let (wins, losses) = if swapped {
+                            (comparison.losses(), comparison.wins())
+                        } else {
+                            (comparison.wins(), comparison.losses())
+                        };
+
+                        format!(
+                            "{:>label_width$}",
+                            format!("{}/{}/{}", wins, comparison.ties(), losses)
+                        )
+                    }
+                    None => format!("{:>label_width$}", "-"),
+                },
+            )
+            .join(" ");
+
+        println!(
+            "{} {}",
+            format!("{:<label_width$}", row_label).bold(),
+            cells
+        );
+    }
+
+    println!();
+    match &result.friedman {
+        Some(friedman) => {
+            println!(
+                "{}",
+                format!(
+                    "Friedman test over {} functions: chi^2 = {:.4}, df = {}, p = {:.4e}",
+                    result.friedman_function_count,
+                    friedman.statistic,
+                    friedman.degrees_of_freedom,
+                    friedman.p_value
+                )
+                .bold()
+            );
+
+            for (label, average_rank) in
+                result.labels.iter().zip(&friedman.average_ranks)
+            {
+                println!(
+                    "  {:<label_width$} average rank {:.3}",
+                    label, average_rank
+                );
+            }
+        }
+        None => println!(
+            "{}",
+            "Friedman test skipped: no functions with results from all configurations."
+                .yellow()
+        ),
+    }
+}
@@ -0,0 +1,6 @@
+pub mod comparison;
+pub mod performance;
+pub mod plots;
+pub mod report;
+pub mod statistics;
+pub mod summary;
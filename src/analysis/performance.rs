@@ -0,0 +1,486 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+
+use crate::algorithms::common::trajectory::TrajectoryEntry;
+use crate::core::suite::SuiteType;
+
+/// Default amount of bootstrap samples for confidence intervals.
+pub const DEFAULT_BOOTSTRAP_SAMPLES: usize = 1000;
+
+/// Default confidence level of bootstrapped confidence intervals.
+pub const DEFAULT_CONFIDENCE_LEVEL: f64 = 0.95;
+
+/// Standard BBOB function groups.
+/// See http://numbbo.github.io/coco/testsuites/bbob for more info.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BBOBFunctionGroup {
+    /// f1 - f5
+    Separable,
+
+    /// f6 - f9
+    LowOrModerateConditioning,
+
+    /// f10 - f14
+    HighConditioningUnimodal,
+
+    /// f15 - f19
+    MultiModalAdequateGlobalStructure,
+
+    /// f20 - f24
+    MultiModalWeakGlobalStructure,
+}
+
+pub const ALL_BBOB_FUNCTION_GROUPS: [BBOBFunctionGroup; 5] = [
+    BBOBFunctionGroup::Separable,
+    BBOBFunctionGroup::LowOrModerateConditioning,
+    BBOBFunctionGroup::HighConditioningUnimodal,
+    BBOBFunctionGroup::MultiModalAdequateGlobalStructure,
+    BBOBFunctionGroup::MultiModalWeakGlobalStructure,
+];
+
+impl BBOBFunctionGroup {
+    /// Group of the function with `function_index` in `suite`
+    /// (only the functions of bbob and bbob-mixint are grouped).
+    pub fn from_function(
+        suite: SuiteType,
+        function_index: usize,
+    ) -> Option<Self> {
+        if !matches!(suite, SuiteType::Bbob | SuiteType::BbobMixint) {
+            return None;
+        }
+
+        match function_index {
+            1..=5 => Some(BBOBFunctionGroup::Separable),
+            6..=9 => Some(BBOBFunctionGroup::LowOrModerateConditioning),
+            10..=14 => Some(BBOBFunctionGroup::HighConditioningUnimodal),
+            15..=19 => {
+                Some(BBOBFunctionGroup::MultiModalAdequateGlobalStructure)
+            }
+            20..=24 => Some(BBOBFunctionGroup::MultiModalWeakGlobalStructure),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BBOBFunctionGroup::Separable => "Separable (f1-f5)",
+            BBOBFunctionGroup::LowOrModerateConditioning => {
+                "Low/moderate conditioning (f6-f9)"
+            }
+            BBOBFunctionGroup::HighConditioningUnimodal => {
+                "High conditioning, unimodal (f10-f14)"
+            }
+            BBOBFunctionGroup::MultiModalAdequateGlobalStructure => {
+                "Multi-modal, adequate structure (f15-f19)"
+            }
+            BBOBFunctionGroup::MultiModalWeakGlobalStructure => {
+                "Multi-modal, weak structure (f20-f24)"
+            }
+        }
+    }
+}
+
+/// First hitting times of a single independent run (one repetition on one function).
+#[derive(Clone, Debug)]
+pub struct RunHittingTimes {
+    pub suite: SuiteType,
+    pub function_index: usize,
+
+    /// Total amount of evaluations performed in the run.
+    pub total_evaluations: u64,
+
+    /// Evaluations needed to reach each precision target
+    /// (same order as the targets, `None` if the target was never reached).
+    pub hitting_times: Vec<Option<u64>>,
+}
+
+/// Best-so-far values of a single independent run (one repetition on one function),
+/// as pairs of (evaluations, best value).
+pub struct ConvergenceTrace {
+    pub suite: SuiteType,
+    pub function_index: usize,
+    pub repetition: usize,
+    pub points: Vec<(u64, f64)>,
+}
+
+/// Collects recorded trajectories into one convergence trace per function and repetition
+/// (sorted by suite, function index and repetition).
+///
+/// Evaluation counts that restart from zero (e.g. a fresh problem per run)
+/// are accumulated so they stay monotonic inside a repetition.
+pub fn convergence_traces(entries: &[TrajectoryEntry]) -> Vec<ConvergenceTrace> {
+    // (suite, function index, repetition) -> (trace, last raw evaluation count, evaluation offset)
+    let mut traces: BTreeMap<
+        (SuiteType, usize, usize),
+        (ConvergenceTrace, u64, u64),
+    > = BTreeMap::new();
+
+    for entry in entries {
+        let suite = entry.suite();
+        let (trace, last_evaluations, evaluation_offset) = traces
+            .entry((suite, entry.function_index, entry.repetition))
+            .or_insert_with(|| {
+                (
+                    ConvergenceTrace {
+                        suite,
+                        function_index: entry.function_index,
+                        repetition: entry.repetition,
+                        points: Vec::new(),
+                    },
+                    0,
+                    0,
+                )
+            });
+
+        if entry.evaluations < *last_evaluations {
+            *evaluation_offset += *last_evaluations;
+        }
+        *last_evaluations = entry.evaluations;
+
+        trace.points.push((
+            *evaluation_offset + entry.evaluations,
+            entry.best_value,
+        ));
+    }
+
+    traces.into_values().map(|(trace, _, _)| trace).collect()
+}
+
+/// Extracts first hitting times of `precision_targets` (distances from the global minimum)
+/// from recorded trajectories, one entry per function and repetition.
+pub fn hitting_times_from_trajectories(
+    entries: &[TrajectoryEntry],
+    precision_targets: &[f64],
+) -> Vec<RunHittingTimes> {
+    convergence_traces(entries)
+        .into_iter()
+        .filter_map(|trace| {
+            let function = trace.suite.function(trace.function_index)?;
+
+            let mut hitting_times = vec![None; precision_targets.len()];
+            for (evaluations, best_value) in &trace.points {
+                let gap = best_value - function.global_minimum();
+
+                for (hitting_time, target) in
+                    hitting_times.iter_mut().zip(precision_targets)
+                {
+                    if hitting_time.is_none() && gap <= *target {
+                        *hitting_time = Some(*evaluations);
+                    }
+                }
+            }
+
+            Some(RunHittingTimes {
+                suite: trace.suite,
+                function_index: trace.function_index,
+                total_evaluations: trace
+                    .points
+                    .iter()
+                    .map(|(evaluations, _)| *evaluations)
+                    .max()
+                    .unwrap_or(0),
+                hitting_times,
+            })
+        })
+        .collect()
+}
+
+/// Groups runs by suite and function index (sorted by suite and function index).
+pub fn runs_by_function(
+    runs: &[RunHittingTimes],
+) -> BTreeMap<(SuiteType, usize), Vec<&RunHittingTimes>> {
+    let mut grouped: BTreeMap<(SuiteType, usize), Vec<&RunHittingTimes>> =
+        BTreeMap::new();
+    for run in runs {
+        grouped
+            .entry((run.suite, run.function_index))
+            .or_default()
+            .push(run);
+    }
+
+    grouped
+}
+
+/// Expected running time (ERT) to reach the target at `target_index`:
+/// the sum of evaluations of all runs (until the target was hit or the run ended),
+/// divided by the amount of successful runs (infinite if there are none).
+pub fn expected_running_time(
+    runs: &[&RunHittingTimes],
+    target_index: usize,
+) -> f64 {
+    let mut total_evaluations = 0u64;
+    let mut successful_runs = 0usize;
+
+    for run in runs {
+        match run.hitting_times[target_index] {
+            Some(hitting_time) => {
+                total_evaluations += hitting_time;
+                successful_runs += 1;
+            }
+            None => total_evaluations += run.total_evaluations,
+        }
+    }
+
+    if successful_runs == 0 {
+        f64::INFINITY
+    } else {
+        total_evaluations as f64 / successful_runs as f64
+    }
+}
+
+/// Proportion of (run, target) pairs solved within `budget` evaluations, with every
+/// function weighted equally regardless of its amount of runs (runtime ECDF value).
+pub fn ecdf_proportion(functions: &[Vec<&RunHittingTimes>], budget: u64) -> f64 {
+    let non_empty_functions: Vec<&Vec<&RunHittingTimes>> =
+        functions.iter().filter(|runs| !runs.is_empty()).collect();
+    if non_empty_functions.is_empty() {
+        return 0f64;
+    }
+
+    let proportion_sum: f64 = non_empty_functions
+        .iter()
+        .map(|runs| {
+            let pairs: usize =
+                runs.iter().map(|run| run.hitting_times.len()).sum();
+            let solved_pairs = runs
+                .iter()
+                .flat_map(|run| run.hitting_times.iter())
+                .filter(|hitting_time| {
+                    matches!(hitting_time, Some(hitting_time) if *hitting_time <= budget)
+                })
+                .count();
+
+            if pairs == 0 {
+                0f64
+            } else {
+                solved_pairs as f64 / pairs as f64
+            }
+        })
+        .sum();
+
+    proportion_sum / non_empty_functions.len() as f64
+}
+
+/// Runtime ECDF as a step function: pairs of (budget, proportion of (run, target) pairs
+/// solved within that budget), sorted by budget. Functions are weighted equally.
+pub fn ecdf_steps(functions: &[Vec<&RunHittingTimes>]) -> Vec<(u64, f64)> {
+    let non_empty_functions: Vec<&Vec<&RunHittingTimes>> =
+        functions.iter().filter(|runs| !runs.is_empty()).collect();
+
+    // Every solved (run, target) pair increases the proportion by its weight.
+    let mut weighted_hits: Vec<(u64, f64)> = Vec::new();
+    for runs in &non_empty_functions {
+        let pairs: usize = runs.iter().map(|run| run.hitting_times.len()).sum();
+        if pairs == 0 {
+            continue;
+        }
+
+        let weight = 1f64 / (pairs * non_empty_functions.len()) as f64;
+        weighted_hits.extend(
+            runs.iter()
+                .flat_map(|run| run.hitting_times.iter().flatten())
+                .map(|hitting_time| (*hitting_time, weight)),
+        );
+    }
+
+    weighted_hits.sort_unstable_by_key(|(hitting_time, _)| *hitting_time);
+
+    let mut steps: Vec<(u64, f64)> = Vec::new();
+    let mut proportion = 0f64;
+    for (hitting_time, weight) in weighted_hits {
+        proportion += weight;
+
+        match steps.last_mut() {
+            Some(last_step) if last_step.0 == hitting_time => {
+                last_step.1 = proportion
+            }
+            _ => steps.push((hitting_time, proportion)),
+        }
+    }
+
+    steps
+}
+
+/// Options for bootstrapped confidence intervals.
+#[derive(Copy, Clone, Debug)]
+pub struct BootstrapOptions {
+    pub samples: usize,
+
+    /// Confidence level of the interval (e.g. `0.95`).
+    pub confidence_level: f64,
+
+    pub random_generator_seed: [u8; 16],
+}
+
+/// Percentile bootstrap confidence interval of `statistic`.
+///
+/// Each bootstrap sample resamples (with replacement) the runs of every function separately,
+/// so the amount of runs per function stays the same.
+pub fn bootstrap_confidence_interval<F>(
+    functions: &[Vec<&RunHittingTimes>],
+    options: &BootstrapOptions,
+    statistic: F,
+) -> (f64, f64)
+where
+    F: Fn(&[Vec<&RunHittingTimes>]) -> f64,
+{
+    let mut rng = Pcg64Mcg::from_seed(options.random_generator_seed);
+
+    let mut bootstrapped_statistics: Vec<f64> = (0..options.samples)
+        .map(|_| {
+            let resampled_functions: Vec<Vec<&RunHittingTimes>> = functions
+                .iter()
+                .map(|runs| {
+                    (0..runs.len())
+                        .map(|_| runs[rng.gen_range(0..runs.len())])
+                        .collect()
+                })
+                .collect();
+
+            statistic(&resampled_functions)
+        })
+        .collect();
+
+    if bootstrapped_statistics.is_empty() {
+        let value = statistic(functions);
+        return (value, value);
+    }
+
+    bootstrapped_statistics
+        .sort_unstable_by(|first, second| first.total_cmp(second));
+
+    let tail_probability = (1f64 - options.confidence_level) / 2f64;
+    let last_index = (bootstrapped_statistics.len() - 1) as f64;
+
+    let lower_index = (tail_probability * last_index).round() as usize;
+    let upper_index = ((1f64 - tail_probability) * last_index).round() as usize;
+
+    (
+        bootstrapped_statistics[lower_index],
+        bootstrapped_statistics[upper_index],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(
+        total_evaluations: u64,
+        hitting_times: Vec<Option<u64>>,
+    ) -> RunHittingTimes {
+        RunHittingTimes {
+            suite: SuiteType::Bbob,
+            function_index: 1,
+            total_evaluations,
+            hitting_times,
+        }
+    }
+
+    fn bootstrap_options(samples: usize) -> BootstrapOptions {
+        BootstrapOptions {
+            samples,
+            confidence_level: 0.95,
+            random_generator_seed: [7; 16],
+        }
+    }
+
+    #[test]
+    fn expected_running_time_of_successful_runs() {
+        let runs = [
+            run(1000, vec![Some(100), Some(400)]),
+            run(1000, vec![Some(200), Some(500)]),
+            run(1000, vec![Some(300), Some(900)]),
+        ];
+        let runs: Vec<&RunHittingTimes> = runs.iter().collect();
+
+        // Mean of the hitting times.
+        assert_eq!(expected_running_time(&runs, 0), 200f64);
+        assert_eq!(expected_running_time(&runs, 1), 600f64);
+    }
+
+    #[test]
+    fn expected_running_time_counts_evaluations_of_unsuccessful_runs() {
+        let runs = [
+            run(1000, vec![Some(100)]),
+            run(500, vec![None]),
+            run(1000, vec![Some(300)]),
+        ];
+        let runs: Vec<&RunHittingTimes> = runs.iter().collect();
+
+        // (100 + 500 + 300) evaluations for two successful runs.
+        assert_eq!(expected_running_time(&runs, 0), 450f64);
+    }
+
+    #[test]
+    fn expected_running_time_without_successful_runs() {
+        let runs = [run(1000, vec![None]), run(500, vec![None])];
+        let runs: Vec<&RunHittingTimes> = runs.iter().collect();
+
+        assert_eq!(expected_running_time(&runs, 0), f64::INFINITY);
+        assert_eq!(expected_running_time(&[], 0), f64::INFINITY);
+    }
+
+    #[test]
+    fn ecdf_proportion_weights_functions_equally() {
+        let first_function_runs = [
+            run(1000, vec![Some(10), Some(100)]),
+            run(1000, vec![Some(50), None]),
+        ];
+        let second_function_runs = [run(1000, vec![None, None])];
+        let functions: Vec<Vec<&RunHittingTimes>> = vec![
+            first_function_runs.iter().collect(),
+            second_function_runs.iter().collect(),
+            Vec::new(),
+        ];
+
+        // The first function has 4 (run, target) pairs, the second one 2
+        // (and is never solved). Empty functions are ignored.
+        assert_eq!(ecdf_proportion(&functions, 5), 0f64);
+        assert_eq!(ecdf_proportion(&functions, 10), 0.125);
+        assert_eq!(ecdf_proportion(&functions, 50), 0.25);
+        assert_eq!(ecdf_proportion(&functions, 100), 0.375);
+        assert_eq!(ecdf_proportion(&functions, u64::MAX), 0.375);
+
+        assert_eq!(ecdf_proportion(&[], 100), 0f64);
+    }
+
+    #[test]
+    fn bootstrap_of_identical_runs_is_exact() {
+        let runs = [run(1000, vec![Some(200)]), run(1000, vec![Some(200)])];
+        let functions: Vec<Vec<&RunHittingTimes>> = vec![runs.iter().collect()];
+
+        let interval = bootstrap_confidence_interval(
+            &functions,
+            &bootstrap_options(100),
+            |functions| expected_running_time(&functions[0], 0),
+        );
+        assert_eq!(interval, (200f64, 200f64));
+    }
+
+    #[test]
+    fn bootstrap_interval_covers_resampled_statistics() {
+        let runs = [run(1000, vec![Some(100)]), run(1000, vec![None])];
+        let functions: Vec<Vec<&RunHittingTimes>> = vec![runs.iter().collect()];
+
+        // Resamples have an ERT of 100 (both runs successful), 1100 or infinity
+        // (no successful run), each of the extremes with a probability of 1/4.
+        let interval = bootstrap_confidence_interval(
+            &functions,
+            &bootstrap_options(1000),
+            |functions| expected_running_time(&functions[0], 0),
+        );
+        assert_eq!(interval, (100f64, f64::INFINITY));
+
+        // Without samples, the statistic of the original runs is returned.
+        let interval = bootstrap_confidence_interval(
+            &functions,
+            &bootstrap_options(0),
+            |functions| expected_running_time(&functions[0], 0),
+        );
+        assert_eq!(interval, (1100f64, 1100f64));
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+const PLOT_WIDTH: f64 = 640f64;
+const PLOT_HEIGHT: f64 = 360f64;
+
+const MARGIN_LEFT: f64 = 70f64;
+const MARGIN_RIGHT: f64 = 20f64;
+const MARGIN_TOP: f64 = 30f64;
+const MARGIN_BOTTOM: f64 = 50f64;
+
+const SERIES_COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2",
+    "#7f7f7f", "#bcbd22", "#17becf",
+];
+
+/// Single line of a plot.
+pub struct PlotSeries {
+    pub label: String,
+
+    /// Points (x, y) sorted by x.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Axis scale of a plot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AxisScale {
+    Linear,
+
+    /// Base-10 logarithmic scale (non-positive values are not drawn).
+    Logarithmic,
+}
+
+/// A simple line plot that renders to a standalone SVG document.
+/// Lines are drawn as step functions (best-so-far values and ECDFs are both piecewise constant).
+pub struct LinePlot {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+
+    pub x_scale: AxisScale,
+    pub y_scale: AxisScale,
+
+    /// Fixed y-axis range (computed from the data if `None`).
+    pub y_range: Option<(f64, f64)>,
+
+    pub series: Vec<PlotSeries>,
+}
+
+struct Axis {
+    scale: AxisScale,
+    minimum: f64,
+    maximum: f64,
+}
+
+impl Axis {
+    fn from_values(values: impl Iterator<Item = f64>, scale: AxisScale) -> Self {
+        let (minimum, maximum) = values
+            .filter(|value| value.is_finite())
+            .filter(|value| scale == AxisScale::Linear || *value > 0f64)
+            .map(|value| Axis::transform(scale, value))
+            .fold(
+                (f64::INFINITY, f64::NEG_INFINITY),
+                |(minimum, maximum), value| {
+                    (minimum.min(value), maximum.max(value))
+                },
+            );
+
+        if !minimum.is_finite() {
+            return Self {
+                scale,
+                minimum: 0f64,
+                maximum: 1f64,
+            };
+        }
+
+        match scale {
+            // Round to whole decades so the ticks are nice.
+            AxisScale::Logarithmic => Self {
+                scale,
+                minimum: minimum.floor(),
+                maximum: maximum.ceil().max(minimum.floor() + 1f64),
+            },
+            AxisScale::Linear => {
+                if minimum == maximum {
+                    Self {
+                        scale,
+                        minimum: minimum - 1f64,
+                        maximum: maximum + 1f64,
+                    }
+                } else {
+                    Self {
+                        scale,
+                        minimum,
+                        maximum,
+                    }
+                }
+            }
+        }
+    }
+
+    fn from_range(range: (f64, f64), scale: AxisScale) -> Self {
+        Self {
+            scale,
+            minimum: Axis::transform(scale, range.0),
+            maximum: Axis::transform(scale, range.1),
+        }
+    }
+
+    #[inline]
+    fn transform(scale: AxisScale, value: f64) -> f64 {
+        match scale {
+            AxisScale::Linear => value,
+            AxisScale::Logarithmic => value.log10(),
+        }
+    }
+
+    /// Maps `value` to the 0-1 range of the axis (`None` if it can't be drawn).
+    fn normalize(&self, value: f64) -> Option<f64> {
+        if !value.is_finite()
+            || (self.scale == AxisScale::Logarithmic && value <= 0f64)
+        {
+            return None;
+        }
+
+        let normalized = (Axis::transform(self.scale, value) - self.minimum)
+            / (self.maximum - self.minimum);
+
+        Some(normalized.clamp(0f64, 1f64))
+    }
+
+    /// Returns pairs of (tick value, label).
+    fn ticks(&self) -> Vec<(f64, String)> {
+        match self.scale {
+            AxisScale::Logarithmic => {
+                let decades = (self.maximum - self.minimum) as usize;
+                let step = (decades / 8).max(1);
+
+                (self.minimum as i32..=self.maximum as i32)
+                    .step_by(step)
+                    .map(|exponent| {
+                        (10f64.powi(exponent), format!("1e{}", exponent))
+                    })
+                    .collect()
+            }
+            AxisScale::Linear => (0..=4)
+                .map(|tick_index| {
+                    let value = self.minimum
+                        + (self.maximum - self.minimum) * tick_index as f64
+                            / 4f64;
+                    (value, format!("{:.2}", value))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Escapes text for use inside HTML (and SVG) documents.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl LinePlot {
+    /// Renders the plot as a standalone SVG document.
+    pub fn to_svg(&self) -> String {
+        let x_axis = Axis::from_values(
+            self.series
+                .iter()
+                .flat_map(|series| series.points.iter().map(|(x, _)| *x)),
+            self.x_scale,
+        );
+        let y_axis = match self.y_range {
+            Some(y_range) => Axis::from_range(y_range, self.y_scale),
+            None => Axis::from_values(
+                self.series
+                    .iter()
+                    .flat_map(|series| series.points.iter().map(|(_, y)| *y)),
+                self.y_scale,
+            ),
+        };
+
+        let inner_width = PLOT_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let inner_height = PLOT_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+        let to_svg_x = |normalized: f64| MARGIN_LEFT + normalized * inner_width;
+        let to_svg_y =
+            |normalized: f64| MARGIN_TOP + (1f64 - normalized) * inner_height;
+
+        let mut svg = String::new();
+
+        // Writing into a `String` can't fail, so the `write!` results are ignored.
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{PLOT_WIDTH}" height="{PLOT_HEIGHT}" viewBox="0 0 {PLOT_WIDTH} {PLOT_HEIGHT}" font-family="sans-serif" font-size="11">"#
+        );
+        let _ = write!(
+            svg,
+            r#"<rect width="{PLOT_WIDTH}" height="{PLOT_HEIGHT}" fill="white"/>"#
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="18" text-anchor="middle" font-size="13" font-weight="bold">{}</text>"#,
+            PLOT_WIDTH / 2f64,
+            escape_html(&self.title)
+        );
+
+        // Grid, ticks and axis labels.
+        for (value, label) in x_axis.ticks() {
+            let Some(normalized) = x_axis.normalize(value) else {
+                continue;
+            };
+            let x = to_svg_x(normalized);
+
+            let _ = write!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{MARGIN_TOP}" x2="{x:.1}" y2="{:.1}" stroke="#e0e0e0"/><text x="{x:.1}" y="{:.1}" text-anchor="middle">{label}</text>"##,
+                MARGIN_TOP + inner_height,
+                MARGIN_TOP + inner_height + 15f64,
+            );
+        }
+        for (value, label) in y_axis.ticks() {
+            let Some(normalized) = y_axis.normalize(value) else {
+                continue;
+            };
+            let y = to_svg_y(normalized);
+
+            let _ = write!(
+                svg,
+                r##"<line x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{:.1}" y="{:.1}" text-anchor="end">{label}</text>"##,
+                MARGIN_LEFT + inner_width,
+                MARGIN_LEFT - 5f64,
+                y + 4f64,
+            );
+        }
+
+        let _ = write!(
+            svg,
+            r#"<rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{inner_width}" height="{inner_height}" fill="none" stroke="black"/>"#
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            MARGIN_LEFT + inner_width / 2f64,
+            PLOT_HEIGHT - 12f64,
+            escape_html(&self.x_label)
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="15" y="{:.1}" text-anchor="middle" transform="rotate(-90 15 {:.1})">{}</text>"#,
+            MARGIN_TOP + inner_height / 2f64,
+            MARGIN_TOP + inner_height / 2f64,
+            escape_html(&self.y_label)
+        );
+
+        // Lines (as step functions) and legend.
+        for (series_index, series) in self.series.iter().enumerate() {
+            let color = SERIES_COLORS[series_index % SERIES_COLORS.len()];
+
+            let visible_points: Vec<(f64, f64)> = series
+                .points
+                .iter()
+                .filter_map(|(x, y)| {
+                    Some((
+                        to_svg_x(x_axis.normalize(*x)?),
+                        to_svg_y(y_axis.normalize(*y)?),
+                    ))
+                })
+                .collect();
+
+            let mut path_points: Vec<(f64, f64)> = Vec::new();
+            for (x, y) in visible_points {
+                if let Some((_, previous_y)) = path_points.last() {
+                    path_points.push((x, *previous_y));
+                }
+                path_points.push((x, y));
+            }
+
+            if !path_points.is_empty() {
+                let _ = write!(
+                    svg,
+                    r#"<polyline fill="none" stroke="{color}" stroke-width="1.5" points="{}"/>"#,
+                    path_points
+                        .iter()
+                        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+                        .join(" ")
+                );
+            }
+
+            if !series.label.is_empty() {
+                let legend_y = MARGIN_TOP + 14f64 + series_index as f64 * 14f64;
+                let _ = write!(
+                    svg,
+                    r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{color}" stroke-width="2"/><text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#,
+                    MARGIN_LEFT + inner_width - 30f64,
+                    legend_y - 4f64,
+                    MARGIN_LEFT + inner_width - 10f64,
+                    legend_y - 4f64,
+                    MARGIN_LEFT + inner_width - 35f64,
+                    legend_y,
+                    escape_html(&series.label)
+                );
+            }
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
@@ -0,0 +1,513 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::algorithms::common::trajectory::TrajectoryEntry;
+use crate::analysis::performance::{
+    convergence_traces,
+    ecdf_steps,
+    hitting_times_from_trajectories,
+    runs_by_function,
+    BBOBFunctionGroup,
+    RunHittingTimes,
+    ALL_BBOB_FUNCTION_GROUPS,
+};
+use crate::analysis::plots::{escape_html, AxisScale, LinePlot, PlotSeries};
+use crate::analysis::statistics::{median, minimum};
+use crate::core::results::OptimizationResultRecord;
+use crate::core::suite::SuiteType;
+
+/// Gaps below this value are drawn at this value in the (logarithmic) convergence plots.
+const MINIMUM_PLOTTED_GAP: f64 = 1e-9;
+
+/// Summary of all runs on a single function.
+pub struct FunctionReportRow {
+    pub suite: SuiteType,
+    pub function_index: usize,
+    pub function_name: String,
+    pub runs: usize,
+
+    /// Known global minimum (see `SuiteFunction::global_minimum`),
+    /// `None` for functions outside the supported suites.
+    pub global_minimum: Option<f64>,
+
+    pub best_value: f64,
+    pub median_value: f64,
+    pub best_gap: f64,
+    pub median_gap: f64,
+
+    pub median_evaluations: f64,
+    pub median_wall_time_seconds: f64,
+}
+
+/// A plot along with a short identifier (used for file names when exporting).
+pub struct ReportPlot {
+    pub identifier: String,
+    pub plot: LinePlot,
+}
+
+/// Benchmark report built from result records and (optionally) recorded trajectories.
+pub struct BenchmarkReport {
+    pub title: String,
+    pub algorithms: Vec<String>,
+    pub precision_targets: Vec<f64>,
+
+    pub rows: Vec<FunctionReportRow>,
+
+    /// Convergence plots, one per function (empty without trajectories).
+    pub convergence_plots: Vec<ReportPlot>,
+
+    /// Runtime ECDF plots, one per BBOB function group and one over
+    /// all functions (empty without trajectories).
+    pub ecdf_plots: Vec<ReportPlot>,
+}
+
+fn build_rows(records: &[OptimizationResultRecord]) -> Vec<FunctionReportRow> {
+    let mut records_by_function: BTreeMap<
+        (SuiteType, usize),
+        Vec<&OptimizationResultRecord>,
+    > = BTreeMap::new();
+    for record in records {
+        records_by_function
+            .entry((record.suite(), record.function_index))
+            .or_default()
+            .push(record);
+    }
+
+    records_by_function
+        .into_values()
+        .map(|function_records| {
+            let first_record = function_records[0];
+            let global_minimum = first_record
+                .function()
+                .map(|function| function.global_minimum());
+
+            let values: Vec<f64> = function_records
+                .iter()
+                .map(|record| record.best_value)
+                .collect();
+            let gaps: Vec<f64> = match global_minimum {
+                Some(global_minimum) => {
+                    values.iter().map(|value| value - global_minimum).collect()
+                }
+                None => function_records
+                    .iter()
+                    .map(|record| record.gap_to_optimum)
+                    .collect(),
+            };
+
+            let evaluations: Vec<f64> = function_records
+                .iter()
+                .map(|record| record.evaluations as f64)
+                .collect();
+            let wall_times: Vec<f64> = function_records
+                .iter()
+                .map(|record| record.wall_time_seconds)
+                .collect();
+
+            FunctionReportRow {
+                suite: first_record.suite(),
+                function_index: first_record.function_index,
+                function_name: first_record.function_name.clone(),
+                runs: function_records.len(),
+                global_minimum,
+                best_value: minimum(&values),
+                median_value: median(&values),
+                best_gap: minimum(&gaps),
+                median_gap: median(&gaps),
+                median_evaluations: median(&evaluations),
+                median_wall_time_seconds: median(&wall_times),
+            }
+        })
+        .collect()
+}
+
+fn build_convergence_plots(trajectory: &[TrajectoryEntry]) -> Vec<ReportPlot> {
+    let traces = convergence_traces(trajectory);
+
+    traces
+        .iter()
+        .group_by(|trace| (trace.suite, trace.function_index))
+        .into_iter()
+        .filter_map(|((suite, function_index), function_traces)| {
+            let function = suite.function(function_index)?;
+
+            let series = function_traces
+                .map(|trace| {
+                    // Best-so-far values are piecewise constant,
+                    // so only the points where they change (and the final one) are kept.
+                    let mut points: Vec<(f64, f64)> = Vec::new();
+                    for (evaluations, best_value) in &trace.points {
+                        let gap = (best_value - function.global_minimum())
+                            .max(MINIMUM_PLOTTED_GAP);
+
+                        match points.last() {
+                            Some((_, last_gap)) if *last_gap == gap => {}
+                            _ => points.push((*evaluations as f64, gap)),
+                        }
+                    }
+                    if let Some((evaluations, best_value)) = trace.points.last()
+                    {
+                        let gap = (best_value - function.global_minimum())
+                            .max(MINIMUM_PLOTTED_GAP);
+                        if points.last() != Some(&(*evaluations as f64, gap)) {
+                            points.push((*evaluations as f64, gap));
+                        }
+                    }
+
+                    PlotSeries {
+                        label: format!("Run {}", trace.repetition),
+                        points,
+                    }
+                })
+                .collect();
+
+            // Functions of other suites are prefixed with the suite, their indices may overlap.
+            let identifier = match suite {
+                SuiteType::Bbob => format!("convergence-f{:02}", function_index),
+                _ => format!(
+                    "convergence-{}-f{:02}",
+                    suite.name(),
+                    function_index
+                ),
+            };
+
+            Some(ReportPlot {
+                identifier,
+                plot: LinePlot {
+                    title: format!(
+                        "f{} {} (global minimum {})",
+                        function_index,
+                        function.name(),
+                        function.global_minimum()
+                    ),
+                    x_label: "Evaluations".to_string(),
+                    y_label: "Best value - global minimum".to_string(),
+                    x_scale: AxisScale::Logarithmic,
+                    y_scale: AxisScale::Logarithmic,
+                    y_range: None,
+                    series,
+                },
+            })
+        })
+        .collect()
+}
+
+fn build_ecdf_plots(
+    trajectory: &[TrajectoryEntry],
+    precision_targets: &[f64],
+) -> Vec<ReportPlot> {
+    let runs = hitting_times_from_trajectories(trajectory, precision_targets);
+    let functions = runs_by_function(&runs);
+
+    let to_ecdf_plot =
+        |identifier: String,
+         title: &str,
+         function_set: Vec<Vec<&RunHittingTimes>>| {
+            let maximum_evaluations = function_set
+                .iter()
+                .flatten()
+                .map(|run| run.total_evaluations)
+                .max()
+                .unwrap_or(1)
+                .max(1);
+
+            // Start at zero and extend the last step to the largest budget.
+            let mut points: Vec<(f64, f64)> = vec![(1f64, 0f64)];
+            points.extend(
+                ecdf_steps(&function_set).into_iter().map(
+                    |(budget, proportion)| (budget.max(1) as f64, proportion),
+                ),
+            );
+            let final_proportion =
+                points.last().map(|(_, proportion)| *proportion);
+            if let Some(final_proportion) = final_proportion {
+                points.push((maximum_evaluations as f64, final_proportion));
+            }
+
+            ReportPlot {
+                identifier,
+                plot: LinePlot {
+                    title: title.to_string(),
+                    x_label: "Evaluations".to_string(),
+                    y_label: "Proportion of run/target pairs".to_string(),
+                    x_scale: AxisScale::Logarithmic,
+                    y_scale: AxisScale::Linear,
+                    y_range: Some((0f64, 1f64)),
+                    series: vec![PlotSeries {
+                        label: String::new(),
+                        points,
+                    }],
+                },
+            }
+        };
+
+    let mut plots: Vec<ReportPlot> = ALL_BBOB_FUNCTION_GROUPS
+        .iter()
+        .enumerate()
+        .filter_map(|(group_index, group)| {
+            let function_set: Vec<Vec<&RunHittingTimes>> = functions
+                .iter()
+                .filter(|((suite, function_index), _)| {
+                    BBOBFunctionGroup::from_function(*suite, *function_index)
+                        == Some(*group)
+                })
+                .map(|(_, runs)| runs.clone())
+                .collect();
+
+            if function_set.is_empty() {
+                return None;
+            }
+
+            Some(to_ecdf_plot(
+                format!("ecdf-group-{}", group_index + 1),
+                group.name(),
+                function_set,
+            ))
+        })
+        .collect();
+
+    if !functions.is_empty() {
+        plots.push(to_ecdf_plot(
+            "ecdf-all".to_string(),
+            "All functions",
+            functions.values().cloned().collect(),
+        ));
+    }
+
+    plots
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("{:.4}", value),
+        None => "-".to_string(),
+    }
+}
+
+impl BenchmarkReport {
+    pub fn new(
+        title: String,
+        records: &[OptimizationResultRecord],
+        trajectory: Option<&[TrajectoryEntry]>,
+        precision_targets: &[f64],
+    ) -> Self {
+        let algorithms = records
+            .iter()
+            .map(|record| record.algorithm.clone())
+            .unique()
+            .collect();
+
+        let (convergence_plots, ecdf_plots) = match trajectory {
+            Some(trajectory) => (
+                build_convergence_plots(trajectory),
+                build_ecdf_plots(trajectory, precision_targets),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        Self {
+            title,
+            algorithms,
+            precision_targets: precision_targets.to_vec(),
+            rows: build_rows(records),
+            convergence_plots,
+            ecdf_plots,
+        }
+    }
+
+    fn table_headers() -> [&'static str; 9] {
+        [
+            "Function",
+            "Runs",
+            "Global minimum",
+            "Best value",
+            "Median value",
+            "Best gap",
+            "Median gap",
+            "Median evaluations",
+            "Median time (s)",
+        ]
+    }
+
+    fn table_cells(row: &FunctionReportRow) -> [String; 9] {
+        [
+            match row.suite {
+                SuiteType::Bbob => {
+                    format!("f{} {}", row.function_index, row.function_name)
+                }
+                _ => format!(
+                    "f{} {} ({})",
+                    row.function_index,
+                    row.function_name,
+                    row.suite.name()
+                ),
+            },
+            row.runs.to_string(),
+            format_optional(row.global_minimum),
+            format!("{:.4}", row.best_value),
+            format!("{:.4}", row.median_value),
+            format!("{:.4e}", row.best_gap),
+            format!("{:.4e}", row.median_gap),
+            format!("{:.0}", row.median_evaluations),
+            format!("{:.2}", row.median_wall_time_seconds),
+        ]
+    }
+
+    fn ecdf_description(&self) -> String {
+        format!(
+            "Proportion of (run, target) pairs solved within a budget of evaluations, targets {} (distances from the global minimum).",
+            self.precision_targets
+                .iter()
+                .map(|target| format!("{:.0e}", target))
+                .join(", ")
+        )
+    }
+
+    /// Renders the report as a self-contained HTML document (plots are inlined as SVG).
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+
+        // Writing into a `String` can't fail, so the `write!` results are ignored.
+        let _ = write!(
+            html,
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; color: #222; }}
+table {{ border-collapse: collapse; margin-bottom: 2em; }}
+th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}
+th:first-child, td:first-child {{ text-align: left; }}
+th {{ background: #f0f0f0; }}
+.plots {{ display: flex; flex-wrap: wrap; gap: 1em; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>Algorithms: {algorithms}</p>
+"#,
+            title = escape_html(&self.title),
+            algorithms = escape_html(&self.algorithms.join(", ")),
+        );
+
+        html.push_str("<h2>Results per function</h2>\n<table>\n<tr>");
+        for header in Self::table_headers() {
+            let _ = write!(html, "<th>{}</th>", header);
+        }
+        html.push_str("</tr>\n");
+        for row in &self.rows {
+            html.push_str("<tr>");
+            for cell in Self::table_cells(row) {
+                let _ = write!(html, "<td>{}</td>", escape_html(&cell));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        if !self.convergence_plots.is_empty() {
+            html.push_str("<h2>Convergence</h2>\n<div class=\"plots\">\n");
+            for report_plot in &self.convergence_plots {
+                html.push_str(&report_plot.plot.to_svg());
+                html.push('\n');
+            }
+            html.push_str("</div>\n");
+        }
+
+        if !self.ecdf_plots.is_empty() {
+            let _ = write!(
+                html,
+                "<h2>Runtime ECDFs</h2>\n<p>{}</p>\n<div class=\"plots\">\n",
+                escape_html(&self.ecdf_description())
+            );
+            for report_plot in &self.ecdf_plots {
+                html.push_str(&report_plot.plot.to_svg());
+                html.push('\n');
+            }
+            html.push_str("</div>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Renders the report as Markdown. Plots are referenced as `<plot_directory>/<identifier>.svg`
+    /// (see `plot_files` for their contents).
+    pub fn to_markdown(&self, plot_directory: &str) -> String {
+        let mut markdown = String::new();
+
+        let _ = writeln!(markdown, "# {}\n", self.title);
+        let _ = writeln!(
+            markdown,
+            "Algorithms: {}\n",
+            self.algorithms.join(", ")
+        );
+
+        let _ = writeln!(markdown, "## Results per function\n");
+        let headers = Self::table_headers();
+        let _ = writeln!(markdown, "| {} |", headers.join(" | "));
+        let _ = writeln!(
+            markdown,
+            "|{}",
+            headers
+                .iter()
+                .enumerate()
+                .map(|(index, _)| if index == 0 { " --- |" } else { " ---: |" })
+                .join("")
+        );
+        for row in &self.rows {
+            let _ = writeln!(
+                markdown,
+                "| {} |",
+                Self::table_cells(row).join(" | ")
+            );
+        }
+        markdown.push('\n');
+
+        if !self.convergence_plots.is_empty() {
+            let _ = writeln!(markdown, "## Convergence\n");
+            for report_plot in &self.convergence_plots {
+                let _ = writeln!(
+                    markdown,
+                    "![{}]({}/{}.svg)\n",
+                    report_plot.plot.title,
+                    plot_directory,
+                    report_plot.identifier
+                );
+            }
+        }
+
+        if !self.ecdf_plots.is_empty() {
+            let _ = writeln!(markdown, "## Runtime ECDFs\n");
+            let _ = writeln!(markdown, "{}\n", self.ecdf_description());
+            for report_plot in &self.ecdf_plots {
+                let _ = writeln!(
+                    markdown,
+                    "![{}]({}/{}.svg)\n",
+                    report_plot.plot.title,
+                    plot_directory,
+                    report_plot.identifier
+                );
+            }
+        }
+
+        markdown
+    }
+
+    /// Returns pairs of (file name, SVG document) of all plots in the report.
+    pub fn plot_files(&self) -> Vec<(String, String)> {
+        self.convergence_plots
+            .iter()
+            .chain(self.ecdf_plots.iter())
+            .map(|report_plot| {
+                (
+                    format!("{}.svg", report_plot.identifier),
+                    report_plot.plot.to_svg(),
+                )
+            })
+            .collect()
+    }
+}
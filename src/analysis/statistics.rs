@@ -0,0 +1,459 @@
+use itertools::Itertools;
+
+/// Arithmetic mean of `values` (`NaN` if empty).
+pub fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Median of `values` (`NaN` if empty).
+pub fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_unstable_by(|first, second| first.total_cmp(second));
+
+    let middle = sorted_values.len() / 2;
+    if sorted_values.len() % 2 == 1 {
+        sorted_values[middle]
+    } else {
+        (sorted_values[middle - 1] + sorted_values[middle]) / 2f64
+    }
+}
+
+/// Sample standard deviation of `values` (with Bessel's correction, `0` for a single value).
+pub fn standard_deviation(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0f64;
+    }
+
+    let values_mean = mean(values);
+    let sum_of_squares = values
+        .iter()
+        .map(|value| (value - values_mean) * (value - values_mean))
+        .sum::<f64>();
+
+    (sum_of_squares / (values.len() - 1) as f64).sqrt()
+}
+
+/// Minimum of `values` (`+inf` if empty).
+pub fn minimum(values: &[f64]) -> f64 {
+    values.iter().copied().fold(f64::INFINITY, f64::min)
+}
+
+/// Maximum of `values` (`-inf` if empty).
+pub fn maximum(values: &[f64]) -> f64 {
+    values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Average (1-based) ranks of `values`, with tied values sharing the mean of their ranks.
+pub fn ranks_with_ties(values: &[f64]) -> Vec<f64> {
+    let mut sorted_indices: Vec<usize> = (0..values.len()).collect();
+    sorted_indices.sort_unstable_by(|first, second| {
+        values[*first].total_cmp(&values[*second])
+    });
+
+    let mut ranks = vec![0f64; values.len()];
+
+    let mut group_start = 0;
+    while group_start < sorted_indices.len() {
+        let mut group_end = group_start + 1;
+        while group_end < sorted_indices.len()
+            && values[sorted_indices[group_end]]
+                == values[sorted_indices[group_start]]
+        {
+            group_end += 1;
+        }
+
+        // Ranks `group_start + 1` to `group_end` (inclusive) are tied.
+        let average_rank = (group_start + 1 + group_end) as f64 / 2f64;
+        for sorted_index in &sorted_indices[group_start..group_end] {
+            ranks[*sorted_index] = average_rank;
+        }
+
+        group_start = group_end;
+    }
+
+    ranks
+}
+
+/// Complementary error function.
+/// Uses the Chebyshev approximation from Numerical Recipes (fractional error below `1.2e-7`).
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1f64 / (1f64 + 0.5 * z);
+
+    let result = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587
+                                        + t * (-0.82215223
+                                            + t * 0.17087277)))))))))
+            .exp();
+
+    if x >= 0f64 {
+        result
+    } else {
+        2f64 - result
+    }
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub fn standard_normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation, `x > 0`).
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+
+    let mut tmp = x + 5.5;
+    tmp -= (x + 0.5) * tmp.ln();
+
+    let mut y = x;
+    let mut series = 1.000000000190015;
+    for coefficient in COEFFICIENTS {
+        y += 1f64;
+        series += coefficient / y;
+    }
+
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`.
+pub fn regularized_upper_gamma(a: f64, x: f64) -> f64 {
+    const MAXIMUM_ITERATIONS: usize = 1000;
+    const EPSILON: f64 = 1e-14;
+    const FLOATING_POINT_MINIMUM: f64 = 1e-300;
+
+    if x <= 0f64 {
+        return 1f64;
+    }
+
+    let log_prefactor = -x + a * x.ln() - ln_gamma(a);
+
+    if x < a + 1f64 {
+        // Series representation of P(a, x), then Q = 1 - P.
+        let mut term_denominator = a;
+        let mut term = 1f64 / a;
+        let mut sum = term;
+
+        for _ in 0..MAXIMUM_ITERATIONS {
+            term_denominator += 1f64;
+            term *= x / term_denominator;
+            sum += term;
+
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        1f64 - sum * log_prefactor.exp()
+    } else {
+        // Continued fraction representation of Q(a, x) (modified Lentz's method).
+        let mut b = x + 1f64 - a;
+        let mut c = 1f64 / FLOATING_POINT_MINIMUM;
+        let mut d = 1f64 / b;
+        let mut fraction = d;
+
+        for iteration in 1..=MAXIMUM_ITERATIONS {
+            let an = -(iteration as f64) * (iteration as f64 - a);
+            b += 2f64;
+
+            d = an * d + b;
+            if d.abs() < FLOATING_POINT_MINIMUM {
+                d = FLOATING_POINT_MINIMUM;
+            }
+            c = b + an / c;
+            if c.abs() < FLOATING_POINT_MINIMUM {
+                c = FLOATING_POINT_MINIMUM;
+            }
+
+            d = 1f64 / d;
+            let delta = d * c;
+            fraction *= delta;
+
+            if (delta - 1f64).abs() < EPSILON {
+                break;
+            }
+        }
+
+        log_prefactor.exp() * fraction
+    }
+}
+
+/// Survival function (`1 - CDF`) of the chi-squared distribution.
+pub fn chi_squared_survival(statistic: f64, degrees_of_freedom: f64) -> f64 {
+    regularized_upper_gamma(degrees_of_freedom / 2f64, statistic / 2f64)
+}
+
+/// Two-sided Wilcoxon rank-sum (Mann-Whitney U) test.
+/// Uses the normal approximation with tie and continuity corrections.
+///
+/// Returns the p-value (`1` if either sample is empty or all values are tied).
+pub fn wilcoxon_rank_sum_test(first: &[f64], second: &[f64]) -> f64 {
+    if first.is_empty() || second.is_empty() {
+        return 1f64;
+    }
+
+    let first_count = first.len() as f64;
+    let second_count = second.len() as f64;
+    let total_count = first_count + second_count;
+
+    let combined: Vec<f64> =
+        first.iter().chain(second.iter()).copied().collect();
+    let ranks = ranks_with_ties(&combined);
+
+    let first_rank_sum: f64 = ranks[..first.len()].iter().sum();
+    let u_statistic = first_rank_sum - first_count * (first_count + 1f64) / 2f64;
+    let u_mean = first_count * second_count / 2f64;
+
+    // Tie correction: sum of (t^3 - t) over all groups of tied values.
+    let mut sorted_ranks = ranks.clone();
+    sorted_ranks.sort_unstable_by(|first, second| first.total_cmp(second));
+    let tie_correction: f64 = sorted_ranks
+        .iter()
+        .dedup_with_count()
+        .map(|(count, _)| {
+            let count = count as f64;
+            count * count * count - count
+        })
+        .sum();
+
+    let u_variance = first_count * second_count / 12f64
+        * ((total_count + 1f64)
+            - tie_correction / (total_count * (total_count - 1f64)));
+
+    if u_variance <= 0f64 {
+        return 1f64;
+    }
+
+    let z_statistic =
+        ((u_statistic - u_mean).abs() - 0.5).max(0f64) / u_variance.sqrt();
+
+    (2f64 * (1f64 - standard_normal_cdf(z_statistic))).min(1f64)
+}
+
+/// Holm-Bonferroni correction of multiple p-values.
+/// Returns the adjusted p-values in the same order as `p_values`.
+pub fn holm_correction(p_values: &[f64]) -> Vec<f64> {
+    let test_count = p_values.len();
+
+    let mut sorted_indices: Vec<usize> = (0..test_count).collect();
+    sorted_indices.sort_unstable_by(|first, second| {
+        p_values[*first].total_cmp(&p_values[*second])
+    });
+
+    let mut adjusted_p_values = vec![1f64; test_count];
+    let mut running_maximum = 0f64;
+
+    for (position, index) in sorted_indices.into_iter().enumerate() {
+        let adjusted =
+            ((test_count - position) as f64 * p_values[index]).min(1f64);
+        running_maximum = running_maximum.max(adjusted);
+        adjusted_p_values[index] = running_maximum;
+    }
+
+    adjusted_p_values
+}
+
+/// Result of the Friedman test.
+pub struct FriedmanTestResult {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+
+    /// Average rank of each treatment across all blocks (lower is better).
+    pub average_ranks: Vec<f64>,
+}
+
+/// Friedman test of `blocks`, where each block contains one value per treatment
+/// (e.g. the median result of each algorithm on a single function).
+/// Uses the chi-squared approximation with tie correction.
+pub fn friedman_test(blocks: &[Vec<f64>]) -> Option<FriedmanTestResult> {
+    let treatment_count = blocks.first()?.len();
+    if treatment_count < 2
+        || blocks.iter().any(|block| block.len() != treatment_count)
+    {
+        return None;
+    }
+
+    let block_count = blocks.len() as f64;
+    let k = treatment_count as f64;
+
+    let block_ranks: Vec<Vec<f64>> =
+        blocks.iter().map(|block| ranks_with_ties(block)).collect();
+
+    let rank_sums: Vec<f64> = (0..treatment_count)
+        .map(|treatment| block_ranks.iter().map(|ranks| ranks[treatment]).sum())
+        .collect();
+
+    let sum_of_squared_ranks: f64 = block_ranks
+        .iter()
+        .flat_map(|ranks| ranks.iter().map(|rank| rank * rank))
+        .sum();
+
+    let expected_rank_sum = block_count * (k + 1f64) / 2f64;
+    let numerator = (k - 1f64)
+        * rank_sums
+            .iter()
+            .map(|rank_sum| (rank_sum - expected_rank_sum).powi(2))
+            .sum::<f64>();
+    let denominator =
+        sum_of_squared_ranks - block_count * k * (k + 1f64).powi(2) / 4f64;
+
+    let statistic = if denominator > 0f64 {
+        numerator / denominator
+    } else {
+        0f64
+    };
+    let degrees_of_freedom = treatment_count - 1;
+
+    Some(FriedmanTestResult {
+        statistic,
+        degrees_of_freedom,
+        p_value: chi_squared_survival(statistic, degrees_of_freedom as f64),
+        average_ranks: rank_sums
+            .iter()
+            .map(|rank_sum| rank_sum / block_count)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn rank_sum_test_of_separated_samples() {
+        let p_value = wilcoxon_rank_sum_test(
+            &[1f64, 2f64, 3f64, 4f64, 5f64],
+            &[6f64, 7f64, 8f64, 9f64, 10f64],
+        );
+        assert_close(p_value, 0.0121858, 1e-6);
+
+        // The test is symmetric.
+        let swapped_p_value = wilcoxon_rank_sum_test(
+            &[6f64, 7f64, 8f64, 9f64, 10f64],
+            &[1f64, 2f64, 3f64, 4f64, 5f64],
+        );
+        assert_close(swapped_p_value, p_value, 1e-12);
+    }
+
+    #[test]
+    fn rank_sum_test_with_ties() {
+        let p_value = wilcoxon_rank_sum_test(
+            &[1f64, 2f64, 2f64, 3f64, 3f64],
+            &[2f64, 3f64, 4f64, 4f64, 5f64, 6f64],
+        );
+        assert_close(p_value, 0.0502903, 1e-6);
+    }
+
+    #[test]
+    fn rank_sum_test_of_equal_or_empty_samples() {
+        assert_eq!(
+            wilcoxon_rank_sum_test(&[3f64; 4], &[3f64; 5]),
+            1f64
+        );
+        assert_eq!(wilcoxon_rank_sum_test(&[], &[1f64, 2f64]), 1f64);
+
+        // Identical samples (z = 0, up to the precision of `erfc`).
+        assert_close(
+            wilcoxon_rank_sum_test(&[1f64, 2f64], &[1f64, 2f64]),
+            1f64,
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn holm_correction_keeps_order_and_monotonicity() {
+        let adjusted = holm_correction(&[0.01, 0.04, 0.03, 0.005]);
+        let expected = [0.03, 0.06, 0.06, 0.02];
+
+        assert_eq!(adjusted.len(), expected.len());
+        for (adjusted, expected) in adjusted.into_iter().zip(expected) {
+            assert_close(adjusted, expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn holm_correction_is_capped_at_one() {
+        assert_eq!(holm_correction(&[0.5, 0.6, 0.7]), vec![1f64; 3]);
+        assert!(holm_correction(&[]).is_empty());
+    }
+
+    #[test]
+    fn friedman_test_without_ties() {
+        let result = friedman_test(&[
+            vec![1f64, 2f64, 3f64],
+            vec![1f64, 2f64, 3f64],
+            vec![1f64, 3f64, 2f64],
+            vec![2f64, 1f64, 3f64],
+        ])
+        .unwrap();
+
+        assert_close(result.statistic, 4.5, 1e-12);
+        assert_eq!(result.degrees_of_freedom, 2);
+        assert_close(result.p_value, 0.1053992, 1e-6);
+        assert_eq!(result.average_ranks, vec![1.25, 2f64, 2.75]);
+    }
+
+    #[test]
+    fn friedman_test_with_ties() {
+        let result = friedman_test(&[
+            vec![1f64, 1f64, 2f64],
+            vec![3f64, 2f64, 1f64],
+            vec![1f64, 2f64, 3f64],
+            vec![2f64, 3f64, 3f64],
+        ])
+        .unwrap();
+
+        assert_close(result.statistic, 1.2857143, 1e-6);
+        assert_close(result.p_value, 0.5257880, 1e-6);
+    }
+
+    #[test]
+    fn friedman_test_of_invalid_blocks() {
+        assert!(friedman_test(&[]).is_none());
+        assert!(friedman_test(&[vec![1f64], vec![2f64]]).is_none());
+        assert!(friedman_test(&[vec![1f64, 2f64], vec![1f64]]).is_none());
+    }
+
+    #[test]
+    fn chi_squared_survival_reference_values() {
+        // Critical values at the 5 % level.
+        assert_close(chi_squared_survival(3.841459, 1f64), 0.05, 1e-6);
+        assert_close(chi_squared_survival(18.307038, 10f64), 0.05, 1e-6);
+
+        // Q(1, x / 2) = exp(-x / 2) for two degrees of freedom.
+        assert_close(
+            chi_squared_survival(4.5, 2f64),
+            (-2.25f64).exp(),
+            1e-9,
+        );
+        assert_close(chi_squared_survival(2f64, 3f64), 0.5724067, 1e-6);
+        assert_close(chi_squared_survival(1f64, 4f64), 0.9097960, 1e-6);
+
+        assert_eq!(chi_squared_survival(0f64, 3f64), 1f64);
+    }
+}
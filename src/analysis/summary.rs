@@ -0,0 +1,117 @@
+use colored::Colorize;
+use itertools::Itertools;
+
+use crate::analysis::statistics::{
+    maximum,
+    mean,
+    median,
+    minimum,
+    standard_deviation,
+};
+
+/// Default precision targets (distance from the global minimum) for success rates.
+pub const DEFAULT_PRECISION_TARGETS: [f64; 8] =
+    [1e-1, 1e-2, 1e-3, 1e-4, 1e-5, 1e-6, 1e-7, 1e-8];
+
+/// Summary statistics of the final gaps (distances from the global minimum)
+/// of repeated independent runs on a single function.
+pub struct GapSummary {
+    pub function_index: usize,
+    pub function_name: String,
+    pub repetitions: usize,
+
+    pub minimum: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub standard_deviation: f64,
+    pub maximum: f64,
+
+    /// Pairs of (precision target, ratio of runs whose gap is at or below the target).
+    pub success_rates: Vec<(f64, f64)>,
+}
+
+impl GapSummary {
+    pub fn from_gaps(
+        function_index: usize,
+        function_name: String,
+        gaps: &[f64],
+        precision_targets: &[f64],
+    ) -> Self {
+        let success_rates = precision_targets
+            .iter()
+            .map(|target| {
+                let successful_runs =
+                    gaps.iter().filter(|gap| **gap <= *target).count();
+
+                (
+                    *target,
+                    successful_runs as f64 / gaps.len() as f64,
+                )
+            })
+            .collect();
+
+        Self {
+            function_index,
+            function_name,
+            repetitions: gaps.len(),
+            minimum: minimum(gaps),
+            median: median(gaps),
+            mean: mean(gaps),
+            standard_deviation: standard_deviation(gaps),
+            maximum: maximum(gaps),
+            success_rates,
+        }
+    }
+}
+
+/// Prints a table of gap summaries (one row per function) to stdout.
+pub fn print_gap_summaries(summaries: &[GapSummary]) {
+    let Some(first_summary) = summaries.first() else {
+        return;
+    };
+
+    let target_headers = first_summary
+        .success_rates
+        .iter()
+        .map(|(target, _)| format!("{:>7}", format!("{:.0e}", target)))
+        .join(" ");
+
+    println!(
+        "{}",
+        format!(
+            "{:<36} {:>5} {:>12} {:>12} {:>12} {:>12} {:>12}  {}",
+            "Function",
+            "Runs",
+            "Min gap",
+            "Median gap",
+            "Mean gap",
+            "Std gap",
+            "Max gap",
+            target_headers,
+        )
+        .bold()
+    );
+
+    for summary in summaries {
+        let success_rates = summary
+            .success_rates
+            .iter()
+            .map(|(_, rate)| format!("{:>6.1}%", rate * 100f64))
+            .join(" ");
+
+        println!(
+            "{:<36} {:>5} {:>12.5} {:>12.5} {:>12.5} {:>12.5} {:>12.5}  {}",
+            format!(
+                "{:02} {}",
+                summary.function_index, summary.function_name
+            ),
+            summary.repetitions,
+            summary.minimum,
+            summary.median,
+            summary.mean,
+            summary.standard_deviation,
+            summary.maximum,
+            success_rates,
+        );
+    }
+}
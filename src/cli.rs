@@ -2,13 +2,22 @@ use clap::{Parser, Subcommand};
 use coco_rs::LogLevel;
 use miette::{miette, Context, IntoDiagnostic, Result};
 
+use crate::commands::analyze::{cmd_analyze, CLIAnalyzeArgs};
+use crate::commands::compare::{cmd_compare, CLICompareArgs};
 use crate::commands::firefly_optimization::{
     cmd_run_firefly_optimization,
     CLIFireflyOptimizationArgs,
 };
-use crate::commands::simulated_annealing::run_cmd_simulated_annealing;
+use crate::commands::import_logs::{cmd_import_logs, CLIImportLogsArgs};
+use crate::commands::nsga2::{cmd_run_nsga2, CLINSGA2Args};
+use crate::commands::report::{cmd_report, CLIReportArgs};
+use crate::commands::simulated_annealing::{
+    run_cmd_simulated_annealing,
+    CLISimulatedAnnealingArgs,
+};
 
 mod algorithms;
+mod analysis;
 mod commands;
 mod core;
 
@@ -20,7 +29,7 @@ struct CLIArgs {
     pub command: CLICommands,
 }
 
-#[derive(Subcommand, Eq, PartialEq)]
+#[derive(Subcommand)]
 enum CLICommands {
     #[command(
         name = "run-firefly-optimization",
@@ -32,7 +41,37 @@ enum CLICommands {
         name = "run-simulated-annealing",
         about = "Runs simulated annealing (local search optimization technique)."
     )]
-    RunSimulatedAnnealing,
+    RunSimulatedAnnealing(CLISimulatedAnnealingArgs),
+
+    #[command(
+        name = "run-nsga2",
+        about = "Runs NSGA-II on bi-objective problems (bbob-biobj or a pair of BBOB functions)."
+    )]
+    RunNSGA2(CLINSGA2Args),
+
+    #[command(
+        name = "compare",
+        about = "Compares the results of two or more runs with statistical tests."
+    )]
+    Compare(CLICompareArgs),
+
+    #[command(
+        name = "analyze",
+        about = "Computes expected running times and runtime ECDFs from a trajectory file."
+    )]
+    Analyze(CLIAnalyzeArgs),
+
+    #[command(
+        name = "report",
+        about = "Generates an HTML and Markdown benchmark report from a result file."
+    )]
+    Report(CLIReportArgs),
+
+    #[command(
+        name = "import-logs",
+        about = "Imports existing firefly optimization logs into the structured result format."
+    )]
+    ImportLogs(CLIImportLogsArgs),
 }
 
 
@@ -44,10 +83,18 @@ fn main() -> Result<()> {
 
     let args = CLIArgs::parse();
 
-    if let CLICommands::RunFireflyOptimization(args) = args.command {
-        cmd_run_firefly_optimization(args)?;
-    } else if args.command == CLICommands::RunSimulatedAnnealing {
-        run_cmd_simulated_annealing()?;
+    match args.command {
+        CLICommands::RunFireflyOptimization(args) => {
+            cmd_run_firefly_optimization(args)?
+        }
+        CLICommands::RunSimulatedAnnealing(args) => {
+            run_cmd_simulated_annealing(args)?
+        }
+        CLICommands::RunNSGA2(args) => cmd_run_nsga2(args)?,
+        CLICommands::Compare(args) => cmd_compare(args)?,
+        CLICommands::Analyze(args) => cmd_analyze(args)?,
+        CLICommands::Report(args) => cmd_report(args)?,
+        CLICommands::ImportLogs(args) => cmd_import_logs(args)?,
     }
 
     Ok(())
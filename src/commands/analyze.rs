@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use colored::Colorize;
+use itertools::Itertools;
+use miette::{miette, Result};
+
+use crate::algorithms::common::trajectory::read_trajectory_entries;
+use crate::analysis::performance::{
+    bootstrap_confidence_interval,
+    ecdf_proportion,
+    expected_running_time,
+    hitting_times_from_trajectories,
+    runs_by_function,
+    BBOBFunctionGroup,
+    BootstrapOptions,
+    RunHittingTimes,
+    ALL_BBOB_FUNCTION_GROUPS,
+    DEFAULT_BOOTSTRAP_SAMPLES,
+    DEFAULT_CONFIDENCE_LEVEL,
+};
+use crate::analysis::summary::DEFAULT_PRECISION_TARGETS;
+use crate::commands::arguments::parse_seed;
+use crate::core::suite::SuiteType;
+
+#[derive(Args)]
+pub struct CLIAnalyzeArgs {
+    #[arg(
+        help = "Trajectory file (.csv or .jsonl, see --trajectory) to analyze."
+    )]
+    pub trajectory_file: PathBuf,
+
+    #[arg(
+        long = "targets",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_PRECISION_TARGETS,
+        help = "Precision targets (distances from the global minimum) to compute running times for."
+    )]
+    pub precision_targets: Vec<f64>,
+
+    #[arg(
+        long = "bootstrap-samples",
+        default_value_t = DEFAULT_BOOTSTRAP_SAMPLES,
+        help = "Amount of bootstrap samples for confidence intervals."
+    )]
+    pub bootstrap_samples: usize,
+
+    #[arg(
+        long = "confidence",
+        default_value_t = DEFAULT_CONFIDENCE_LEVEL,
+        help = "Confidence level of the bootstrapped confidence intervals."
+    )]
+    pub confidence_level: f64,
+
+    #[arg(
+        long = "seed",
+        value_parser = parse_seed,
+        help = "Random generator seed for bootstrapping (unsigned integer)."
+    )]
+    pub random_generator_seed: Option<[u8; 16]>,
+}
+
+fn format_evaluations(evaluations: f64) -> String {
+    if evaluations.is_finite() {
+        format!("{:.2e}", evaluations)
+    } else {
+        "inf".to_string()
+    }
+}
+
+fn print_expected_running_times(
+    functions: &[((SuiteType, usize), Vec<&RunHittingTimes>)],
+    precision_targets: &[f64],
+    bootstrap_options: &BootstrapOptions,
+) {
+    println!(
+        "{}",
+        format!(
+            "Expected running time (evaluations, {:.0}% bootstrap confidence interval below)",
+            bootstrap_options.confidence_level * 100f64
+        )
+        .bold()
+    );
+    println!(
+        "{}",
+        format!(
+            "{:<36} {:>5} {}",
+            "Function",
+            "Runs",
+            precision_targets
+                .iter()
+                .map(|target| format!("{:>19}", format!("{:.0e}", target)))
+                .join(" ")
+        )
+        .bold()
+    );
+
+    for ((suite, function_index), runs) in functions {
+        let function_name = suite
+            .function(*function_index)
+            .map(|function| function.name())
+            .unwrap_or_default();
+
+        let expected_running_times = (0..precision_targets.len())
+            .map(|target_index| {
+                format!(
+                    "{:>19}",
+                    format_evaluations(expected_running_time(
+                        runs,
+                        target_index
+                    ))
+                )
+            })
+            .join(" ");
+
+        let confidence_intervals = (0..precision_targets.len())
+            .map(|target_index| {
+                let (lower, upper) = bootstrap_confidence_interval(
+                    std::slice::from_ref(runs),
+                    bootstrap_options,
+                    |resampled| {
+                        expected_running_time(&resampled[0], target_index)
+                    },
+                );
+
+                format!(
+                    "{:>19}",
+                    format!(
+                        "[{}, {}]",
+                        format_evaluations(lower),
+                        format_evaluations(upper)
+                    )
+                )
+            })
+            .join(" ");
+
+        println!(
+            "{:<36} {:>5} {}",
+            format!("{:02} {}", function_index, function_name),
+            runs.len(),
+            expected_running_times
+        );
+        println!(
+            "{}",
+            format!("{:<36} {:>5} {}", "", "", confidence_intervals).dimmed()
+        );
+    }
+}
+
+fn print_runtime_ecdfs(
+    functions: &[((SuiteType, usize), Vec<&RunHittingTimes>)],
+    bootstrap_options: &BootstrapOptions,
+) {
+    let maximum_evaluations = functions
+        .iter()
+        .flat_map(|(_, runs)| runs.iter().map(|run| run.total_evaluations))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    // Budgets 10^1, 10^2, ... up to (and including) the largest run.
+    let mut budgets: Vec<u64> = Vec::new();
+    let mut budget = 10u64;
+    loop {
+        budgets.push(budget);
+        if budget >= maximum_evaluations {
+            break;
+        }
+        budget = budget.saturating_mul(10);
+    }
+
+    let mut function_sets: Vec<(&str, Vec<Vec<&RunHittingTimes>>)> =
+        ALL_BBOB_FUNCTION_GROUPS
+            .iter()
+            .map(|group| {
+                (
+                    group.name(),
+                    functions
+                        .iter()
+                        .filter(|((suite, function_index), _)| {
+                            BBOBFunctionGroup::from_function(
+                                *suite,
+                                *function_index,
+                            ) == Some(*group)
+                        })
+                        .map(|(_, runs)| runs.clone())
+                        .collect(),
+                )
+            })
+            .collect();
+    function_sets.push((
+        "All functions",
+        functions.iter().map(|(_, runs)| runs.clone()).collect(),
+    ));
+
+    println!(
+        "{}",
+        format!(
+            "Runtime ECDF (proportion of run/target pairs solved within the budget, {:.0}% bootstrap confidence interval)",
+            bootstrap_options.confidence_level * 100f64
+        )
+        .bold()
+    );
+    println!(
+        "{}",
+        format!(
+            "{:<42} {}",
+            "Functions",
+            budgets
+                .iter()
+                .map(|budget| format!(
+                    "{:>19}",
+                    format!("{:.0e}", *budget as f64)
+                ))
+                .join(" ")
+        )
+        .bold()
+    );
+
+    for (name, function_set) in function_sets {
+        if function_set.is_empty() {
+            continue;
+        }
+
+        let proportions = budgets
+            .iter()
+            .map(|budget| {
+                let proportion = ecdf_proportion(&function_set, *budget);
+                let (lower, upper) = bootstrap_confidence_interval(
+                    &function_set,
+                    bootstrap_options,
+                    |resampled| ecdf_proportion(resampled, *budget),
+                );
+
+                format!(
+                    "{:>19}",
+                    format!("{:.2} [{:.2}, {:.2}]", proportion, lower, upper)
+                )
+            })
+            .join(" ");
+
+        println!("{:<42} {}", name, proportions);
+    }
+}
+
+pub fn cmd_analyze(args: CLIAnalyzeArgs) -> Result<()> {
+    if args.confidence_level <= 0f64 || args.confidence_level >= 1f64 {
+        return Err(miette!(
+            "Invalid --confidence {}: expected a value between 0 and 1.",
+            args.confidence_level
+        ));
+    }
+
+    let entries = read_trajectory_entries(&args.trajectory_file)?;
+    let runs =
+        hitting_times_from_trajectories(&entries, &args.precision_targets);
+    if runs.is_empty() {
+        return Err(miette!(
+            "Trajectory file {} contains no BBOB runs.",
+            args.trajectory_file.display()
+        ));
+    }
+
+    let functions: Vec<((SuiteType, usize), Vec<&RunHittingTimes>)> =
+        runs_by_function(&runs).into_iter().collect();
+
+    let bootstrap_options = BootstrapOptions {
+        samples: args.bootstrap_samples,
+        confidence_level: args.confidence_level,
+        random_generator_seed: args.random_generator_seed.unwrap_or_default(),
+    };
+
+    print_expected_running_times(
+        &functions,
+        &args.precision_targets,
+        &bootstrap_options,
+    );
+    println!();
+    print_runtime_ecdfs(&functions, &bootstrap_options);
+
+    Ok(())
+}
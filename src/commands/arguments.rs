@@ -0,0 +1,481 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Args;
+use miette::{miette, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::algorithms::common::checkpoint::{
+    read_checkpoint,
+    Checkpoint,
+    CheckpointRecorder,
+    DEFAULT_CHECKPOINT_INTERVAL_SECONDS,
+};
+use crate::algorithms::common::observer::{
+    OptimizationObserver,
+    SilentObserver,
+};
+use crate::algorithms::common::progress::{
+    JsonlProgressWriter,
+    PlainProgressPrinter,
+    ProgressMode,
+};
+use crate::algorithms::common::trajectory::{
+    TrajectoryFormat,
+    TrajectoryRecorder,
+    TrajectoryRecorderState,
+};
+use crate::analysis::summary::DEFAULT_PRECISION_TARGETS;
+use crate::core::cache::EvaluationCache;
+use crate::core::functions::SuiteFunction;
+use crate::core::noise::Resampling;
+use crate::core::problem::BBOBProblem;
+use crate::core::results::{
+    write_result_records,
+    OptimizationResultRecord,
+    ResultFormat,
+};
+use crate::core::solutions::SavedSolutions;
+use crate::core::suite::{BBOBSuite, SuiteType, BBOB_DIMENSIONS};
+
+/// Parses a random generator seed from the command line.
+///
+/// The seed is given as an unsigned 128-bit integer (e.g. `--seed 42`),
+/// which is then converted into the 16-byte seed our PCG generators expect.
+pub fn parse_seed(value: &str) -> Result<[u8; 16], String> {
+    value
+        .trim()
+        .parse::<u128>()
+        .map(|seed| seed.to_le_bytes())
+        .map_err(|_| {
+            format!("Invalid seed \"{value}\": expected an unsigned integer.")
+        })
+}
+
+/// Resizes `items` to exactly `length` elements, cycling through
+/// the original elements if more are needed.
+///
+/// Returns `None` if `items` is empty and `length` is non-zero
+/// (there is nothing to cycle through).
+pub fn cycle_to_length<T: Clone>(
+    items: Vec<T>,
+    length: usize,
+) -> Option<Vec<T>> {
+    if items.is_empty() && length > 0 {
+        return None;
+    }
+
+    Some(items.iter().cloned().cycle().take(length).collect())
+}
+
+
+#[derive(Args)]
+pub struct CLIOutputArgs {
+    #[arg(
+        long = "output",
+        help = "Write machine-readable results to this file (.json or .csv)."
+    )]
+    pub output_path: Option<PathBuf>,
+
+    #[arg(
+        long = "output-format",
+        value_enum,
+        help = "Format of the output file (inferred from the file extension by default)."
+    )]
+    pub output_format: Option<ResultFormat>,
+}
+
+impl CLIOutputArgs {
+    /// Returns the output file path and format, if an output file was requested.
+    pub fn output_path_and_format(
+        &self,
+    ) -> Result<Option<(&Path, ResultFormat)>> {
+        let Some(output_path) = self.output_path.as_deref() else {
+            return Ok(None);
+        };
+
+        let output_format = match self.output_format {
+            Some(output_format) => output_format,
+            None => ResultFormat::from_path(output_path).ok_or_else(|| {
+                miette!(
+                    "Could not infer output format from {}, please specify --output-format.",
+                    output_path.display()
+                )
+            })?,
+        };
+
+        Ok(Some((output_path, output_format)))
+    }
+
+    /// Writes (overwrites) the output file with `records`, if an output file was requested.
+    pub fn write_if_requested(
+        &self,
+        records: &[OptimizationResultRecord],
+    ) -> Result<()> {
+        if let Some((output_path, output_format)) =
+            self.output_path_and_format()?
+        {
+            write_result_records(output_path, output_format, records)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLITrajectoryArgs {
+    #[arg(
+        long = "trajectory",
+        help = "Record per-iteration convergence trajectories to this file (.csv or .jsonl)."
+    )]
+    pub trajectory_path: Option<PathBuf>,
+
+    #[arg(
+        long = "trajectory-format",
+        value_enum,
+        help = "Format of the trajectory file (inferred from the file extension by default)."
+    )]
+    pub trajectory_format: Option<TrajectoryFormat>,
+}
+
+impl CLITrajectoryArgs {
+    /// Returns the trajectory file path and format, if a trajectory file was requested.
+    fn trajectory_path_and_format(
+        &self,
+    ) -> Result<Option<(&Path, TrajectoryFormat)>> {
+        let Some(trajectory_path) = self.trajectory_path.as_deref() else {
+            return Ok(None);
+        };
+
+        let trajectory_format = match self.trajectory_format {
+            Some(trajectory_format) => trajectory_format,
+            None => TrajectoryFormat::from_path(trajectory_path).ok_or_else(|| {
+                miette!(
+                    "Could not infer trajectory format from {}, please specify --trajectory-format.",
+                    trajectory_path.display()
+                )
+            })?,
+        };
+
+        Ok(Some((trajectory_path, trajectory_format)))
+    }
+
+    /// Creates the trajectory recorder, if a trajectory file was requested.
+    pub fn create_recorder(&self) -> Result<Option<TrajectoryRecorder>> {
+        let Some((trajectory_path, trajectory_format)) =
+            self.trajectory_path_and_format()?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(TrajectoryRecorder::create(
+            trajectory_path,
+            trajectory_format,
+        )?))
+    }
+
+    /// Creates the trajectory recorder, continuing from the checkpointed `state` if there is one.
+    pub fn create_or_resume_recorder(
+        &self,
+        state: Option<&TrajectoryRecorderState>,
+    ) -> Result<Option<TrajectoryRecorder>> {
+        let Some(state) = state else {
+            return self.create_recorder();
+        };
+        let Some((trajectory_path, trajectory_format)) =
+            self.trajectory_path_and_format()?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(TrajectoryRecorder::resume(
+            trajectory_path,
+            trajectory_format,
+            state,
+        )?))
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLIRepetitionArgs {
+    #[arg(
+        long = "repetitions",
+        help = "Perform this many independent runs per problem (with seeds derived from the main seed) and summarize them."
+    )]
+    pub repetitions: Option<NonZeroUsize>,
+
+    #[arg(
+        long = "targets",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_PRECISION_TARGETS,
+        help = "Precision targets (distances from the global minimum) to report success rates for."
+    )]
+    pub precision_targets: Vec<f64>,
+}
+
+impl CLIRepetitionArgs {
+    /// Amount of runs to perform per problem (`1` if not specified).
+    pub fn repetitions(&self) -> usize {
+        self.repetitions.map(NonZeroUsize::get).unwrap_or(1)
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLIWarmStartArgs {
+    #[arg(
+        long = "warm-start",
+        help = "Start from previously found solutions in this file (a result file, of which only solutions from --suite are used, or a tab-separated vector file of bbob solutions such as results/firefly-optimization-results.txt)."
+    )]
+    pub warm_start_path: Option<PathBuf>,
+}
+
+impl CLIWarmStartArgs {
+    /// Loads the saved solutions of functions from `suite` (with `dimensions` values each),
+    /// if a warm start was requested.
+    pub fn load(
+        &self,
+        suite: SuiteType,
+        dimensions: usize,
+    ) -> Result<Option<SavedSolutions>> {
+        self.warm_start_path
+            .as_deref()
+            .map(|path| SavedSolutions::load(path, suite, dimensions))
+            .transpose()
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLICheckpointArgs {
+    #[arg(
+        long = "checkpoint",
+        help = "Periodically save the full optimizer state to this file (see --resume)."
+    )]
+    pub checkpoint_path: Option<PathBuf>,
+
+    #[arg(
+        long = "checkpoint-interval",
+        default_value_t = DEFAULT_CHECKPOINT_INTERVAL_SECONDS,
+        help = "Minimum amount of seconds between two checkpoints."
+    )]
+    pub checkpoint_interval_seconds: u64,
+
+    #[arg(
+        long = "resume",
+        requires = "checkpoint_path",
+        help = "Continue from the --checkpoint file (pass the same arguments as the interrupted command)."
+    )]
+    pub resume: bool,
+}
+
+impl CLICheckpointArgs {
+    /// Creates the checkpoint recorder, if a checkpoint file was requested.
+    pub fn create_recorder<C: Serialize>(
+        &self,
+    ) -> Option<CheckpointRecorder<C>> {
+        self.checkpoint_path.as_deref().map(|checkpoint_path| {
+            CheckpointRecorder::new(
+                checkpoint_path,
+                Duration::from_secs(self.checkpoint_interval_seconds),
+            )
+        })
+    }
+
+    /// Reads the checkpoint to continue from, if resuming was requested.
+    pub fn load_if_resuming<C: DeserializeOwned, S: DeserializeOwned>(
+        &self,
+    ) -> Result<Option<Checkpoint<C, S>>> {
+        match self.checkpoint_path.as_deref() {
+            Some(checkpoint_path) if self.resume => {
+                Ok(Some(read_checkpoint(checkpoint_path)?))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLIProgressArgs {
+    #[arg(
+        long = "progress",
+        value_enum,
+        default_value_t = ProgressMode::Auto,
+        help = "How to show optimization progress (auto: progress bars if stdout is a terminal, plain lines otherwise)."
+    )]
+    pub progress_mode: ProgressMode,
+
+    #[arg(
+        long = "quiet",
+        conflicts_with = "progress_mode",
+        help = "Don't show any optimization progress."
+    )]
+    pub quiet: bool,
+}
+
+impl CLIProgressArgs {
+    /// Creates the observer showing the progress of a single optimization
+    /// of a problem from `suite` (its name, see `SuiteType::name`) with `dimensions` variables,
+    /// using `create_progress_bars` for progress bars.
+    pub fn create_observer<B: OptimizationObserver + 'static>(
+        &self,
+        suite: &'static str,
+        dimensions: usize,
+        create_progress_bars: impl FnOnce() -> B,
+    ) -> Box<dyn OptimizationObserver> {
+        if self.quiet {
+            return Box::new(SilentObserver);
+        }
+
+        match self.progress_mode.resolve() {
+            ProgressMode::Auto | ProgressMode::Bars => {
+                Box::new(create_progress_bars())
+            }
+            ProgressMode::Plain => Box::new(PlainProgressPrinter::new()),
+            ProgressMode::Jsonl => {
+                Box::new(JsonlProgressWriter::new(suite, dimensions))
+            }
+        }
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLIEvaluationCacheArgs {
+    #[arg(
+        long = "evaluation-cache",
+        help = "Cache the objective values of up to this many evaluated points (least recently used ones are evicted first)."
+    )]
+    pub evaluation_cache_capacity: Option<NonZeroUsize>,
+
+    #[arg(
+        long = "uncounted-cache-hits",
+        requires = "evaluation_cache_capacity",
+        help = "Don't count cache hits as objective function evaluations (they then don't use up the evaluation budget)."
+    )]
+    pub uncounted_cache_hits: bool,
+}
+
+impl CLIEvaluationCacheArgs {
+    /// Enables the evaluation cache on `problem`, if requested.
+    pub fn apply_to(&self, problem: &mut BBOBProblem) {
+        problem.set_evaluation_cache(self.evaluation_cache_capacity.map(
+            |capacity| {
+                EvaluationCache::new(capacity, !self.uncounted_cache_hits)
+            },
+        ));
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLIEvaluationThreadsArgs {
+    #[arg(
+        long = "evaluation-threads",
+        help = "Evaluate the initial swarm of each restart on this many threads, each with its own problem handle (not supported with --suite bbob-constrained)."
+    )]
+    pub evaluation_threads: Option<NonZeroUsize>,
+}
+
+impl CLIEvaluationThreadsArgs {
+    /// Enables parallel batch evaluation on `problem`, if requested.
+    pub fn apply_to(&self, problem: &mut BBOBProblem) -> Result<()> {
+        match self.evaluation_threads {
+            Some(threads) => problem.set_evaluation_threads(threads),
+            None => Ok(()),
+        }
+    }
+}
+
+
+#[derive(Args)]
+pub struct CLISuiteArgs {
+    #[arg(
+        long = "suite",
+        value_enum,
+        default_value_t = SuiteType::Bbob,
+        help = "Benchmark suite to take the problems from."
+    )]
+    pub suite: SuiteType,
+
+    #[arg(
+        long = "resampling",
+        help = "How many noisy samples are drawn per evaluated point and how they are combined: \
+        single (default), mean-<N> or median-<N> (noisy functions only, see README)."
+    )]
+    pub resampling: Option<Resampling>,
+
+    #[arg(
+        long = "dimensions",
+        default_value_t = BBOB_DIMENSIONS,
+        help = "Dimension of the problems: 40, or 80, 160, 320 or 640 to take them from the \
+        bbob-largescale suite (noiseless functions only). bbob-mixint supports 40, 80 and 160, \
+        bbob-noisy and bbob-constrained only 40."
+    )]
+    pub dimensions: usize,
+}
+
+impl CLISuiteArgs {
+    /// Creates the suite the problems are taken from (see `--dimensions`).
+    pub fn create_suite(&self) -> Result<BBOBSuite> {
+        match self.suite {
+            SuiteType::Bbob => BBOBSuite::with_dimensions(self.dimensions),
+            SuiteType::BbobNoisy => {
+                if self.dimensions != BBOB_DIMENSIONS {
+                    return Err(miette!(
+                        "Noisy functions are only available in {} dimensions.",
+                        BBOB_DIMENSIONS
+                    ));
+                }
+
+                BBOBSuite::new()
+            }
+            SuiteType::BbobMixint => BBOBSuite::mixint(self.dimensions),
+            SuiteType::BbobConstrained => {
+                if self.dimensions != BBOB_DIMENSIONS {
+                    return Err(miette!(
+                        "Constrained functions are only available in {} dimensions.",
+                        BBOB_DIMENSIONS
+                    ));
+                }
+
+                BBOBSuite::constrained()
+            }
+        }
+    }
+
+    /// The selected problem of the suite, or all of them.
+    pub fn functions(
+        &self,
+        problem_number: Option<NonZeroUsize>,
+    ) -> Result<Vec<SuiteFunction>> {
+        match problem_number {
+            Some(problem_number) => {
+                let function = self
+                    .suite
+                    .function(problem_number.get())
+                    .ok_or_else(|| {
+                        miette!(
+                            "Invalid problem index (not in {} range).",
+                            self.suite.function_index_range()
+                        )
+                    })?;
+
+                Ok(vec![function])
+            }
+            None => Ok(self.suite.functions()),
+        }
+    }
+
+    /// Applies the resampling of noisy objective values to `problem`, if requested.
+    pub fn apply_to(&self, problem: &mut BBOBProblem) {
+        if let Some(resampling) = self.resampling {
+            problem.set_resampling(resampling);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miette::{miette, Result};
+
+use crate::analysis::comparison::{
+    compare_configurations,
+    print_comparison,
+    ComparedConfiguration,
+    DEFAULT_SIGNIFICANCE_LEVEL,
+};
+use crate::core::results::read_result_records;
+
+#[derive(Args)]
+pub struct CLICompareArgs {
+    #[arg(
+        num_args = 2..,
+        required = true,
+        help = "Result files (.json or .csv, see --output) of the runs to compare."
+    )]
+    pub result_files: Vec<PathBuf>,
+
+    #[arg(
+        long = "labels",
+        value_delimiter = ',',
+        help = "Comma-separated labels of the runs (file names are used by default)."
+    )]
+    pub labels: Option<Vec<String>>,
+
+    #[arg(
+        long = "alpha",
+        default_value_t = DEFAULT_SIGNIFICANCE_LEVEL,
+        help = "Significance level of the statistical tests."
+    )]
+    pub significance_level: f64,
+}
+
+pub fn cmd_compare(args: CLICompareArgs) -> Result<()> {
+    if args.significance_level <= 0f64 || args.significance_level >= 1f64 {
+        return Err(miette!(
+            "Invalid --alpha {}: expected a value between 0 and 1.",
+            args.significance_level
+        ));
+    }
+
+    let labels = match args.labels {
+        Some(labels) => {
+            if labels.len() != args.result_files.len() {
+                return Err(miette!(
+                    "Got {} labels for {} result files.",
+                    labels.len(),
+                    args.result_files.len()
+                ));
+            }
+
+            labels
+        }
+        None => args
+            .result_files
+            .iter()
+            .map(|path| {
+                path.file_stem()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect(),
+    };
+
+    let configurations = args
+        .result_files
+        .iter()
+        .zip(labels)
+        .map(|(path, label)| {
+            let records = read_result_records(path)?;
+            if records.is_empty() {
+                return Err(miette!(
+                    "Result file {} contains no results.",
+                    path.display()
+                ));
+            }
+
+            Ok(ComparedConfiguration { label, records })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let comparison =
+        compare_configurations(&configurations, args.significance_level);
+    print_comparison(&comparison);
+
+    Ok(())
+}
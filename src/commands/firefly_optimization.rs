@@ -3,71 +3,598 @@ use std::time::Instant;
 
 use clap::{Args, Subcommand};
 use itertools::Itertools;
-use miette::{miette, Result};
-
+use miette::{miette, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::common::boundary::BoundaryHandling;
+use crate::algorithms::common::checkpoint::{Checkpoint, CheckpointRecorder};
+use crate::algorithms::common::constraints::ConstraintHandling;
+use crate::algorithms::common::initialization::InitializationStrategy;
+use crate::algorithms::common::interrupt::{
+    install_interrupt_handler,
+    is_interrupt_requested,
+};
+use crate::algorithms::common::observer::MultiObserver;
+use crate::algorithms::common::rng::derive_seeds;
+use crate::algorithms::common::stopping::StoppingCriterion;
+use crate::algorithms::common::trajectory::TrajectoryRecorder;
+use crate::algorithms::firefly::checkpoint::FireflyOptimizationState;
+use crate::algorithms::firefly::visualization::FireflyOptimizationMultiProgressBar;
 use crate::algorithms::firefly::{
     get_optimized_hyperparameters,
     run_firefly_swarm_optimization,
+    FireflyFullOptimizationResult,
+    FireflyRunOptions,
+    FullFireflyOptions,
+};
+use crate::analysis::summary::{print_gap_summaries, GapSummary};
+use crate::commands::arguments::{
+    cycle_to_length,
+    parse_seed,
+    CLICheckpointArgs,
+    CLIEvaluationCacheArgs,
+    CLIEvaluationThreadsArgs,
+    CLIOutputArgs,
+    CLIProgressArgs,
+    CLIRepetitionArgs,
+    CLISuiteArgs,
+    CLITrajectoryArgs,
+    CLIWarmStartArgs,
 };
-use crate::core::functions::{BBOBFunctionType, ALL_BBOB_FUNCTIONS};
-use crate::core::suite::BBOBSuite;
+use crate::core::functions::SuiteFunction;
+use crate::core::results::{seed_to_u128, OptimizationResultRecord};
+use crate::core::solutions::SavedSolutions;
+use crate::core::suite::{BBOBSuite, SuiteType, BBOB_DIMENSIONS, BBOB_INSTANCE};
 
-#[derive(Args, Eq, PartialEq)]
+#[derive(Args)]
 pub struct CLIFireflyOptimizationArgs {
     #[command(subcommand)]
     pub mode: CLIFireflyOptimizationMode,
 }
 
-#[derive(Subcommand, Eq, PartialEq)]
+#[derive(Subcommand)]
 pub enum CLIFireflyOptimizationMode {
-    #[command(name = "all", about = "Optimize all 24 problems.")]
-    AllProblems,
+    #[command(name = "all", about = "Optimize all problems of the suite.")]
+    AllProblems(CLIRunAllArgs),
 
     #[command(name = "single", about = "Optimize a specific problem.")]
     OneProblem(CLIRunOneArgs),
 }
 
-#[derive(Args, Eq, PartialEq)]
+#[derive(Args)]
+pub struct CLIRunAllArgs {
+    #[command(flatten)]
+    pub run: CLIFireflyRunArgs,
+}
+
+#[derive(Args)]
 pub struct CLIRunOneArgs {
     #[arg(
         short = 'p',
         long = "problem",
-        help = "What problem to run (1 to 24)."
+        help = "What problem to run (1 to 24, 101 to 130 except 116 to 118 with --suite bbob-noisy, or 1 to 54 with --suite bbob-constrained)."
     )]
     pub problem_number: NonZeroUsize,
+
+    #[command(flatten)]
+    pub run: CLIFireflyRunArgs,
 }
 
-pub fn cmd_run_all_problems() -> Result<()> {
-    println!("-- Running firefly optimization on all 24 problems. --");
-    println!();
+/// Arguments shared by all firefly optimization modes.
+#[derive(Args)]
+pub struct CLIFireflyRunArgs {
+    #[command(flatten)]
+    pub option_overrides: CLIFireflyOptionOverrides,
 
-    // Initialize coco / BBOB suite.
-    let mut suite = BBOBSuite::new()?;
+    #[command(flatten)]
+    pub output: CLIOutputArgs,
 
-    let total_start_time = Instant::now();
+    #[command(flatten)]
+    pub trajectory: CLITrajectoryArgs,
+
+    #[command(flatten)]
+    pub repetitions: CLIRepetitionArgs,
+
+    #[command(flatten)]
+    pub warm_start: CLIWarmStartArgs,
+
+    #[command(flatten)]
+    pub checkpoint: CLICheckpointArgs,
+
+    #[command(flatten)]
+    pub progress: CLIProgressArgs,
+
+    #[command(flatten)]
+    pub evaluation_cache: CLIEvaluationCacheArgs,
+
+    #[command(flatten)]
+    pub evaluation_threads: CLIEvaluationThreadsArgs,
+
+    #[command(flatten)]
+    pub suite: CLISuiteArgs,
+}
+
+/// Command-line overrides for `FullFireflyOptions`.
+/// Every option that is not specified keeps its per-problem default
+/// (see `get_optimized_hyperparameters`).
+#[derive(Args)]
+pub struct CLIFireflyOptionOverrides {
+    #[arg(
+        long = "seed",
+        value_parser = parse_seed,
+        help = "Main random generator seed (unsigned integer)."
+    )]
+    pub random_generator_seed: Option<[u8; 16]>,
+
+    #[arg(
+        long = "restarts",
+        help = "Number of exploration restarts (default restart options are cycled if needed)."
+    )]
+    pub restarts: Option<NonZeroUsize>,
+
+    #[arg(
+        long = "refinement-runs",
+        help = "Number of refinement runs on the best solution (0 disables refinement)."
+    )]
+    pub refinement_runs: Option<usize>,
+
+    #[arg(
+        long = "stop-when",
+        help = "Stop the optimization early once this criterion is met, \
+        e.g. \"target=1e-8 | evaluations=1e6\" (see README)."
+    )]
+    pub stopping_criterion: Option<StoppingCriterion>,
+
+    #[command(flatten)]
+    pub run_overrides: CLIFireflyRunOptionOverrides,
+}
+
+/// Command-line overrides for `FireflyRunOptions`.
+/// These are applied to every exploration and refinement run.
+#[derive(Args)]
+pub struct CLIFireflyRunOptionOverrides {
+    #[arg(long = "swarm-size", help = "Amount of fireflies in the swarm.")]
+    pub swarm_size: Option<usize>,
+
+    #[arg(
+        long = "maximum-iterations",
+        help = "Maximum amount of iterations to perform per run."
+    )]
+    pub maximum_iterations: Option<usize>,
+
+    #[arg(
+        long = "consider-stuck-after",
+        help = "Abort the run after this many iterations without improvement."
+    )]
+    pub consider_stuck_after_n_iterations: Option<usize>,
+
+    #[arg(
+        long = "attractiveness",
+        help = "Coefficient of attraction to brighter fireflies (β_0)."
+    )]
+    pub attractiveness_coefficient: Option<f64>,
+
+    #[arg(
+        long = "light-absorption",
+        help = "Coefficient of light absorption (γ)."
+    )]
+    pub light_absorption_coefficient: Option<f64>,
+
+    #[arg(
+        long = "jitter-start",
+        help = "Starting movement jitter coefficient."
+    )]
+    pub movement_jitter_starting_coefficient: Option<f64>,
+
+    #[arg(
+        long = "jitter-cooling-factor",
+        help = "Factor the jitter is multiplied by on each improving iteration."
+    )]
+    pub movement_jitter_cooling_factor: Option<f64>,
+
+    #[arg(
+        long = "jitter-min-stuck-runs-to-reheat",
+        help = "Amount of stuck iterations after which the jitter starts heating up."
+    )]
+    pub movement_jitter_min_stuck_runs_to_reheat: Option<usize>,
+
+    #[arg(
+        long = "jitter-heating-factor",
+        help = "Factor the jitter is multiplied by on each stuck iteration (when reheating)."
+    )]
+    pub movement_jitter_heating_factor: Option<f64>,
+
+    #[arg(
+        long = "jitter-minimum",
+        help = "Lower bound for the movement jitter coefficient."
+    )]
+    pub movement_jitter_minimum_coefficient: Option<f64>,
+
+    #[arg(
+        long = "jitter-maximum",
+        help = "Upper bound for the movement jitter coefficient."
+    )]
+    pub movement_jitter_maximum_coefficient: Option<f64>,
+
+    #[arg(
+        long = "initialization",
+        help = "How the initial swarm is chosen: uniform, latin-hypercube, halton, sobol, \
+        opposition or best-of-<K> (see README)."
+    )]
+    pub initialization: Option<InitializationStrategy>,
+
+    #[arg(
+        long = "boundary-handling",
+        help = "How moves leaving the bounds are handled: clamp (default), reflect, wrap, random, \
+        midpoint, penalty[=<coefficient>] or discard (see README)."
+    )]
+    pub boundary_handling: Option<BoundaryHandling>,
+
+    #[arg(
+        long = "constraint-handling",
+        help = "How the brightness of fireflies violating the constraints is compared \
+        (bbob-constrained only): deb (default), static-penalty[=<coefficient>], \
+        adaptive-penalty[=<coefficient>] or epsilon=<level>[,<decay>] (see README)."
+    )]
+    pub constraint_handling: Option<ConstraintHandling>,
+}
+
+impl CLIFireflyRunOptionOverrides {
+    pub fn apply_to(&self, options: FireflyRunOptions) -> FireflyRunOptions {
+        let mut options = options;
+
+        if let Some(swarm_size) = self.swarm_size {
+            options = options.with_swarm_size(swarm_size);
+        }
+        if let Some(maximum_iterations) = self.maximum_iterations {
+            options = options.with_maximum_iterations(maximum_iterations);
+        }
+        if let Some(stuck_after) = self.consider_stuck_after_n_iterations {
+            options = options.with_consider_stuck_after_runs(stuck_after);
+        }
+        if let Some(attractiveness) = self.attractiveness_coefficient {
+            options = options.with_attractiveness_coefficient(attractiveness);
+        }
+        if let Some(light_absorption) = self.light_absorption_coefficient {
+            options =
+                options.with_light_absorption_coefficient(light_absorption);
+        }
+        if let Some(jitter_start) = self.movement_jitter_starting_coefficient {
+            options =
+                options.with_movement_jitter_starting_coefficient(jitter_start);
+        }
+        if let Some(cooling_factor) = self.movement_jitter_cooling_factor {
+            options =
+                options.with_movement_jitter_cooling_factor(cooling_factor);
+        }
+        if let Some(stuck_runs) = self.movement_jitter_min_stuck_runs_to_reheat {
+            options = options
+                .with_movement_jitter_min_stuck_runs_to_reheat(stuck_runs);
+        }
+        if let Some(heating_factor) = self.movement_jitter_heating_factor {
+            options =
+                options.with_movement_jitter_heating_factor(heating_factor);
+        }
+        if let Some(jitter_minimum) = self.movement_jitter_minimum_coefficient {
+            options =
+                options.with_movement_jitter_minimum_coefficient(jitter_minimum);
+        }
+        if let Some(jitter_maximum) = self.movement_jitter_maximum_coefficient {
+            options =
+                options.with_movement_jitter_maximum_coefficient(jitter_maximum);
+        }
+        if let Some(initialization) = self.initialization {
+            options = options.with_initialization(initialization);
+        }
+        if let Some(boundary_handling) = self.boundary_handling {
+            options = options.with_boundary_handling(boundary_handling);
+        }
+        if let Some(constraint_handling) = self.constraint_handling {
+            options = options.with_constraint_handling(constraint_handling);
+        }
+
+        options
+    }
+}
+
+impl CLIFireflyOptionOverrides {
+    pub fn apply_to(&self, options: FullFireflyOptions) -> FullFireflyOptions {
+        let random_generator_seed = self
+            .random_generator_seed
+            .unwrap_or(options.random_generator_seed);
+
+        let per_restart_options = match self.restarts {
+            Some(restarts) => {
+                cycle_to_length(options.per_restart_options, restarts.get())
+                    .expect("BUG: No default restart options.")
+            }
+            None => options.per_restart_options,
+        };
+
+        let post_process_best_options = match self.refinement_runs {
+            Some(0) => None,
+            Some(refinement_runs) => Some(
+                options
+                    .post_process_best_options
+                    .and_then(|runs| cycle_to_length(runs, refinement_runs))
+                    .unwrap_or_else(|| {
+                        vec![FireflyRunOptions::default(); refinement_runs]
+                    }),
+            ),
+            None => options.post_process_best_options,
+        };
+
+        FullFireflyOptions {
+            random_generator_seed,
+            per_restart_options: per_restart_options
+                .into_iter()
+                .map(|run_options| self.run_overrides.apply_to(run_options))
+                .collect(),
+            post_process_best_options: post_process_best_options.map(|runs| {
+                runs.into_iter()
+                    .map(|run_options| self.run_overrides.apply_to(run_options))
+                    .collect()
+            }),
+            stopping_criterion: self
+                .stopping_criterion
+                .clone()
+                .or(options.stopping_criterion),
+        }
+    }
+}
+
+fn build_result_record(
+    suite: SuiteType,
+    bbob_function: SuiteFunction,
+    dimensions: usize,
+    repetition: usize,
+    options: &FullFireflyOptions,
+    optimization_results: &FireflyFullOptimizationResult,
+    wall_time_seconds: f64,
+) -> Result<OptimizationResultRecord> {
+    Ok(OptimizationResultRecord {
+        suite: Some(suite),
+        function_index: bbob_function.index(),
+        function_name: bbob_function.name(),
+        instance: BBOB_INSTANCE,
+        dimension: dimensions,
+        algorithm: "firefly".to_string(),
+        repetition,
+        options: serde_json::to_value(options).into_diagnostic()?,
+        seed: seed_to_u128(options.random_generator_seed),
+        best_value: optimization_results.minimum.value,
+        best_vector: optimization_results.minimum.vector.clone(),
+        evaluations: optimization_results.evaluations_performed,
+        wall_time_seconds,
+        gap_to_optimum: optimization_results.minimum.value
+            - bbob_function.global_minimum(),
+        interrupted: optimization_results.interrupted,
+        constraint_violation: bbob_function
+            .is_constrained()
+            .then_some(optimization_results.minimum.constraint_violation),
+        feasible: bbob_function
+            .is_constrained()
+            .then(|| optimization_results.minimum.is_feasible()),
+    })
+}
+
+/// State of the command saved in checkpoints, along with the firefly optimization state.
+#[derive(Serialize, Deserialize)]
+struct FireflyCheckpointContext {
+    function_index: usize,
+
+    /// Dimension of the problem (see `--dimensions`).
+    #[serde(default = "default_checkpoint_dimensions")]
+    dimensions: usize,
+
+    /// Whether the problem is from the bbob-mixint suite.
+    #[serde(default)]
+    mixed_integer: bool,
+
+    /// Whether the problem is from the bbob-constrained suite.
+    #[serde(default)]
+    constrained: bool,
+
+    /// Repetition number (1-based) being optimized.
+    repetition: usize,
+
+    /// Options of the repetition being optimized (resuming is only bit-exact with the same options).
+    options: serde_json::Value,
+
+    /// Results of all finished repetitions.
+    result_records: Vec<OptimizationResultRecord>,
+}
+
+fn default_checkpoint_dimensions() -> usize {
+    BBOB_DIMENSIONS
+}
+
+type FireflyCheckpoint =
+    Checkpoint<FireflyCheckpointContext, FireflyOptimizationState>;
+
+/// Settings shared by the optimization of all problems in a single command invocation.
+struct ProblemRunSettings<'a> {
+    option_overrides: &'a CLIFireflyOptionOverrides,
+    repetitions: usize,
+    output: &'a CLIOutputArgs,
+    warm_start: Option<&'a SavedSolutions>,
+    progress: &'a CLIProgressArgs,
+    evaluation_cache: &'a CLIEvaluationCacheArgs,
+    evaluation_threads: &'a CLIEvaluationThreadsArgs,
+    suite: &'a CLISuiteArgs,
+}
+
+/// Performs all repetitions of the firefly optimization on a single problem,
+/// printing and recording the results of each one.
+///
+/// Repetitions that already have a result in `result_records` (when resuming) are skipped.
+/// The first repetition that is performed continues from `resume_from`, if there is a checkpoint left.
+/// On Ctrl-C, the repetition in progress reports its best-so-far result and no further repetitions are performed.
+///
+/// Returns the final gaps (distances from the global minimum) of all finished repetitions.
+fn optimize_problem_with_repetitions(
+    suite: &mut BBOBSuite,
+    bbob_function: SuiteFunction,
+    settings: &ProblemRunSettings,
+    result_records: &mut Vec<OptimizationResultRecord>,
+    resume_from: &mut Option<FireflyCheckpoint>,
+    mut checkpoint: Option<&mut CheckpointRecorder<FireflyCheckpointContext>>,
+    mut trajectory: Option<&mut TrajectoryRecorder>,
+) -> Result<Vec<f64>> {
+    let ProblemRunSettings {
+        option_overrides,
+        repetitions,
+        output,
+        warm_start,
+        progress,
+        evaluation_cache,
+        evaluation_threads,
+        suite: suite_args,
+    } = *settings;
+
+    let optimized_hyperparameters = option_overrides.apply_to(
+        get_optimized_hyperparameters(bbob_function.base_function()),
+    );
+    let repetition_seeds = derive_seeds(
+        optimized_hyperparameters.random_generator_seed,
+        repetitions,
+    );
+
+    let mut final_gaps: Vec<f64> = result_records
+        .iter()
+        .filter(|record| record.function_index == bbob_function.index())
+        .map(|record| record.gap_to_optimum)
+        .collect();
+
+    for (repetition_index, repetition_seed) in repetition_seeds
+        .into_iter()
+        .enumerate()
+        .skip(final_gaps.len())
+    {
+        if is_interrupt_requested() {
+            break;
+        }
 
-    // TODO We can actually parallelize this by running multiple individual problems at the same time.
-    for bbob_function in ALL_BBOB_FUNCTIONS {
         let problem_start_time = Instant::now();
 
-        let optimized_hyperparameters =
-            get_optimized_hyperparameters(bbob_function);
-        let problem = suite.problem(bbob_function)?;
+        let repetition_hyperparameters = FullFireflyOptions {
+            random_generator_seed: repetition_seed,
+            ..optimized_hyperparameters.clone()
+        };
+        let repetition_options =
+            serde_json::to_value(&repetition_hyperparameters)
+                .into_diagnostic()?;
+        let dimensions = suite.dimensions();
+        let mut problem = suite.problem(bbob_function)?;
+        evaluation_cache.apply_to(&mut problem);
+        evaluation_threads.apply_to(&mut problem)?;
+        suite_args.apply_to(&mut problem);
+
+        let (resumed_state, elapsed_seconds_before) = match resume_from.take() {
+            Some(resumed) => {
+                let context = &resumed.context;
+                if context.function_index != bbob_function.index()
+                    || context.dimensions != dimensions
+                    || context.mixed_integer != problem.is_mixed_integer()
+                    || context.constrained != problem.is_constrained()
+                    || context.repetition != repetition_index + 1
+                    || context.options != repetition_options
+                {
+                    return Err(miette!(
+                        "The checkpoint is for problem {} (repetition {}) with different arguments, \
+                        resume with the same arguments as the interrupted command.",
+                        context.function_index,
+                        context.repetition
+                    ));
+                }
+
+                (
+                    Some(resumed.state),
+                    resumed.problem_elapsed_seconds,
+                )
+            }
+            None => (None, 0f64),
+        };
+
+        if let Some(checkpoint) = checkpoint.as_deref_mut() {
+            checkpoint.start_problem(
+                FireflyCheckpointContext {
+                    function_index: bbob_function.index(),
+                    dimensions,
+                    mixed_integer: problem.is_mixed_integer(),
+                    constrained: problem.is_constrained(),
+                    repetition: repetition_index + 1,
+                    options: repetition_options,
+                    result_records: result_records.clone(),
+                },
+                elapsed_seconds_before,
+            );
+        }
 
-        println!(
-            "[[Problem {:02}/{:02} ({}) | global minimum is {:.4}]]",
-            bbob_function.index(),
-            ALL_BBOB_FUNCTIONS.len(),
-            bbob_function.name(),
-            bbob_function.global_minimum(),
+        if repetitions > 1 {
+            println!(
+                "[[Problem {:02}/{:02} ({}) | repetition {}/{} | global minimum is {:.4}]]",
+                bbob_function.index(),
+                bbob_function.suite().last_function_index(),
+                bbob_function.name(),
+                repetition_index + 1,
+                repetitions,
+                bbob_function.global_minimum(),
+            );
+        } else {
+            println!(
+                "[[Problem {:02}/{:02} ({}) | global minimum is {:.4}]]",
+                bbob_function.index(),
+                bbob_function.suite().last_function_index(),
+                bbob_function.name(),
+                bbob_function.global_minimum(),
+            );
+        }
+
+        if resumed_state.is_some() {
+            println!("  (resuming from checkpoint)");
+        } else if let Some(trajectory) = trajectory.as_deref_mut() {
+            trajectory.start_problem(
+                suite_args.suite,
+                bbob_function.index(),
+                repetition_index + 1,
+            );
+        }
+
+        let mut progress_observer = progress.create_observer(
+            suite_args.suite.name(),
+            dimensions,
+            FireflyOptimizationMultiProgressBar::new,
         );
+        let mut observer = MultiObserver::new()
+            .with_observer(progress_observer.as_mut())
+            .with_optional_observer(trajectory.as_deref_mut());
 
         let optimization_results = run_firefly_swarm_optimization(
             problem,
-            optimized_hyperparameters.clone(),
+            repetition_hyperparameters.clone(),
+            warm_start.and_then(|solutions| {
+                solutions.vector_for(suite_args.suite, bbob_function)
+            }),
+            resumed_state,
+            checkpoint.as_deref_mut(),
+            &mut observer,
         )?;
 
-        let problem_delta_time = problem_start_time.elapsed().as_secs_f64();
+        let problem_delta_time =
+            elapsed_seconds_before + problem_start_time.elapsed().as_secs_f64();
+
+        // Rewrite the output file after each run, so we don't lose the results on a crash.
+        result_records.push(build_result_record(
+            suite_args.suite,
+            bbob_function,
+            dimensions,
+            repetition_index + 1,
+            &repetition_hyperparameters,
+            &optimization_results,
+            problem_delta_time,
+        )?);
+        output.write_if_requested(result_records)?;
 
 
         let formatted_parameters = optimization_results
@@ -77,99 +604,222 @@ pub fn cmd_run_all_problems() -> Result<()> {
             .map(|parameter| parameter.to_string())
             .join(",");
 
-        println!(
-            "\n  Optimized in {:.4} seconds",
-            problem_delta_time
-        );
+        let final_gap =
+            optimization_results.minimum.value - bbob_function.global_minimum();
+
+        if optimization_results.interrupted {
+            println!(
+                "\n  Interrupted after {:.4} seconds (best so far)",
+                problem_delta_time
+            );
+        } else {
+            final_gaps.push(final_gap);
+
+            println!(
+                "\n  Optimized in {:.4} seconds{}",
+                problem_delta_time,
+                if optimization_results.stopping_criterion_met {
+                    " (stopping criterion met)"
+                } else {
+                    ""
+                }
+            );
+        }
 
         println!(
             "  Minimum: {}",
             optimization_results.minimum.value,
         );
         println!("  At: [{}]", formatted_parameters);
+        if bbob_function.is_constrained() {
+            println!(
+                "  {}",
+                optimization_results.minimum.describe_feasibility()
+            );
+        }
 
-        println!(
-            "  Distance from global minimum: {:.5}",
-            optimization_results.minimum.value - bbob_function.global_minimum()
-        );
+        println!("  Distance from global minimum: {:.5}", final_gap);
+        if let Some(cache_statistics) =
+            optimization_results.evaluation_cache_statistics
+        {
+            println!(
+                "  Evaluation cache: {}",
+                cache_statistics.describe()
+            );
+        }
         println!();
         println!();
     }
 
-    let total_delta_time = total_start_time.elapsed().as_secs_f64();
-
-    println!(
-        "\n-- Optimized all 24 problems in {:.4} seconds. --",
-        total_delta_time
-    );
-
-    Ok(())
+    Ok(final_gaps)
 }
 
-pub fn cmd_run_specific_problem(args: CLIRunOneArgs) -> Result<()> {
-    let bbob_function =
-        BBOBFunctionType::from_function_index(args.problem_number.into())
-            .ok_or_else(|| {
-                miette!("Invalid problem index (not in 1-24 range).")
-            })?;
+fn cmd_run_problems(
+    bbob_functions: &[SuiteFunction],
+    args: &CLIFireflyRunArgs,
+) -> Result<()> {
+    let CLIFireflyRunArgs {
+        option_overrides,
+        output,
+        trajectory,
+        repetitions: repetition_args,
+        warm_start,
+        checkpoint: checkpoint_args,
+        progress,
+        evaluation_cache,
+        evaluation_threads,
+        suite: suite_args,
+    } = args;
+
+    // Fail early if the output file format can't be determined.
+    output.output_path_and_format()?;
+
+    install_interrupt_handler()?;
+
+    let mut resume_from: Option<FireflyCheckpoint> =
+        checkpoint_args.load_if_resuming()?;
+    let mut checkpoint = checkpoint_args.create_recorder();
+
+    let warm_start = warm_start.load(suite_args.suite, suite_args.dimensions)?;
+    let settings = ProblemRunSettings {
+        option_overrides,
+        repetitions: repetition_args.repetitions(),
+        output,
+        warm_start: warm_start.as_ref(),
+        progress,
+        evaluation_cache,
+        evaluation_threads,
+        suite: suite_args,
+    };
+
+    if let Some(resumed) = resume_from.as_ref() {
+        if resumed.trajectory.is_none() && trajectory.trajectory_path.is_some() {
+            return Err(miette!(
+                "The checkpoint was saved without --trajectory, so it can't be resumed with one."
+            ));
+        }
+    }
 
-    println!(
-        "[[Problem {} ({}) | global minimum is {:.4}]]",
-        bbob_function.index(),
-        bbob_function.name(),
-        bbob_function.global_minimum(),
-    );
+    // When resuming, continue with the results and trajectory as they were at the checkpoint.
+    let mut result_records: Vec<OptimizationResultRecord> = resume_from
+        .as_ref()
+        .map(|resumed| resumed.context.result_records.clone())
+        .unwrap_or_default();
+    let mut trajectory = trajectory.create_or_resume_recorder(
+        resume_from
+            .as_ref()
+            .and_then(|resumed| resumed.trajectory.as_ref()),
+    )?;
 
-    let problem_start_time = Instant::now();
+    let mut gap_summaries: Vec<GapSummary> =
+        Vec::with_capacity(bbob_functions.len());
 
     // Initialize coco / BBOB suite.
-    let mut suite = BBOBSuite::new()?;
+    let mut suite = settings.suite.create_suite()?;
 
-    let optimized_hyperparameters = get_optimized_hyperparameters(bbob_function);
-    let problem = suite.problem(bbob_function)?;
+    let total_start_time = Instant::now();
 
-    let optimization_results =
-        run_firefly_swarm_optimization(problem, optimized_hyperparameters)?;
+    // TODO We can actually parallelize this by running multiple individual problems at the same time.
+    for bbob_function in bbob_functions.iter().copied() {
+        let final_gaps = optimize_problem_with_repetitions(
+            &mut suite,
+            bbob_function,
+            &settings,
+            &mut result_records,
+            &mut resume_from,
+            checkpoint.as_mut(),
+            trajectory.as_mut(),
+        )?;
 
-    let problem_delta_time = problem_start_time.elapsed().as_secs_f64();
+        if !final_gaps.is_empty() {
+            gap_summaries.push(GapSummary::from_gaps(
+                bbob_function.index(),
+                bbob_function.name(),
+                &final_gaps,
+                &repetition_args.precision_targets,
+            ));
+        }
 
+        if is_interrupt_requested() {
+            break;
+        }
+    }
 
-    let formatted_parameters = optimization_results
-        .minimum
-        .vector
-        .iter()
-        .map(|parameter| parameter.to_string())
-        .join(",");
+    let total_delta_time = total_start_time.elapsed().as_secs_f64();
 
-    println!();
-    println!();
-    println!(
-        "Problem {:02}/{:02} ({}) optimized in {:.4} seconds.",
-        bbob_function.index(),
-        ALL_BBOB_FUNCTIONS.len(),
-        bbob_function.name(),
-        problem_delta_time,
-    );
+    if is_interrupt_requested() {
+        println!(
+            "\n-- Interrupted after {:.4} seconds. --",
+            total_delta_time
+        );
+
+        if repetition_args.repetitions.is_some() {
+            println!();
+            print_gap_summaries(&gap_summaries);
+        }
+
+        return Err(match checkpoint_args.checkpoint_path.as_deref() {
+            Some(checkpoint_path) => miette!(
+                "Optimization was interrupted, rerun with --resume to continue from {}.",
+                checkpoint_path.display()
+            ),
+            None => miette!("Optimization was interrupted."),
+        });
+    }
+
+    if let Some(resumed) = resume_from {
+        return Err(miette!(
+            "The checkpoint is for problem {} (repetition {}), which was not optimized, \
+            resume with the same arguments as the interrupted command.",
+            resumed.context.function_index,
+            resumed.context.repetition
+        ));
+    }
+
+    // All problems have been optimized, so the checkpoint is no longer needed.
+    if let Some(checkpoint) = checkpoint.as_ref() {
+        checkpoint.remove()?;
+    }
 
     println!(
-        "  Minimum: {}",
-        optimization_results.minimum.value,
+        "\n-- Optimized {} problem(s) in {:.4} seconds. --",
+        bbob_functions.len(),
+        total_delta_time
     );
-    println!("  At: [{}]", formatted_parameters);
+
+    if repetition_args.repetitions.is_some() {
+        println!();
+        print_gap_summaries(&gap_summaries);
+    }
+
+    Ok(())
+}
+
+pub fn cmd_run_all_problems(args: CLIRunAllArgs) -> Result<()> {
+    let bbob_functions = args.run.suite.functions(None)?;
 
     println!(
-        "  Distance from global minimum: {:.5}",
-        optimization_results.minimum.value - bbob_function.global_minimum()
+        "-- Running firefly optimization on all {} problems. --",
+        bbob_functions.len()
     );
+    println!();
 
-    Ok(())
+    cmd_run_problems(&bbob_functions, &args.run)
+}
+
+pub fn cmd_run_specific_problem(args: CLIRunOneArgs) -> Result<()> {
+    let bbob_functions = args.run.suite.functions(Some(args.problem_number))?;
+
+    cmd_run_problems(&bbob_functions, &args.run)
 }
 
 pub fn cmd_run_firefly_optimization(
     args: CLIFireflyOptimizationArgs,
 ) -> Result<()> {
     match args.mode {
-        CLIFireflyOptimizationMode::AllProblems => cmd_run_all_problems(),
+        CLIFireflyOptimizationMode::AllProblems(all_args) => {
+            cmd_run_all_problems(all_args)
+        }
         CLIFireflyOptimizationMode::OneProblem(one_args) => {
             cmd_run_specific_problem(one_args)
         }
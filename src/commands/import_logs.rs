@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miette::{miette, Result};
+
+use crate::core::log_import::import_firefly_log;
+use crate::core::results::{write_result_records, ResultFormat};
+
+#[derive(Args)]
+pub struct CLIImportLogsArgs {
+    #[arg(
+        num_args = 1..,
+        required = true,
+        help = "Firefly optimization logs to import (e.g. results/*_firefly-attempt-*.log)."
+    )]
+    pub log_files: Vec<PathBuf>,
+
+    #[arg(
+        long = "output-directory",
+        default_value = "results/imported",
+        help = "Directory to write the imported results into (one file per log, named after the log)."
+    )]
+    pub output_directory: PathBuf,
+
+    #[arg(
+        long = "output-format",
+        value_enum,
+        default_value = "json",
+        help = "Format of the imported result files."
+    )]
+    pub output_format: ResultFormat,
+}
+
+pub fn cmd_import_logs(args: CLIImportLogsArgs) -> Result<()> {
+    let extension = match args.output_format {
+        ResultFormat::Json => "json",
+        ResultFormat::Csv => "csv",
+    };
+
+    for log_file in &args.log_files {
+        let records = import_firefly_log(log_file)?;
+
+        let file_stem = log_file.file_stem().ok_or_else(|| {
+            miette!("Invalid log file path {}.", log_file.display())
+        })?;
+        let output_path = args
+            .output_directory
+            .join(file_stem)
+            .with_extension(extension);
+
+        write_result_records(&output_path, args.output_format, &records)?;
+
+        println!(
+            "Imported {} problems from {} into {}.",
+            records.len(),
+            log_file.display(),
+            output_path.display()
+        );
+    }
+
+    Ok(())
+}
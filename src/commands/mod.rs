@@ -1,2 +1,8 @@
+pub mod analyze;
+pub mod arguments;
+pub mod compare;
 pub mod firefly_optimization;
+pub mod import_logs;
+pub mod nsga2;
+pub mod report;
 pub mod simulated_annealing;
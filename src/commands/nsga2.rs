@@ -0,0 +1,340 @@
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::Args;
+use miette::{miette, IntoDiagnostic, Result};
+
+use crate::algorithms::common::initialization::InitializationStrategy;
+use crate::algorithms::common::interrupt::install_interrupt_handler;
+use crate::algorithms::common::observer::SilentObserver;
+use crate::algorithms::common::stopping::StoppingCriterion;
+use crate::algorithms::nsga2::{
+    default_reference_point,
+    run_nsga2,
+    NSGA2Options,
+    NSGA2Result,
+};
+use crate::commands::arguments::{parse_seed, CLIProgressArgs};
+use crate::core::functions::{
+    BBOBBiobjFunctionType,
+    BBOBFunctionType,
+    BiObjectiveFunction,
+    BBOB_BIOBJ_FUNCTION_COUNT,
+};
+use crate::core::pareto::ObjectiveVector;
+use crate::core::results::{
+    seed_to_u128,
+    write_pareto_front_records,
+    ParetoFrontRecord,
+};
+use crate::core::suite::{BBOBBiobjSuite, BBOB_DIMENSIONS, BBOB_INSTANCE};
+
+/// Command-line overrides for `NSGA2Options`.
+/// Every option that is not specified keeps its default value.
+#[derive(Args)]
+pub struct CLINSGA2Args {
+    #[arg(
+        short = 'p',
+        long = "problem",
+        help = "Run only this bbob-biobj problem (1 to 55) instead of all of them."
+    )]
+    pub problem_number: Option<NonZeroUsize>,
+
+    #[arg(
+        long = "objectives",
+        value_delimiter = ',',
+        conflicts_with = "problem_number",
+        help = "Instead of the bbob-biobj suite, minimize this pair of BBOB functions \
+        (1 to 24) at the same time, e.g. \"1,15\"."
+    )]
+    pub objective_functions: Option<Vec<usize>>,
+
+    #[arg(
+        long = "population-size",
+        help = "Amount of individuals in the population."
+    )]
+    pub population_size: Option<usize>,
+
+    #[arg(long = "generations", help = "Amount of generations.")]
+    pub generations: Option<usize>,
+
+    #[arg(
+        long = "crossover-probability",
+        help = "Probability of recombining two parents with simulated binary crossover."
+    )]
+    pub crossover_probability: Option<f64>,
+
+    #[arg(
+        long = "mutation-probability",
+        help = "Probability of mutating each coordinate of an offspring."
+    )]
+    pub mutation_probability: Option<f64>,
+
+    #[arg(
+        long = "seed",
+        value_parser = parse_seed,
+        help = "Random generator seed (unsigned integer)."
+    )]
+    pub seed: Option<[u8; 16]>,
+
+    #[arg(
+        long = "initialization",
+        help = "How the initial population is chosen: uniform, latin-hypercube, halton, sobol, \
+        opposition or best-of-<K> (see README)."
+    )]
+    pub initialization: Option<InitializationStrategy>,
+
+    #[arg(
+        long = "stop-when",
+        help = "Stop each problem early once this criterion is met, e.g. \
+        \"evaluations=1e6 | stagnation=50000\" (stagnation of the hypervolume, see README)."
+    )]
+    pub stopping_criterion: Option<StoppingCriterion>,
+
+    #[arg(
+        long = "reference-point",
+        value_delimiter = ',',
+        help = "Reference point of the hypervolume, e.g. \"100,250\" (defaults to the nadir \
+        point of each Pareto front, moved away from the front by 10 % of its extent)."
+    )]
+    pub reference_point: Option<Vec<f64>>,
+
+    #[arg(
+        short = 'o',
+        long = "output",
+        help = "Write the Pareto fronts (and their hypervolumes) to this JSON file."
+    )]
+    pub output_path: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub progress: CLIProgressArgs,
+}
+
+impl CLINSGA2Args {
+    pub fn apply_to(&self, options: NSGA2Options) -> Result<NSGA2Options> {
+        if self.population_size.is_some_and(|size| size < 2) {
+            return Err(miette!(
+                "Population needs at least two individuals."
+            ));
+        }
+
+        for probability in
+            [self.crossover_probability, self.mutation_probability]
+                .into_iter()
+                .flatten()
+        {
+            if !(0f64..=1f64).contains(&probability) {
+                return Err(miette!("Invalid probability (not in 0-1 range)."));
+            }
+        }
+
+        if self
+            .stopping_criterion
+            .as_ref()
+            .is_some_and(StoppingCriterion::uses_target)
+        {
+            return Err(miette!(
+                "Stopping criterion target is not supported \
+                (bi-objective problems have no single global minimum)."
+            ));
+        }
+
+        Ok(NSGA2Options {
+            population_size: self
+                .population_size
+                .unwrap_or(options.population_size),
+            generations: self.generations.unwrap_or(options.generations),
+            crossover_probability: self
+                .crossover_probability
+                .unwrap_or(options.crossover_probability),
+            mutation_probability: self
+                .mutation_probability
+                .unwrap_or(options.mutation_probability),
+            initialization: self
+                .initialization
+                .unwrap_or(options.initialization),
+            seed: self.seed.unwrap_or(options.seed),
+            ..options
+        })
+    }
+
+    fn functions(&self) -> Result<Vec<BiObjectiveFunction>> {
+        if let Some(indices) = &self.objective_functions {
+            let functions: Vec<BBOBFunctionType> = indices
+                .iter()
+                .map(|index| {
+                    BBOBFunctionType::from_function_index(*index).ok_or_else(
+                        || miette!("Invalid BBOB function (not in 1-24 range)."),
+                    )
+                })
+                .collect::<Result<_>>()?;
+
+            let functions: [BBOBFunctionType; 2] =
+                functions.try_into().map_err(|_| {
+                    miette!("--objectives needs exactly two BBOB functions.")
+                })?;
+
+            return Ok(vec![BiObjectiveFunction::Pair(functions)]);
+        }
+
+        match self.problem_number {
+            Some(problem_number) => {
+                let function = BBOBBiobjFunctionType::from_function_index(
+                    problem_number.get(),
+                )
+                .ok_or_else(|| {
+                    miette!("Invalid problem number (not in 1-55 range).")
+                })?;
+
+                Ok(vec![BiObjectiveFunction::Biobj(function)])
+            }
+            None => Ok(BBOBBiobjFunctionType::all()
+                .into_iter()
+                .map(BiObjectiveFunction::Biobj)
+                .collect()),
+        }
+    }
+
+    fn reference_point(&self) -> Result<Option<ObjectiveVector>> {
+        self.reference_point
+            .as_ref()
+            .map(|values| {
+                values.as_slice().try_into().map_err(|_| {
+                    miette!("--reference-point needs exactly two values.")
+                })
+            })
+            .transpose()
+    }
+}
+
+fn build_pareto_front_record(
+    function: BiObjectiveFunction,
+    options: &NSGA2Options,
+    results: &NSGA2Result,
+    reference_point: ObjectiveVector,
+    wall_time_seconds: f64,
+) -> Result<ParetoFrontRecord> {
+    Ok(ParetoFrontRecord {
+        function_index: function.index(),
+        function_name: function.name(),
+        objective_function_indices: function
+            .objective_functions()
+            .map(|objective_function| objective_function.index()),
+        instance: BBOB_INSTANCE,
+        dimension: BBOB_DIMENSIONS,
+        algorithm: "nsga2".to_string(),
+        options: serde_json::to_value(options).into_diagnostic()?,
+        seed: seed_to_u128(options.seed),
+        evaluations: results.evaluations,
+        wall_time_seconds,
+        hypervolume: results.archive.hypervolume(reference_point),
+        reference_point,
+        pareto_front: results.archive.solutions().to_vec(),
+        interrupted: results.interrupted,
+    })
+}
+
+pub fn cmd_run_nsga2(args: CLINSGA2Args) -> Result<()> {
+    let options = args.apply_to(NSGA2Options::default())?;
+    let functions = args.functions()?;
+    let reference_point = args.reference_point()?;
+
+    install_interrupt_handler()?;
+
+    let total_start_time = Instant::now();
+    let mut records: Vec<ParetoFrontRecord> = Vec::new();
+    let mut suite = BBOBBiobjSuite::new()?;
+
+    for function in functions.iter().copied() {
+        let mut problem = suite.problem(function)?;
+
+        // NSGA-II has no progress bars.
+        let mut progress_observer = args.progress.create_observer(
+            function.suite_name(),
+            BBOB_DIMENSIONS,
+            || SilentObserver,
+        );
+
+        let problem_start_time = Instant::now();
+        let results = run_nsga2(
+            &mut problem,
+            options,
+            reference_point,
+            args.stopping_criterion.clone(),
+            progress_observer.as_mut(),
+        )?;
+        let problem_delta_time = problem_start_time.elapsed().as_secs_f64();
+
+        let problem_reference_point = reference_point
+            .unwrap_or_else(|| default_reference_point(&results.archive));
+
+        match function.index() {
+            Some(index) => println!(
+                "[Problem {:02}/{:02}: {}] - {:.4} seconds",
+                index,
+                BBOB_BIOBJ_FUNCTION_COUNT,
+                function.name(),
+                problem_delta_time
+            ),
+            None => println!(
+                "[Problem {}] - {:.4} seconds",
+                function.name(),
+                problem_delta_time
+            ),
+        }
+
+        println!(
+            "  Pareto front: {} solutions ({} evaluations, {} generations{})",
+            results.archive.len(),
+            results.evaluations,
+            results.generations_performed,
+            if results.interrupted {
+                ", interrupted"
+            } else if results.stopping_criterion_met {
+                ", stopping criterion met"
+            } else {
+                ""
+            }
+        );
+        if let (Some(ideal), Some(nadir)) = (
+            results.archive.ideal_point(),
+            results.archive.nadir_point(),
+        ) {
+            println!("  First objective: {} to {}", ideal[0], nadir[0]);
+            println!("  Second objective: {} to {}", ideal[1], nadir[1]);
+        }
+        println!(
+            "  Hypervolume: {} (reference point [{}, {}])",
+            results.archive.hypervolume(problem_reference_point),
+            problem_reference_point[0],
+            problem_reference_point[1]
+        );
+        println!();
+
+        // Rewrite the output file after each problem, so we don't lose the results on a crash.
+        records.push(build_pareto_front_record(
+            problem.name,
+            &options,
+            &results,
+            problem_reference_point,
+            problem_delta_time,
+        )?);
+        if let Some(output_path) = &args.output_path {
+            write_pareto_front_records(output_path, &records)?;
+        }
+
+        if results.interrupted {
+            println!("Interrupted, skipping the remaining problems.");
+            break;
+        }
+    }
+
+    println!(
+        "-- Finished {} problem(s) in {:.4} seconds --",
+        records.len(),
+        total_start_time.elapsed().as_secs_f64()
+    );
+
+    Ok(())
+}
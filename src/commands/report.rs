@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use miette::{miette, Context, IntoDiagnostic, Result};
+
+use crate::algorithms::common::trajectory::read_trajectory_entries;
+use crate::analysis::report::BenchmarkReport;
+use crate::analysis::summary::DEFAULT_PRECISION_TARGETS;
+use crate::core::results::read_result_records;
+
+/// Name of the plot directory (relative to the output directory) the Markdown report refers to.
+const PLOT_DIRECTORY_NAME: &str = "plots";
+
+#[derive(Args)]
+pub struct CLIReportArgs {
+    #[arg(
+        help = "Result file (.json or .csv, see --output) to build the report from."
+    )]
+    pub result_file: PathBuf,
+
+    #[arg(
+        long = "trajectory",
+        help = "Trajectory file (.csv or .jsonl, see --trajectory) for convergence and ECDF plots."
+    )]
+    pub trajectory_file: Option<PathBuf>,
+
+    #[arg(
+        long = "output-directory",
+        default_value = "report",
+        help = "Directory to write report.html, report.md and the plots into."
+    )]
+    pub output_directory: PathBuf,
+
+    #[arg(
+        long = "title",
+        help = "Title of the report (defaults to the result file name)."
+    )]
+    pub title: Option<String>,
+
+    #[arg(
+        long = "targets",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_PRECISION_TARGETS,
+        help = "Precision targets (distances from the global minimum) for the ECDF plots."
+    )]
+    pub precision_targets: Vec<f64>,
+}
+
+pub fn cmd_report(args: CLIReportArgs) -> Result<()> {
+    let records = read_result_records(&args.result_file)?;
+    if records.is_empty() {
+        return Err(miette!(
+            "Result file {} contains no results.",
+            args.result_file.display()
+        ));
+    }
+
+    let trajectory = match &args.trajectory_file {
+        Some(trajectory_file) => Some(read_trajectory_entries(trajectory_file)?),
+        None => None,
+    };
+
+    let title = args.title.unwrap_or_else(|| {
+        format!(
+            "Benchmark report: {}",
+            args.result_file
+                .file_name()
+                .unwrap_or(args.result_file.as_os_str())
+                .to_string_lossy()
+        )
+    });
+
+    let report = BenchmarkReport::new(
+        title,
+        &records,
+        trajectory.as_deref(),
+        &args.precision_targets,
+    );
+
+    let plot_directory = args.output_directory.join(PLOT_DIRECTORY_NAME);
+    fs::create_dir_all(&plot_directory)
+        .into_diagnostic()
+        .wrap_err_with(|| {
+            miette!(
+                "Could not create directory {}.",
+                plot_directory.display()
+            )
+        })?;
+
+    let html_path = args.output_directory.join("report.html");
+    fs::write(&html_path, report.to_html())
+        .into_diagnostic()
+        .wrap_err_with(|| miette!("Could not write {}.", html_path.display()))?;
+
+    let markdown_path = args.output_directory.join("report.md");
+    fs::write(
+        &markdown_path,
+        report.to_markdown(PLOT_DIRECTORY_NAME),
+    )
+    .into_diagnostic()
+    .wrap_err_with(|| miette!("Could not write {}.", markdown_path.display()))?;
+
+    for (file_name, svg) in report.plot_files() {
+        let plot_path = plot_directory.join(file_name);
+        fs::write(&plot_path, svg)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                miette!("Could not write {}.", plot_path.display())
+            })?;
+    }
+
+    println!(
+        "Report written to {} and {}.",
+        html_path.display(),
+        markdown_path.display()
+    );
+
+    Ok(())
+}
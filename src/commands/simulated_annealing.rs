@@ -1,41 +1,358 @@
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::num::NonZeroUsize;
 use std::time::Instant;
 
+use clap::Args;
 use itertools::Itertools;
-use miette::Result;
+use miette::{miette, IntoDiagnostic, Result};
 
+use crate::algorithms::common::boundary::BoundaryHandling;
+use crate::algorithms::common::constraints::ConstraintHandling;
+use crate::algorithms::common::initialization::InitializationStrategy;
+use crate::algorithms::common::observer::{MultiObserver, SilentObserver};
+use crate::algorithms::common::rng::derive_seeds;
+use crate::algorithms::common::stopping::StoppingCriterion;
+use crate::algorithms::common::structs::Minimum;
 use crate::algorithms::simulated_annealing::hyperparam_optimization::get_optimal_params;
 use crate::algorithms::simulated_annealing::options::SAOptions;
 use crate::algorithms::simulated_annealing::simulated_annealing::run_sa;
-use crate::core::functions::ALL_BBOB_FUNCTIONS;
-use crate::core::suite::BBOBSuite;
+use crate::analysis::summary::{print_gap_summaries, GapSummary};
+use crate::commands::arguments::{
+    parse_seed,
+    CLIEvaluationCacheArgs,
+    CLIOutputArgs,
+    CLIProgressArgs,
+    CLIRepetitionArgs,
+    CLISuiteArgs,
+    CLITrajectoryArgs,
+    CLIWarmStartArgs,
+};
+use crate::core::functions::SuiteFunction;
+use crate::core::results::{seed_to_u128, OptimizationResultRecord};
+use crate::core::suite::{SuiteType, BBOB_INSTANCE};
+
+/// Command-line overrides for `SAOptions`.
+/// Every option that is not specified keeps its default value.
+#[derive(Args)]
+pub struct CLISimulatedAnnealingArgs {
+    #[arg(
+        short = 'p',
+        long = "problem",
+        help = "Run only this problem (1 to 24, 101 to 130 except 116 to 118 with --suite bbob-noisy, or 1 to 54 with --suite bbob-constrained) instead of all of them."
+    )]
+    pub problem_number: Option<NonZeroUsize>,
+
+    #[arg(long = "initial-temperature", help = "Initial temperature.")]
+    pub initial_temperature: Option<u8>,
+
+    #[arg(
+        long = "annealing-schedule",
+        help = "Cooling rate (temperature is multiplied by this value on each iteration)."
+    )]
+    pub annealing_schedule: Option<f64>,
+
+    #[arg(
+        long = "max-iterations-sa",
+        help = "Maximum amount of simulated annealing iterations."
+    )]
+    pub max_iterations_sa: Option<u64>,
+
+    #[arg(
+        long = "max-iterations-ls",
+        help = "Maximum amount of local search iterations."
+    )]
+    pub max_iterations_ls: Option<u64>,
+
+    #[arg(
+        long = "initial-step-size-sa",
+        help = "Initial step size of the simulated annealing phase."
+    )]
+    pub initial_step_size_sa: Option<f64>,
+
+    #[arg(
+        long = "initial-step-size-ls",
+        help = "Initial step size of the local search phase."
+    )]
+    pub initial_step_size_ls: Option<f64>,
+
+    #[arg(long = "min-temperature", help = "Minimum temperature.")]
+    pub min_temp: Option<f64>,
+
+    #[arg(
+        long = "n-best-sa",
+        help = "Amount of best dimensions to generate neighbours for in simulated annealing \
+        (1 to the problem dimension)."
+    )]
+    pub n_best_sa: Option<usize>,
+
+    #[arg(
+        long = "n-best-ls",
+        help = "Amount of best dimensions to generate neighbours for in local search \
+        (1 to the problem dimension)."
+    )]
+    pub n_best_ls: Option<usize>,
+
+    #[arg(
+        long = "ls-step-decrease",
+        help = "How much the local search step decreases when stuck."
+    )]
+    pub ls_step_decrease: Option<f64>,
+
+    #[arg(
+        long = "seed",
+        value_parser = parse_seed,
+        help = "Random generator seed (unsigned integer)."
+    )]
+    pub seed: Option<[u8; 16]>,
+
+    #[arg(
+        long = "initialization",
+        help = "How the starting point is chosen: uniform, latin-hypercube, halton, sobol, \
+        opposition or best-of-<K> (see README)."
+    )]
+    pub initialization: Option<InitializationStrategy>,
+
+    #[arg(
+        long = "boundary-handling",
+        help = "How neighbours outside the bounds are handled: discard (default), clamp, reflect, \
+        wrap, random, midpoint or penalty[=<coefficient>] (see README)."
+    )]
+    pub boundary_handling: Option<BoundaryHandling>,
+
+    #[arg(
+        long = "constraint-handling",
+        help = "How neighbours violating the constraints are accepted (bbob-constrained only): \
+        deb (default), static-penalty[=<coefficient>], adaptive-penalty[=<coefficient>] \
+        or epsilon=<level>[,<decay>] (see README)."
+    )]
+    pub constraint_handling: Option<ConstraintHandling>,
+
+    #[arg(
+        long = "stop-when",
+        help = "Stop each run early once this criterion is met, \
+        e.g. \"target=1e-8 | evaluations=1e6\" (see README)."
+    )]
+    pub stopping_criterion: Option<StoppingCriterion>,
+
+    #[command(flatten)]
+    pub output: CLIOutputArgs,
+
+    #[command(flatten)]
+    pub trajectory: CLITrajectoryArgs,
+
+    #[command(flatten)]
+    pub repetitions: CLIRepetitionArgs,
+
+    #[command(flatten)]
+    pub warm_start: CLIWarmStartArgs,
+
+    #[command(flatten)]
+    pub progress: CLIProgressArgs,
+
+    #[command(flatten)]
+    pub evaluation_cache: CLIEvaluationCacheArgs,
+
+    #[command(flatten)]
+    pub suite: CLISuiteArgs,
+}
+
+impl CLISimulatedAnnealingArgs {
+    pub fn apply_to(&self, options: SAOptions) -> Result<SAOptions> {
+        for n_best in [self.n_best_sa, self.n_best_ls].into_iter().flatten() {
+            if !(1..=self.suite.dimensions).contains(&n_best) {
+                return Err(miette!(
+                    "Invalid amount of best dimensions (not in 1-{} range).",
+                    self.suite.dimensions
+                ));
+            }
+        }
+
+        Ok(SAOptions {
+            initial_temperature: self
+                .initial_temperature
+                .unwrap_or(options.initial_temperature),
+            annealing_schedule: self
+                .annealing_schedule
+                .unwrap_or(options.annealing_schedule),
+            max_iterations_sa: self
+                .max_iterations_sa
+                .unwrap_or(options.max_iterations_sa),
+            max_iterations_ls: self
+                .max_iterations_ls
+                .unwrap_or(options.max_iterations_ls),
+            initial_step_size_sa: self
+                .initial_step_size_sa
+                .unwrap_or(options.initial_step_size_sa),
+            initial_step_size_ls: self
+                .initial_step_size_ls
+                .unwrap_or(options.initial_step_size_ls),
+            min_temp: self.min_temp.unwrap_or(options.min_temp),
+            n_best_sa: self.n_best_sa.unwrap_or(options.n_best_sa),
+            n_best_ls: self.n_best_ls.unwrap_or(options.n_best_ls),
+            ls_step_decrease: self
+                .ls_step_decrease
+                .unwrap_or(options.ls_step_decrease),
+            seed: self.seed.unwrap_or(options.seed),
+            initialization: self
+                .initialization
+                .unwrap_or(options.initialization),
+            boundary_handling: self
+                .boundary_handling
+                .unwrap_or(options.boundary_handling),
+            constraint_handling: self
+                .constraint_handling
+                .unwrap_or(options.constraint_handling),
+            ..options
+        })
+    }
+
+    fn bbob_functions(&self) -> Result<Vec<SuiteFunction>> {
+        self.suite.functions(self.problem_number)
+    }
+}
+
+/// Amount of runs per problem when `--repetitions` is not specified.
+/// These runs are not independent: options are tuned with `get_optimal_params` after each one.
+const TUNED_RUNS_PER_PROBLEM: usize = 20;
+
+fn build_result_record(
+    suite: SuiteType,
+    options: &SAOptions,
+    dimensions: usize,
+    repetition: usize,
+    results: &Minimum,
+    evaluations: u64,
+    wall_time_seconds: f64,
+) -> Result<OptimizationResultRecord> {
+    Ok(OptimizationResultRecord {
+        suite: Some(suite),
+        function_index: options.function.index(),
+        function_name: options.function.name(),
+        instance: BBOB_INSTANCE,
+        dimension: dimensions,
+        algorithm: "simulated-annealing".to_string(),
+        repetition,
+        options: serde_json::to_value(options).into_diagnostic()?,
+        seed: seed_to_u128(options.seed),
+        best_value: results.value,
+        best_vector: results.vector.clone(),
+        evaluations,
+        wall_time_seconds,
+        gap_to_optimum: results.value - options.function.global_minimum(),
+        interrupted: false,
+        constraint_violation: options
+            .function
+            .is_constrained()
+            .then_some(results.constraint_violation),
+        feasible: options
+            .function
+            .is_constrained()
+            .then(|| results.is_feasible()),
+    })
+}
+
+pub fn run_cmd_simulated_annealing(
+    args: CLISimulatedAnnealingArgs,
+) -> Result<()> {
+    // Fail early if the output file format can't be determined.
+    args.output.output_path_and_format()?;
 
-pub fn run_cmd_simulated_annealing() -> Result<()> {
     let total_start_time = Instant::now();
 
+    let mut result_records: Vec<OptimizationResultRecord> = Vec::new();
+    let mut trajectory = args.trajectory.create_recorder()?;
+    let warm_start = args
+        .warm_start
+        .load(args.suite.suite, args.suite.dimensions)?;
+
+    let bbob_functions = args.bbob_functions()?;
+
+    // With `--repetitions`, we perform independent runs with derived seeds and fixed options.
+    // Otherwise, we tune the options after each run.
+    let independent_repetitions = args.repetitions.repetitions.is_some();
+    let runs_per_problem = if independent_repetitions {
+        args.repetitions.repetitions()
+    } else {
+        TUNED_RUNS_PER_PROBLEM
+    };
+
     let mut minimums = Vec::new();
     let mut vectors = Vec::new();
+    let mut gap_summaries: Vec<GapSummary> = Vec::new();
 
-    // Run all 24 BBOB problems.
-    for bbob_function in ALL_BBOB_FUNCTIONS {
-        let mut options = SAOptions::default();
+    // Run all selected BBOB problems.
+    for bbob_function in bbob_functions.iter().copied() {
+        let mut options = args.apply_to(SAOptions {
+            function: bbob_function,
+            ..SAOptions::default()
+        })?;
+
+        let repetition_seeds = derive_seeds(options.seed, runs_per_problem);
 
         let mut fun_values = Vec::new();
         let mut min_vectors = Vec::new();
 
-        for _ in 0..20 {
-            let mut suite = BBOBSuite::new()?;
+        for (run_index, repetition_seed) in
+            repetition_seeds.into_iter().enumerate()
+        {
+            if independent_repetitions {
+                options = SAOptions {
+                    seed: repetition_seed,
+                    ..options
+                };
+            }
+
+            if let Some(trajectory) = trajectory.as_mut() {
+                trajectory.start_problem(
+                    args.suite.suite,
+                    bbob_function.index(),
+                    run_index + 1,
+                );
+            }
+
+            let mut suite = args.suite.create_suite()?;
             let mut problem = suite.problem(bbob_function)?;
+            args.evaluation_cache.apply_to(&mut problem);
+            args.suite.apply_to(&mut problem);
 
             let problem_start_time = Instant::now();
 
-            let results = run_sa(&mut problem, options)?;
+            // Simulated annealing has no progress bars.
+            let mut progress_observer = args.progress.create_observer(
+                args.suite.suite.name(),
+                problem.input_dimensions,
+                || SilentObserver,
+            );
+
+            let results = run_sa(
+                &mut problem,
+                options,
+                warm_start.as_ref().and_then(|solutions| {
+                    solutions.vector_for(args.suite.suite, bbob_function)
+                }),
+                args.stopping_criterion.clone(),
+                &mut MultiObserver::new()
+                    .with_observer(progress_observer.as_mut())
+                    .with_optional_observer(trajectory.as_mut()),
+            )?;
             fun_values.push(results.value);
             min_vectors.push(results.vector.clone());
 
             let problem_delta_time = problem_start_time.elapsed().as_secs_f64();
 
+            // Rewrite the output file after each run, so we don't lose the results on a crash.
+            result_records.push(build_result_record(
+                args.suite.suite,
+                &options,
+                problem.input_dimensions,
+                run_index + 1,
+                &results,
+                problem.evaluations(),
+                problem_delta_time,
+            )?);
+            args.output.write_if_requested(&result_records)?;
+
             let formatted_parameters = results
                 .vector
                 .iter()
@@ -45,23 +362,52 @@ pub fn run_cmd_simulated_annealing() -> Result<()> {
             println!(
                 "[Problem {:02}/{:02}: {}] - {:.4} seconds",
                 bbob_function.index(),
-                ALL_BBOB_FUNCTIONS.len(),
+                bbob_function.suite().last_function_index(),
                 bbob_function.name(),
                 problem_delta_time
             );
 
             println!("  Minimum: {}", results.value,);
             println!("  At: [{}]", formatted_parameters);
+            if bbob_function.is_constrained() {
+                println!("  {}", results.describe_feasibility());
+            }
 
             println!(
                 "  Distance from global minimum: {:.5}",
                 results.value - bbob_function.global_minimum()
             );
+            if let Some(cache_statistics) = problem.evaluation_cache_statistics()
+            {
+                println!(
+                    "  Evaluation cache: {}",
+                    cache_statistics.describe()
+                );
+            }
             println!();
-            println!("Finding optimal params:");
-            options = get_optimal_params(&mut problem, options);
+
+            if !independent_repetitions {
+                println!("Finding optimal params:");
+                let tuned_options = get_optimal_params(&mut problem, options);
+                for change in tuned_options.evaluated_changes.iter() {
+                    println!("{}", change.describe());
+                }
+
+                options = tuned_options.options;
+            }
         }
 
+        let final_gaps: Vec<f64> = fun_values
+            .iter()
+            .map(|value| value - bbob_function.global_minimum())
+            .collect();
+        gap_summaries.push(GapSummary::from_gaps(
+            bbob_function.index(),
+            bbob_function.name(),
+            &final_gaps,
+            &args.repetitions.precision_targets,
+        ));
+
         let mut min = f64::MAX;
         let mut min_index = 0;
         for (i, el) in fun_values.iter().enumerate() {
@@ -78,14 +424,20 @@ pub fn run_cmd_simulated_annealing() -> Result<()> {
     let total_delta_time = total_start_time.elapsed().as_secs_f64();
 
     println!(
-        "\n-- Finished all 24 problems in {:.4} seconds --",
+        "\n-- Finished {} problem(s) in {:.4} seconds --",
+        bbob_functions.len(),
         total_delta_time
     );
 
     println!("Minimum values:");
 
     for (i, el) in minimums.iter().enumerate() {
-        println!("{}: {}", ALL_BBOB_FUNCTIONS[i].name(), el);
+        println!("{}: {}", bbob_functions[i].name(), el);
+    }
+
+    if independent_repetitions {
+        println!();
+        print_gap_summaries(&gap_summaries);
     }
 
     let mut file = OpenOptions::new()
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+
+/// Hit and miss counts of an `EvaluationCache`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationCacheStatistics {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+
+    /// Lookups that had to evaluate the objective function.
+    pub misses: u64,
+
+    /// Entries removed to make room for newer ones.
+    pub evictions: u64,
+}
+
+impl EvaluationCacheStatistics {
+    /// Share of lookups answered from the cache (0 if there were no lookups).
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0f64;
+        }
+
+        self.hits as f64 / lookups as f64
+    }
+
+    /// Short human-readable summary, e.g. `120 hits, 880 misses (12.0 % hit rate), 0 evictions`.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} hits, {} misses ({:.1} % hit rate), {} evictions",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100f64,
+            self.evictions
+        )
+    }
+}
+
+struct CachedValue {
+    objective_value: f64,
+
+    /// Time of the last lookup or insertion (see `EvaluationCache::clock`).
+    last_used: u64,
+}
+
+/// Least-recently-used cache of objective function values.
+///
+/// Points are matched exactly, by the bit patterns of their coordinates
+/// (so e.g. `0.0` and `-0.0` are different points).
+pub struct EvaluationCache {
+    capacity: NonZeroUsize,
+
+    /// Whether cache hits still count as objective function evaluations
+    /// (and with that against the evaluation budget).
+    count_hits_as_evaluations: bool,
+
+    values: HashMap<Vec<u64>, CachedValue>,
+
+    /// Keys of `values` ordered by their `last_used` time, the least recently used one first.
+    usage_order: BTreeMap<u64, Vec<u64>>,
+
+    /// Incremented on every lookup and insertion.
+    clock: u64,
+
+    statistics: EvaluationCacheStatistics,
+}
+
+impl EvaluationCache {
+    pub fn new(capacity: NonZeroUsize, count_hits_as_evaluations: bool) -> Self {
+        Self {
+            capacity,
+            count_hits_as_evaluations,
+            values: HashMap::with_capacity(capacity.get()),
+            usage_order: BTreeMap::new(),
+            clock: 0,
+            statistics: EvaluationCacheStatistics::default(),
+        }
+    }
+
+    #[inline]
+    fn key(point: &[f64]) -> Vec<u64> {
+        point.iter().map(|value| value.to_bits()).collect()
+    }
+
+    /// Looks up the objective value of `point`, counting the lookup as a hit or a miss.
+    pub fn get(&mut self, point: &[f64]) -> Option<f64> {
+        let key = Self::key(point);
+
+        let Some(cached_value) = self.values.get_mut(&key) else {
+            self.statistics.misses += 1;
+            return None;
+        };
+
+        self.clock += 1;
+        let key = self
+            .usage_order
+            .remove(&cached_value.last_used)
+            .expect("BUG: Cached value is missing from the usage order.");
+        cached_value.last_used = self.clock;
+        self.usage_order.insert(self.clock, key);
+
+        self.statistics.hits += 1;
+        Some(cached_value.objective_value)
+    }
+
+    /// Stores the objective value of `point`, evicting the least recently used value if full.
+    pub fn insert(&mut self, point: &[f64], objective_value: f64) {
+        let key = Self::key(point);
+        self.clock += 1;
+
+        if let Some(cached_value) = self.values.get_mut(&key) {
+            self.usage_order.remove(&cached_value.last_used);
+            cached_value.objective_value = objective_value;
+            cached_value.last_used = self.clock;
+            self.usage_order.insert(self.clock, key);
+            return;
+        }
+
+        if self.values.len() >= self.capacity.get() {
+            if let Some((_, evicted_key)) = self.usage_order.pop_first() {
+                self.values.remove(&evicted_key);
+                self.statistics.evictions += 1;
+            }
+        }
+
+        self.usage_order.insert(self.clock, key.clone());
+        self.values.insert(
+            key,
+            CachedValue {
+                objective_value,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    pub fn counts_hits_as_evaluations(&self) -> bool {
+        self.count_hits_as_evaluations
+    }
+
+    pub fn statistics(&self) -> EvaluationCacheStatistics {
+        self.statistics
+    }
+}
@@ -1,5 +1,10 @@
+use serde::Serialize;
+
+use crate::core::noise::NoiseModel;
+use crate::core::suite::{SuiteType, BBOB_DIMENSIONS};
+
 /// See http://numbbo.github.io/coco/testsuites/bbob for more info.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize)]
 pub enum BBOBFunctionType {
     /// Index: 1.
     Sphere,
@@ -236,3 +241,642 @@ impl BBOBFunctionType {
         }
     }
 }
+
+/// Noisy BBOB functions (noiseless landscapes with one of three noise models).
+/// See http://numbbo.github.io/coco/testsuites/bbob-noisy for more info.
+///
+/// The ellipsoid (f116 to f118) is not supported: its condition number of 1e4 differs from the
+/// noiseless ellipsoid we apply the noise to (see `base_function`).
+#[derive(Copy, Clone, Serialize)]
+pub enum BBOBNoisyFunctionType {
+    /// Index: 101.
+    SphereModerateGaussian,
+
+    /// Index: 102.
+    SphereModerateUniform,
+
+    /// Index: 103.
+    SphereModerateCauchy,
+
+    /// Index: 104.
+    RosenbrockModerateGaussian,
+
+    /// Index: 105.
+    RosenbrockModerateUniform,
+
+    /// Index: 106.
+    RosenbrockModerateCauchy,
+
+    /// Index: 107.
+    SphereGaussian,
+
+    /// Index: 108.
+    SphereUniform,
+
+    /// Index: 109.
+    SphereCauchy,
+
+    /// Index: 110.
+    RosenbrockGaussian,
+
+    /// Index: 111.
+    RosenbrockUniform,
+
+    /// Index: 112.
+    RosenbrockCauchy,
+
+    /// Index: 113.
+    StepEllipsoidalGaussian,
+
+    /// Index: 114.
+    StepEllipsoidalUniform,
+
+    /// Index: 115.
+    StepEllipsoidalCauchy,
+
+    /// Index: 119.
+    DifferentPowersGaussian,
+
+    /// Index: 120.
+    DifferentPowersUniform,
+
+    /// Index: 121.
+    DifferentPowersCauchy,
+
+    /// Index: 122.
+    SchafferF7Gaussian,
+
+    /// Index: 123.
+    SchafferF7Uniform,
+
+    /// Index: 124.
+    SchafferF7Cauchy,
+
+    /// Index: 125.
+    CompositeGriewankRosenbrockGaussian,
+
+    /// Index: 126.
+    CompositeGriewankRosenbrockUniform,
+
+    /// Index: 127.
+    CompositeGriewankRosenbrockCauchy,
+
+    /// Index: 128.
+    Gallagher101PeaksGaussian,
+
+    /// Index: 129.
+    Gallagher101PeaksUniform,
+
+    /// Index: 130.
+    Gallagher101PeaksCauchy,
+}
+
+pub const ALL_BBOB_NOISY_FUNCTIONS: [BBOBNoisyFunctionType; 27] = [
+    BBOBNoisyFunctionType::SphereModerateGaussian,
+    BBOBNoisyFunctionType::SphereModerateUniform,
+    BBOBNoisyFunctionType::SphereModerateCauchy,
+    BBOBNoisyFunctionType::RosenbrockModerateGaussian,
+    BBOBNoisyFunctionType::RosenbrockModerateUniform,
+    BBOBNoisyFunctionType::RosenbrockModerateCauchy,
+    BBOBNoisyFunctionType::SphereGaussian,
+    BBOBNoisyFunctionType::SphereUniform,
+    BBOBNoisyFunctionType::SphereCauchy,
+    BBOBNoisyFunctionType::RosenbrockGaussian,
+    BBOBNoisyFunctionType::RosenbrockUniform,
+    BBOBNoisyFunctionType::RosenbrockCauchy,
+    BBOBNoisyFunctionType::StepEllipsoidalGaussian,
+    BBOBNoisyFunctionType::StepEllipsoidalUniform,
+    BBOBNoisyFunctionType::StepEllipsoidalCauchy,
+    BBOBNoisyFunctionType::DifferentPowersGaussian,
+    BBOBNoisyFunctionType::DifferentPowersUniform,
+    BBOBNoisyFunctionType::DifferentPowersCauchy,
+    BBOBNoisyFunctionType::SchafferF7Gaussian,
+    BBOBNoisyFunctionType::SchafferF7Uniform,
+    BBOBNoisyFunctionType::SchafferF7Cauchy,
+    BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian,
+    BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform,
+    BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy,
+    BBOBNoisyFunctionType::Gallagher101PeaksGaussian,
+    BBOBNoisyFunctionType::Gallagher101PeaksUniform,
+    BBOBNoisyFunctionType::Gallagher101PeaksCauchy,
+];
+
+impl BBOBNoisyFunctionType {
+    pub fn from_function_index(index: usize) -> Option<Self> {
+        match index {
+            101 => Some(BBOBNoisyFunctionType::SphereModerateGaussian),
+            102 => Some(BBOBNoisyFunctionType::SphereModerateUniform),
+            103 => Some(BBOBNoisyFunctionType::SphereModerateCauchy),
+            104 => Some(BBOBNoisyFunctionType::RosenbrockModerateGaussian),
+            105 => Some(BBOBNoisyFunctionType::RosenbrockModerateUniform),
+            106 => Some(BBOBNoisyFunctionType::RosenbrockModerateCauchy),
+            107 => Some(BBOBNoisyFunctionType::SphereGaussian),
+            108 => Some(BBOBNoisyFunctionType::SphereUniform),
+            109 => Some(BBOBNoisyFunctionType::SphereCauchy),
+            110 => Some(BBOBNoisyFunctionType::RosenbrockGaussian),
+            111 => Some(BBOBNoisyFunctionType::RosenbrockUniform),
+            112 => Some(BBOBNoisyFunctionType::RosenbrockCauchy),
+            113 => Some(BBOBNoisyFunctionType::StepEllipsoidalGaussian),
+            114 => Some(BBOBNoisyFunctionType::StepEllipsoidalUniform),
+            115 => Some(BBOBNoisyFunctionType::StepEllipsoidalCauchy),
+            119 => Some(BBOBNoisyFunctionType::DifferentPowersGaussian),
+            120 => Some(BBOBNoisyFunctionType::DifferentPowersUniform),
+            121 => Some(BBOBNoisyFunctionType::DifferentPowersCauchy),
+            122 => Some(BBOBNoisyFunctionType::SchafferF7Gaussian),
+            123 => Some(BBOBNoisyFunctionType::SchafferF7Uniform),
+            124 => Some(BBOBNoisyFunctionType::SchafferF7Cauchy),
+            125 => {
+                Some(BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian)
+            }
+            126 => {
+                Some(BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform)
+            }
+            127 => {
+                Some(BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy)
+            }
+            128 => Some(BBOBNoisyFunctionType::Gallagher101PeaksGaussian),
+            129 => Some(BBOBNoisyFunctionType::Gallagher101PeaksUniform),
+            130 => Some(BBOBNoisyFunctionType::Gallagher101PeaksCauchy),
+            _ => None,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            BBOBNoisyFunctionType::SphereModerateGaussian => 101,
+            BBOBNoisyFunctionType::SphereModerateUniform => 102,
+            BBOBNoisyFunctionType::SphereModerateCauchy => 103,
+            BBOBNoisyFunctionType::RosenbrockModerateGaussian => 104,
+            BBOBNoisyFunctionType::RosenbrockModerateUniform => 105,
+            BBOBNoisyFunctionType::RosenbrockModerateCauchy => 106,
+            BBOBNoisyFunctionType::SphereGaussian => 107,
+            BBOBNoisyFunctionType::SphereUniform => 108,
+            BBOBNoisyFunctionType::SphereCauchy => 109,
+            BBOBNoisyFunctionType::RosenbrockGaussian => 110,
+            BBOBNoisyFunctionType::RosenbrockUniform => 111,
+            BBOBNoisyFunctionType::RosenbrockCauchy => 112,
+            BBOBNoisyFunctionType::StepEllipsoidalGaussian => 113,
+            BBOBNoisyFunctionType::StepEllipsoidalUniform => 114,
+            BBOBNoisyFunctionType::StepEllipsoidalCauchy => 115,
+            BBOBNoisyFunctionType::DifferentPowersGaussian => 119,
+            BBOBNoisyFunctionType::DifferentPowersUniform => 120,
+            BBOBNoisyFunctionType::DifferentPowersCauchy => 121,
+            BBOBNoisyFunctionType::SchafferF7Gaussian => 122,
+            BBOBNoisyFunctionType::SchafferF7Uniform => 123,
+            BBOBNoisyFunctionType::SchafferF7Cauchy => 124,
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian => 125,
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform => 126,
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy => 127,
+            BBOBNoisyFunctionType::Gallagher101PeaksGaussian => 128,
+            BBOBNoisyFunctionType::Gallagher101PeaksUniform => 129,
+            BBOBNoisyFunctionType::Gallagher101PeaksCauchy => 130,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            BBOBNoisyFunctionType::SphereModerateGaussian => {
+                "SphereModerateGaussian"
+            }
+            BBOBNoisyFunctionType::SphereModerateUniform => {
+                "SphereModerateUniform"
+            }
+            BBOBNoisyFunctionType::SphereModerateCauchy => {
+                "SphereModerateCauchy"
+            }
+            BBOBNoisyFunctionType::RosenbrockModerateGaussian => {
+                "RosenbrockModerateGaussian"
+            }
+            BBOBNoisyFunctionType::RosenbrockModerateUniform => {
+                "RosenbrockModerateUniform"
+            }
+            BBOBNoisyFunctionType::RosenbrockModerateCauchy => {
+                "RosenbrockModerateCauchy"
+            }
+            BBOBNoisyFunctionType::SphereGaussian => "SphereGaussian",
+            BBOBNoisyFunctionType::SphereUniform => "SphereUniform",
+            BBOBNoisyFunctionType::SphereCauchy => "SphereCauchy",
+            BBOBNoisyFunctionType::RosenbrockGaussian => "RosenbrockGaussian",
+            BBOBNoisyFunctionType::RosenbrockUniform => "RosenbrockUniform",
+            BBOBNoisyFunctionType::RosenbrockCauchy => "RosenbrockCauchy",
+            BBOBNoisyFunctionType::StepEllipsoidalGaussian => {
+                "StepEllipsoidalGaussian"
+            }
+            BBOBNoisyFunctionType::StepEllipsoidalUniform => {
+                "StepEllipsoidalUniform"
+            }
+            BBOBNoisyFunctionType::StepEllipsoidalCauchy => {
+                "StepEllipsoidalCauchy"
+            }
+            BBOBNoisyFunctionType::DifferentPowersGaussian => {
+                "DifferentPowersGaussian"
+            }
+            BBOBNoisyFunctionType::DifferentPowersUniform => {
+                "DifferentPowersUniform"
+            }
+            BBOBNoisyFunctionType::DifferentPowersCauchy => {
+                "DifferentPowersCauchy"
+            }
+            BBOBNoisyFunctionType::SchafferF7Gaussian => "SchafferF7Gaussian",
+            BBOBNoisyFunctionType::SchafferF7Uniform => "SchafferF7Uniform",
+            BBOBNoisyFunctionType::SchafferF7Cauchy => "SchafferF7Cauchy",
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian => {
+                "CompositeGriewankRosenbrockGaussian"
+            }
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform => {
+                "CompositeGriewankRosenbrockUniform"
+            }
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy => {
+                "CompositeGriewankRosenbrockCauchy"
+            }
+            BBOBNoisyFunctionType::Gallagher101PeaksGaussian => {
+                "Gallagher101PeaksGaussian"
+            }
+            BBOBNoisyFunctionType::Gallagher101PeaksUniform => {
+                "Gallagher101PeaksUniform"
+            }
+            BBOBNoisyFunctionType::Gallagher101PeaksCauchy => {
+                "Gallagher101PeaksCauchy"
+            }
+        }
+        .to_string()
+    }
+
+    /// Noiseless function the noise is applied to.
+    ///
+    /// The noiseless problem only approximates the bbob-noisy one: its instance transformations
+    /// (optimum, rotations and `fopt`) are the ones of the bbob problem, not of f101 to f130,
+    /// and the boundary penalty `f_pen` of bbob-noisy (zero inside `[-5, 5]`) is not added.
+    pub fn base_function(&self) -> BBOBFunctionType {
+        match self {
+            BBOBNoisyFunctionType::SphereModerateGaussian
+            | BBOBNoisyFunctionType::SphereModerateUniform
+            | BBOBNoisyFunctionType::SphereModerateCauchy
+            | BBOBNoisyFunctionType::SphereGaussian
+            | BBOBNoisyFunctionType::SphereUniform
+            | BBOBNoisyFunctionType::SphereCauchy => BBOBFunctionType::Sphere,
+            BBOBNoisyFunctionType::RosenbrockModerateGaussian
+            | BBOBNoisyFunctionType::RosenbrockModerateUniform
+            | BBOBNoisyFunctionType::RosenbrockModerateCauchy
+            | BBOBNoisyFunctionType::RosenbrockGaussian
+            | BBOBNoisyFunctionType::RosenbrockUniform
+            | BBOBNoisyFunctionType::RosenbrockCauchy => {
+                BBOBFunctionType::RosenbrockFunction
+            }
+            BBOBNoisyFunctionType::StepEllipsoidalGaussian
+            | BBOBNoisyFunctionType::StepEllipsoidalUniform
+            | BBOBNoisyFunctionType::StepEllipsoidalCauchy => {
+                BBOBFunctionType::StepEllipsoidal
+            }
+            BBOBNoisyFunctionType::DifferentPowersGaussian
+            | BBOBNoisyFunctionType::DifferentPowersUniform
+            | BBOBNoisyFunctionType::DifferentPowersCauchy => {
+                BBOBFunctionType::DifferentPowers
+            }
+            BBOBNoisyFunctionType::SchafferF7Gaussian
+            | BBOBNoisyFunctionType::SchafferF7Uniform
+            | BBOBNoisyFunctionType::SchafferF7Cauchy => {
+                BBOBFunctionType::SchafferF7
+            }
+            BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian
+            | BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform
+            | BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy => {
+                BBOBFunctionType::CompositeGriewankRosenbrockF8F2
+            }
+            BBOBNoisyFunctionType::Gallagher101PeaksGaussian
+            | BBOBNoisyFunctionType::Gallagher101PeaksUniform
+            | BBOBNoisyFunctionType::Gallagher101PeaksCauchy => {
+                BBOBFunctionType::GallagherGaussian101MePeaks
+            }
+        }
+    }
+
+    /// Noise model for the given amount of `dimensions`.
+    pub fn noise_model(&self, dimensions: usize) -> NoiseModel {
+        match self {
+            BBOBNoisyFunctionType::SphereModerateGaussian
+            | BBOBNoisyFunctionType::RosenbrockModerateGaussian => {
+                NoiseModel::Gaussian { beta: 0.01 }
+            }
+            BBOBNoisyFunctionType::SphereModerateUniform
+            | BBOBNoisyFunctionType::RosenbrockModerateUniform => {
+                NoiseModel::Uniform {
+                    alpha: 0.01 * (0.49 + 1.0 / dimensions as f64),
+                    beta: 0.01,
+                }
+            }
+            BBOBNoisyFunctionType::SphereModerateCauchy
+            | BBOBNoisyFunctionType::RosenbrockModerateCauchy => {
+                NoiseModel::Cauchy {
+                    alpha: 0.01,
+                    outlier_probability: 0.05,
+                }
+            }
+            BBOBNoisyFunctionType::SphereGaussian
+            | BBOBNoisyFunctionType::RosenbrockGaussian
+            | BBOBNoisyFunctionType::StepEllipsoidalGaussian
+            | BBOBNoisyFunctionType::DifferentPowersGaussian
+            | BBOBNoisyFunctionType::SchafferF7Gaussian
+            | BBOBNoisyFunctionType::CompositeGriewankRosenbrockGaussian
+            | BBOBNoisyFunctionType::Gallagher101PeaksGaussian => {
+                NoiseModel::Gaussian { beta: 1.0 }
+            }
+            BBOBNoisyFunctionType::SphereUniform
+            | BBOBNoisyFunctionType::RosenbrockUniform
+            | BBOBNoisyFunctionType::StepEllipsoidalUniform
+            | BBOBNoisyFunctionType::DifferentPowersUniform
+            | BBOBNoisyFunctionType::SchafferF7Uniform
+            | BBOBNoisyFunctionType::CompositeGriewankRosenbrockUniform
+            | BBOBNoisyFunctionType::Gallagher101PeaksUniform => {
+                NoiseModel::Uniform {
+                    alpha: 0.49 + 1.0 / dimensions as f64,
+                    beta: 1.0,
+                }
+            }
+            BBOBNoisyFunctionType::SphereCauchy
+            | BBOBNoisyFunctionType::RosenbrockCauchy
+            | BBOBNoisyFunctionType::StepEllipsoidalCauchy
+            | BBOBNoisyFunctionType::DifferentPowersCauchy
+            | BBOBNoisyFunctionType::SchafferF7Cauchy
+            | BBOBNoisyFunctionType::CompositeGriewankRosenbrockCauchy
+            | BBOBNoisyFunctionType::Gallagher101PeaksCauchy => {
+                NoiseModel::Cauchy {
+                    alpha: 1.0,
+                    outlier_probability: 0.2,
+                }
+            }
+        }
+    }
+
+    /// Global minimum of the noiseless function (the noise vanishes at the optimum).
+    pub fn global_minimum(&self) -> f64 {
+        self.base_function().global_minimum()
+    }
+}
+
+/// The 9 BBOB functions the constrained functions of the bbob-constrained suite are based on
+/// (in the order of the suite).
+pub const BBOB_CONSTRAINED_BASE_FUNCTIONS: [BBOBFunctionType; 9] = [
+    BBOBFunctionType::Sphere,
+    BBOBFunctionType::SeparableEllipsoidal,
+    BBOBFunctionType::LinearSlope,
+    BBOBFunctionType::Ellipsoidal,
+    BBOBFunctionType::Discus,
+    BBOBFunctionType::BentCigar,
+    BBOBFunctionType::DifferentPowers,
+    BBOBFunctionType::Rastrigin,
+    BBOBFunctionType::RastriginMultiModal,
+];
+
+pub const BBOB_CONSTRAINED_FUNCTION_COUNT: usize = 54;
+
+/// Constrained function of the bbob-constrained suite (indices 1-54): one of the
+/// `BBOB_CONSTRAINED_BASE_FUNCTIONS` with 1, 2, 6, 6+n/2, 6+n or 6+3n linear inequality
+/// constraints (n being the dimension). f1 to f6 are the sphere with each amount of constraints,
+/// f7 to f12 the separable ellipsoid and so on.
+/// See http://numbbo.github.io/coco/testsuites/bbob-constrained for more info.
+#[derive(Copy, Clone, Serialize)]
+pub struct BBOBConstrainedFunctionType {
+    index: usize,
+}
+
+impl BBOBConstrainedFunctionType {
+    pub fn from_function_index(index: usize) -> Option<Self> {
+        if (1..=BBOB_CONSTRAINED_FUNCTION_COUNT).contains(&index) {
+            Some(Self { index })
+        } else {
+            None
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        (1..=BBOB_CONSTRAINED_FUNCTION_COUNT)
+            .map(|index| Self { index })
+            .collect()
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// BBOB function whose landscape is constrained.
+    pub fn base_function(&self) -> BBOBFunctionType {
+        BBOB_CONSTRAINED_BASE_FUNCTIONS[(self.index - 1) / 6]
+    }
+
+    /// Amount of constraints of the function in `dimensions` dimensions.
+    pub fn number_of_constraints(&self, dimensions: usize) -> usize {
+        match (self.index - 1) % 6 {
+            0 => 1,
+            1 => 2,
+            2 => 6,
+            3 => 6 + dimensions / 2,
+            4 => 6 + dimensions,
+            _ => 6 + 3 * dimensions,
+        }
+    }
+
+    /// Name of the base function with the amount of constraints it has in `BBOB_DIMENSIONS`,
+    /// e.g. `SphereWith26Constraints`.
+    pub fn name(&self) -> String {
+        match self.number_of_constraints(BBOB_DIMENSIONS) {
+            1 => format!("{}With1Constraint", self.base_function().name()),
+            constraints => format!(
+                "{}With{}Constraints",
+                self.base_function().name(),
+                constraints
+            ),
+        }
+    }
+
+    /// Global minimum of the unconstrained base function. The constraints can exclude it,
+    /// so this is only a lower bound of the minimum of the constrained function.
+    pub fn global_minimum(&self) -> f64 {
+        self.base_function().global_minimum()
+    }
+}
+
+/// A function of one of the supported suites (see `SuiteType`).
+///
+/// The function indices of the bbob and bbob-noisy suites don't overlap (1-24 and 101-130),
+/// so the index alone identifies those functions. The indices of bbob-constrained (1-54)
+/// overlap with bbob, so its functions are only looked up through `SuiteType::function`.
+#[derive(Copy, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SuiteFunction {
+    Bbob(BBOBFunctionType),
+    BbobNoisy(BBOBNoisyFunctionType),
+    BbobConstrained(BBOBConstrainedFunctionType),
+}
+
+impl From<BBOBFunctionType> for SuiteFunction {
+    fn from(function: BBOBFunctionType) -> Self {
+        SuiteFunction::Bbob(function)
+    }
+}
+
+impl SuiteFunction {
+    pub fn from_function_index(index: usize) -> Option<Self> {
+        BBOBFunctionType::from_function_index(index)
+            .map(SuiteFunction::Bbob)
+            .or_else(|| {
+                BBOBNoisyFunctionType::from_function_index(index)
+                    .map(SuiteFunction::BbobNoisy)
+            })
+    }
+
+    pub fn suite(&self) -> SuiteType {
+        match self {
+            SuiteFunction::Bbob(_) => SuiteType::Bbob,
+            SuiteFunction::BbobNoisy(_) => SuiteType::BbobNoisy,
+            SuiteFunction::BbobConstrained(_) => SuiteType::BbobConstrained,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            SuiteFunction::Bbob(function) => function.index(),
+            SuiteFunction::BbobNoisy(function) => function.index(),
+            SuiteFunction::BbobConstrained(function) => function.index(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            SuiteFunction::Bbob(function) => function.name(),
+            SuiteFunction::BbobNoisy(function) => function.name(),
+            SuiteFunction::BbobConstrained(function) => function.name(),
+        }
+    }
+
+    /// Whether the function has constraints (see `BBOBProblem::evaluate_constraints`).
+    pub fn is_constrained(&self) -> bool {
+        matches!(self, SuiteFunction::BbobConstrained(_))
+    }
+
+    pub fn global_minimum(&self) -> f64 {
+        match self {
+            SuiteFunction::Bbob(function) => function.global_minimum(),
+            SuiteFunction::BbobNoisy(function) => function.global_minimum(),
+            SuiteFunction::BbobConstrained(function) => {
+                function.global_minimum()
+            }
+        }
+    }
+
+    /// Noiseless (and unconstrained) BBOB function with the same landscape
+    /// (e.g. for looking up per-function hyperparameters).
+    pub fn base_function(&self) -> BBOBFunctionType {
+        match self {
+            SuiteFunction::Bbob(function) => *function,
+            SuiteFunction::BbobNoisy(function) => function.base_function(),
+            SuiteFunction::BbobConstrained(function) => function.base_function(),
+        }
+    }
+}
+
+/// The 10 BBOB functions the bi-objective functions of the bbob-biobj suite are combined from.
+pub const BBOB_BIOBJ_COMPONENT_FUNCTIONS: [BBOBFunctionType; 10] = [
+    BBOBFunctionType::Sphere,
+    BBOBFunctionType::SeparableEllipsoidal,
+    BBOBFunctionType::AttractiveSector,
+    BBOBFunctionType::RosenbrockFunction,
+    BBOBFunctionType::SharpRidge,
+    BBOBFunctionType::DifferentPowers,
+    BBOBFunctionType::RastriginMultiModal,
+    BBOBFunctionType::SchafferF7,
+    BBOBFunctionType::Schwefel,
+    BBOBFunctionType::GallagherGaussian101MePeaks,
+];
+
+/// Amount of functions in the bbob-biobj suite (all pairs of `BBOB_BIOBJ_COMPONENT_FUNCTIONS`).
+pub const BBOB_BIOBJ_FUNCTION_COUNT: usize = 55;
+
+/// Bi-objective function of the bbob-biobj suite (indices 1-55), which minimizes
+/// a pair of the `BBOB_BIOBJ_COMPONENT_FUNCTIONS` at the same time.
+/// See http://numbbo.github.io/coco/testsuites/bbob-biobj for more info.
+#[derive(Copy, Clone, Serialize)]
+pub struct BBOBBiobjFunctionType {
+    index: usize,
+}
+
+impl BBOBBiobjFunctionType {
+    pub fn from_function_index(index: usize) -> Option<Self> {
+        if (1..=BBOB_BIOBJ_FUNCTION_COUNT).contains(&index) {
+            Some(Self { index })
+        } else {
+            None
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        (1..=BBOB_BIOBJ_FUNCTION_COUNT)
+            .map(|index| Self { index })
+            .collect()
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// BBOB functions of the first and second objective. The functions are ordered by pairs
+    /// of `BBOB_BIOBJ_COMPONENT_FUNCTIONS`: f1 is (f1, f1), f2 is (f1, f2), ..., f10 is (f1, f21),
+    /// f11 is (f2, f2) and so on.
+    pub fn objective_functions(&self) -> [BBOBFunctionType; 2] {
+        let mut remaining_pairs = self.index - 1;
+
+        for first in 0..BBOB_BIOBJ_COMPONENT_FUNCTIONS.len() {
+            let pairs_with_first = BBOB_BIOBJ_COMPONENT_FUNCTIONS.len() - first;
+            if remaining_pairs < pairs_with_first {
+                return [
+                    BBOB_BIOBJ_COMPONENT_FUNCTIONS[first],
+                    BBOB_BIOBJ_COMPONENT_FUNCTIONS[first + remaining_pairs],
+                ];
+            }
+
+            remaining_pairs -= pairs_with_first;
+        }
+
+        unreachable!("BUG: Invalid bbob-biobj function index.");
+    }
+}
+
+/// A bi-objective function: either one of the bbob-biobj suite
+/// or any pair of (noiseless) BBOB functions.
+#[derive(Copy, Clone, Serialize)]
+pub enum BiObjectiveFunction {
+    Biobj(BBOBBiobjFunctionType),
+    Pair([BBOBFunctionType; 2]),
+}
+
+impl BiObjectiveFunction {
+    /// Index in the bbob-biobj suite (`None` for other pairs of BBOB functions).
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            BiObjectiveFunction::Biobj(function) => Some(function.index()),
+            BiObjectiveFunction::Pair(_) => None,
+        }
+    }
+
+    pub fn objective_functions(&self) -> [BBOBFunctionType; 2] {
+        match self {
+            BiObjectiveFunction::Biobj(function) => {
+                function.objective_functions()
+            }
+            BiObjectiveFunction::Pair(functions) => *functions,
+        }
+    }
+
+    /// Name of the suite the function comes from (`bbob` for other pairs of BBOB functions).
+    pub fn suite_name(&self) -> &'static str {
+        match self {
+            BiObjectiveFunction::Biobj(_) => "bbob-biobj",
+            BiObjectiveFunction::Pair(_) => "bbob",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let [first, second] = self.objective_functions();
+        format!("{}/{}", first.name(), second.name())
+    }
+}
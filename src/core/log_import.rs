@@ -0,0 +1,346 @@
+use std::fs;
+use std::path::Path;
+
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::Serialize;
+
+use crate::core::functions::{BBOBFunctionType, ALL_BBOB_FUNCTIONS};
+use crate::core::results::OptimizationResultRecord;
+use crate::core::suite::{SuiteType, BBOB_INSTANCE};
+
+/// A single progress line of a firefly log, e.g.
+/// `[   explore   | run 1/10]  4658/20000 iterations | minimum: 21.10006, distance: 0.00006`.
+#[derive(Serialize)]
+pub struct LoggedRunProgress {
+    /// Phase of the optimization (`explore` or `refine best`), `None` in older logs.
+    pub phase: Option<String>,
+
+    pub run: usize,
+    pub total_runs: usize,
+
+    pub iterations: usize,
+    pub maximum_iterations: usize,
+
+    /// Best value at the end of this run.
+    pub minimum: f64,
+}
+
+/// Everything logged about the optimization of a single problem.
+pub struct LoggedProblemResult {
+    pub function: BBOBFunctionType,
+    pub runs: Vec<LoggedRunProgress>,
+    pub wall_time_seconds: Option<f64>,
+    pub minimum: f64,
+    pub vector: Vec<f64>,
+}
+
+/// Problem block that is still being parsed.
+struct PartialProblemResult {
+    function: BBOBFunctionType,
+    runs: Vec<LoggedRunProgress>,
+    wall_time_seconds: Option<f64>,
+    minimum: Option<f64>,
+    vector: Option<Vec<f64>>,
+}
+
+impl PartialProblemResult {
+    fn new(function: BBOBFunctionType) -> Self {
+        Self {
+            function,
+            runs: Vec::new(),
+            wall_time_seconds: None,
+            minimum: None,
+            vector: None,
+        }
+    }
+
+    fn finish(self) -> Result<LoggedProblemResult> {
+        let function_name = self.function.name();
+
+        Ok(LoggedProblemResult {
+            function: self.function,
+            runs: self.runs,
+            wall_time_seconds: self.wall_time_seconds,
+            minimum: self.minimum.ok_or_else(|| {
+                miette!(
+                    "Problem block for {} has no \"Minimum:\" line.",
+                    function_name
+                )
+            })?,
+            vector: self.vector.ok_or_else(|| {
+                miette!(
+                    "Problem block for {} has no \"At:\" vector.",
+                    function_name
+                )
+            })?,
+        })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T> {
+    let value = value.trim().trim_end_matches('.');
+
+    value
+        .parse::<T>()
+        .map_err(|_| miette!("Invalid number \"{}\".", value))
+}
+
+/// Finds the BBOB function from a problem header (by name, falling back to the problem number).
+fn function_from_header(
+    problem_number: &str,
+    function_name: &str,
+) -> Result<BBOBFunctionType> {
+    let function_name = function_name.trim();
+
+    if let Some(function) = ALL_BBOB_FUNCTIONS
+        .iter()
+        .find(|function| function.name() == function_name)
+    {
+        return Ok(*function);
+    }
+
+    let function_index: usize =
+        parse_number(problem_number.split('/').next().unwrap_or_default())?;
+    BBOBFunctionType::from_function_index(function_index)
+        .ok_or_else(|| miette!("Unknown problem \"{}\".", function_name))
+}
+
+/// Parses a problem header, returning the function and the wall time, if included.
+///
+/// Supports all header styles our logs have used:
+/// - `[[Problem 01/24 (Sphere) | global minimum is 21.1000]]`,
+/// - `[Problem 01/24: Sphere]`,
+/// - `[Problem 01/24: Sphere] - 2000/2000 iterations, 6.1757 seconds`.
+fn parse_problem_header(
+    line: &str,
+) -> Result<Option<(BBOBFunctionType, Option<f64>)>> {
+    if let Some(header) = line.strip_prefix("[[Problem ") {
+        let (problem_number, rest) = header
+            .split_once(" (")
+            .ok_or_else(|| miette!("Invalid problem header."))?;
+        let (function_name, _) = rest
+            .split_once(')')
+            .ok_or_else(|| miette!("Invalid problem header."))?;
+
+        return Ok(Some((
+            function_from_header(problem_number, function_name)?,
+            None,
+        )));
+    }
+
+    if let Some(header) = line.strip_prefix("[Problem ") {
+        let (problem_number, rest) = header
+            .split_once(": ")
+            .ok_or_else(|| miette!("Invalid problem header."))?;
+        let (function_name, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| miette!("Invalid problem header."))?;
+
+        // Optional " - 2000/2000 iterations, 6.1757 seconds" suffix.
+        let wall_time_seconds = match rest.rsplit_once(", ") {
+            Some((_, seconds)) => {
+                Some(parse_number(seconds.trim_end_matches("seconds"))?)
+            }
+            None => None,
+        };
+
+        return Ok(Some((
+            function_from_header(problem_number, function_name)?,
+            wall_time_seconds,
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Parses a progress line (e.g. `[run 1/5]  4658/15000 iterations, minimum: 21.10006`).
+fn parse_run_progress(line: &str) -> Result<Option<LoggedRunProgress>> {
+    let Some(line) = line.strip_prefix('[') else {
+        return Ok(None);
+    };
+    let Some((label, rest)) = line.split_once(']') else {
+        return Ok(None);
+    };
+
+    let (phase, run_label) = match label.split_once('|') {
+        Some((phase, run_label)) => {
+            (Some(phase.trim().to_string()), run_label.trim())
+        }
+        None => (None, label.trim()),
+    };
+    let Some(run_numbers) = run_label.strip_prefix("run ") else {
+        return Ok(None);
+    };
+
+    let (run, total_runs) = run_numbers
+        .split_once('/')
+        .ok_or_else(|| miette!("Invalid run number \"{}\".", run_numbers))?;
+
+    let (iteration_numbers, rest) = rest
+        .trim()
+        .split_once(" iterations")
+        .ok_or_else(|| miette!("Missing iteration count."))?;
+    let (iterations, maximum_iterations) =
+        iteration_numbers.split_once('/').ok_or_else(|| {
+            miette!(
+                "Invalid iteration count \"{}\".",
+                iteration_numbers
+            )
+        })?;
+
+    // "minimum: 21.10006", "minimum: 21.10006, distance: ..."
+    // or (when refining) "minimum: 21.10000 -> 21.10000, distance: ...".
+    let (_, minimum) = rest
+        .split_once("minimum: ")
+        .ok_or_else(|| miette!("Missing minimum."))?;
+    let minimum = minimum.split(',').next().unwrap_or_default();
+    let minimum = minimum.rsplit("->").next().unwrap_or_default();
+
+    Ok(Some(LoggedRunProgress {
+        phase,
+        run: parse_number(run)?,
+        total_runs: parse_number(total_runs)?,
+        iterations: parse_number(iterations)?,
+        maximum_iterations: parse_number(maximum_iterations)?,
+        minimum: parse_number(minimum)?,
+    }))
+}
+
+/// Line-by-line parser of firefly optimization logs.
+#[derive(Default)]
+struct FireflyLogParser {
+    problems: Vec<LoggedProblemResult>,
+    current_problem: Option<PartialProblemResult>,
+
+    /// Text of a vector whose closing bracket hasn't been reached yet.
+    pending_vector: Option<String>,
+}
+
+impl FireflyLogParser {
+    fn parse_line(&mut self, line: &str) -> Result<()> {
+        let line = line.trim();
+
+        if let Some(vector_text) = self.pending_vector.as_mut() {
+            vector_text.push_str(line);
+        } else if let Some((function, wall_time_seconds)) =
+            parse_problem_header(line)?
+        {
+            if let Some(problem) = self.current_problem.take() {
+                self.problems.push(problem.finish()?);
+            }
+
+            let mut problem = PartialProblemResult::new(function);
+            problem.wall_time_seconds = wall_time_seconds;
+            self.current_problem = Some(problem);
+        } else if let Some(problem) = self.current_problem.as_mut() {
+            if let Some(run_progress) = parse_run_progress(line)? {
+                problem.runs.push(run_progress);
+            } else if let Some((_, seconds)) = line
+                .split_once("Optimized in ")
+                .or_else(|| line.split_once("optimized in "))
+            {
+                let seconds = seconds.trim_end_matches('.');
+                problem.wall_time_seconds =
+                    Some(parse_number(seconds.trim_end_matches("seconds"))?);
+            } else if let Some(minimum) = line.strip_prefix("Minimum: ") {
+                // Some logs lost the line break between the minimum and the vector.
+                match minimum.split_once("At: ") {
+                    Some((minimum, vector_text)) => {
+                        problem.minimum = Some(parse_number(minimum)?);
+                        self.pending_vector =
+                            Some(vector_text.trim().to_string());
+                    }
+                    None => problem.minimum = Some(parse_number(minimum)?),
+                }
+            } else if let Some(vector_text) = line.strip_prefix("At: ") {
+                self.pending_vector = Some(vector_text.trim().to_string());
+            }
+        }
+
+        let vector_complete = matches!(
+            &self.pending_vector,
+            Some(vector_text) if vector_text.ends_with(']')
+        );
+        if vector_complete {
+            let vector_text = self.pending_vector.take().unwrap_or_default();
+            let vector = serde_json::from_str::<Vec<f64>>(&vector_text)
+                .map_err(|_| miette!("Invalid vector \"{}\".", vector_text))?;
+
+            if let Some(problem) = self.current_problem.as_mut() {
+                problem.vector = Some(vector);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<LoggedProblemResult>> {
+        if self.pending_vector.is_some() {
+            return Err(miette!("Log ends inside an \"At:\" vector."));
+        }
+
+        if let Some(problem) = self.current_problem.take() {
+            self.problems.push(problem.finish()?);
+        }
+
+        Ok(self.problems)
+    }
+}
+
+/// Parses the contents of a firefly optimization log (as printed by `run-firefly-optimization`).
+///
+/// Vectors after `At:` may span multiple lines (the terminal wrapped them when the log
+/// was captured), so their lines are concatenated until the closing `]`.
+pub fn parse_firefly_log(contents: &str) -> Result<Vec<LoggedProblemResult>> {
+    let mut parser = FireflyLogParser::default();
+
+    for (line_index, line) in contents.lines().enumerate() {
+        parser.parse_line(line).wrap_err_with(|| {
+            miette!("Could not parse line {}.", line_index + 1)
+        })?;
+    }
+
+    parser.finish()
+}
+
+/// Imports a firefly optimization log as result records (algorithm `firefly`).
+///
+/// Logs contain neither the seed nor the evaluation count, so those are recorded as `0`.
+/// The logged per-run progress is kept in the `options` field (along with the log path).
+pub fn import_firefly_log(path: &Path) -> Result<Vec<OptimizationResultRecord>> {
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| miette!("Could not read {}.", path.display()))?;
+
+    let problems = parse_firefly_log(&contents)
+        .wrap_err_with(|| miette!("Could not parse log {}.", path.display()))?;
+
+    problems
+        .into_iter()
+        .map(|problem| {
+            Ok(OptimizationResultRecord {
+                suite: Some(SuiteType::Bbob),
+                function_index: problem.function.index(),
+                function_name: problem.function.name(),
+                instance: BBOB_INSTANCE,
+                dimension: problem.vector.len(),
+                algorithm: "firefly".to_string(),
+                repetition: 1,
+                options: serde_json::json!({
+                    "imported_from": path.display().to_string(),
+                    "runs": problem.runs,
+                }),
+                seed: 0,
+                best_value: problem.minimum,
+                best_vector: problem.vector,
+                evaluations: 0,
+                wall_time_seconds: problem.wall_time_seconds.unwrap_or(0f64),
+                gap_to_optimum: problem.minimum
+                    - problem.function.global_minimum(),
+                interrupted: false,
+                constraint_violation: None,
+                feasible: None,
+            })
+        })
+        .collect()
+}
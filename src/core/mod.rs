@@ -1,3 +1,10 @@
+pub mod cache;
 pub mod functions;
+pub mod log_import;
+pub mod noise;
+pub mod parallel;
+pub mod pareto;
 pub mod problem;
+pub mod results;
+pub mod solutions;
 pub mod suite;
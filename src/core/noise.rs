@@ -0,0 +1,199 @@
+use std::f64::consts::PI;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+
+/// Noiseless values below this are returned without noise (see `ProblemNoise::sample`).
+const NOISE_THRESHOLD: f64 = 1e-8;
+
+/// Noise models of the bbob-noisy suite (see "Real-Parameter Black-Box Optimization
+/// Benchmarking 2009: Noisy Functions Definitions", Hansen et al.).
+///
+/// The noise is applied to the noiseless objective value without the optimal value
+/// (so the value is never negative).
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub enum NoiseModel {
+    /// Multiplies the value by `exp(beta * N(0, 1))`.
+    Gaussian { beta: f64 },
+
+    /// Multiplies the value by `U(0, 1)^beta * max(1, (1e9 / value)^(alpha * U(0, 1)))`.
+    Uniform { alpha: f64, beta: f64 },
+
+    /// Adds `alpha * max(0, 1000 + N(0, 1) / |N(0, 1)|)`, where the Cauchy distributed outlier
+    /// (the fraction) only occurs with probability `outlier_probability`.
+    Cauchy {
+        alpha: f64,
+        outlier_probability: f64,
+    },
+}
+
+impl NoiseModel {
+    fn apply<R: Rng>(&self, value: f64, rng: &mut R) -> f64 {
+        match *self {
+            NoiseModel::Gaussian { beta } => {
+                value * (beta * sample_standard_normal(rng)).exp()
+            }
+            NoiseModel::Uniform { alpha, beta } => {
+                let scale = rng.gen::<f64>().powf(beta);
+                let boost = (1e9 / (value + 1e-99))
+                    .powf(alpha * rng.gen::<f64>())
+                    .max(1f64);
+
+                value * scale * boost
+            }
+            NoiseModel::Cauchy {
+                alpha,
+                outlier_probability,
+            } => {
+                let outlier = if rng.gen::<f64>() < outlier_probability {
+                    sample_standard_normal(rng)
+                        / (sample_standard_normal(rng).abs() + 1e-199)
+                } else {
+                    0f64
+                };
+
+                value + alpha * (1000f64 + outlier).max(0f64)
+            }
+        }
+    }
+}
+
+/// Standard normal sample (Box-Muller transform).
+fn sample_standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    // `1 - U` is in (0, 1], so the logarithm is finite.
+    let radius = (-2f64 * (1f64 - rng.gen::<f64>()).ln()).sqrt();
+    let angle = 2f64 * PI * rng.gen::<f64>();
+
+    radius * angle.cos()
+}
+
+/// Noise of a single noisy problem.
+pub struct ProblemNoise {
+    model: NoiseModel,
+
+    /// Optimal value of the noiseless function (the noise is applied to the distance from it).
+    optimal_value: f64,
+
+    seed: u64,
+
+    /// Amount of noise samples drawn so far. Each sample uses its own generator seeded with
+    /// `seed` and this counter, so the noise can be reproduced after resuming from a checkpoint.
+    samples_drawn: u64,
+}
+
+impl ProblemNoise {
+    pub fn new(model: NoiseModel, optimal_value: f64, seed: u64) -> Self {
+        Self {
+            model,
+            optimal_value,
+            seed,
+            samples_drawn: 0,
+        }
+    }
+
+    /// Draws a noisy objective value for the point with the objective value `noiseless_value`.
+    /// Like in the bbob-noisy suite, values within `1e-8` of the optimal value stay noiseless.
+    pub fn sample(&mut self, noiseless_value: f64) -> f64 {
+        let sample_seed = self.seed.wrapping_add(self.samples_drawn);
+        self.samples_drawn += 1;
+
+        let value = noiseless_value - self.optimal_value;
+        if value < NOISE_THRESHOLD {
+            return noiseless_value;
+        }
+
+        let mut rng = Pcg64Mcg::seed_from_u64(sample_seed);
+
+        self.optimal_value + self.model.apply(value, &mut rng) + 1.01e-8
+    }
+
+    /// Amount of noise samples drawn so far (saved in checkpoints, see `restore_samples_drawn`).
+    pub fn samples_drawn(&self) -> u64 {
+        self.samples_drawn
+    }
+
+    /// Restores the amount of noise samples drawn (e.g. when resuming from a checkpoint).
+    pub fn restore_samples_drawn(&mut self, samples_drawn: u64) {
+        self.samples_drawn = samples_drawn;
+    }
+}
+
+/// How many noisy samples are drawn per evaluated point and how they are combined.
+/// Every sample counts as an objective function evaluation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resampling {
+    /// A single sample.
+    #[default]
+    Single,
+
+    /// Mean of this many samples.
+    Mean(usize),
+
+    /// Median of this many samples (robust against outliers, e.g. of Cauchy noise).
+    Median(usize),
+}
+
+/// Parses `single`, `mean-<N>` and `median-<N>`.
+impl FromStr for Resampling {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value == "single" {
+            return Ok(Self::Single);
+        }
+
+        let (name, samples) = value
+            .rsplit_once('-')
+            .and_then(|(name, samples)| {
+                Some((name, samples.parse::<usize>().ok()?))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Unknown resampling \"{value}\" (expected single, mean-<N> or median-<N>)."
+                )
+            })?;
+        if samples == 0 {
+            return Err("Resampling needs at least one sample.".to_string());
+        }
+
+        match name {
+            "mean" => Ok(Self::Mean(samples)),
+            "median" => Ok(Self::Median(samples)),
+            _ => Err(format!(
+                "Unknown resampling \"{value}\" (expected single, mean-<N> or median-<N>)."
+            )),
+        }
+    }
+}
+
+impl Resampling {
+    pub fn samples(&self) -> usize {
+        match *self {
+            Resampling::Single => 1,
+            Resampling::Mean(samples) | Resampling::Median(samples) => samples,
+        }
+    }
+
+    /// Combines the noisy `samples` of a single point into its objective value.
+    pub fn combine(&self, samples: &mut [f64]) -> f64 {
+        match self {
+            Resampling::Single | Resampling::Mean(_) => {
+                samples.iter().sum::<f64>() / samples.len() as f64
+            }
+            Resampling::Median(_) => {
+                samples
+                    .sort_unstable_by(|first, second| first.total_cmp(second));
+
+                let middle = samples.len() / 2;
+                if samples.len() % 2 == 1 {
+                    samples[middle]
+                } else {
+                    (samples[middle - 1] + samples[middle]) / 2f64
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::num::NonZeroUsize;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use miette::Result;
+
+use crate::core::suite::BBOBProblemFactory;
+
+/// Buffers of a single batch sent to a worker thread and back,
+/// so they are reused by all batches instead of being allocated for each one.
+struct EvaluationJob {
+    /// Row-major points to evaluate.
+    points: Vec<f64>,
+
+    /// Objective values of the points, in the same order.
+    values: Vec<f64>,
+}
+
+/// A worker thread with its own problem handle (see `BBOBProblemFactory`).
+struct EvaluationWorker {
+    jobs: Sender<EvaluationJob>,
+
+    /// Evaluated jobs (with their `values` filled in).
+    results: Receiver<EvaluationJob>,
+
+    /// Buffers for the next job (`None` while the worker is evaluating them).
+    idle_job: Option<EvaluationJob>,
+
+    handle: JoinHandle<()>,
+}
+
+/// Evaluates batches of points of a single BBOB problem on multiple threads.
+pub struct ParallelEvaluator {
+    workers: Vec<EvaluationWorker>,
+}
+
+impl ParallelEvaluator {
+    /// Starts `threads` worker threads, each with its own handle created by `factory`.
+    pub fn new(
+        factory: BBOBProblemFactory,
+        threads: NonZeroUsize,
+    ) -> Result<Self> {
+        let workers = (0..threads.get())
+            .map(|_| {
+                let mut problem = factory.create()?;
+
+                let (job_sender, job_receiver) =
+                    mpsc::channel::<EvaluationJob>();
+                let (result_sender, result_receiver) =
+                    mpsc::channel::<EvaluationJob>();
+
+                let handle = thread::spawn(move || {
+                    problem.with_problem(|problem| {
+                        // Runs until the evaluator (and with it the job sender) is dropped.
+                        for mut job in job_receiver {
+                            job.values.clear();
+                            problem.evaluate_batch_flat_into(
+                                &job.points,
+                                &mut job.values,
+                            );
+
+                            if result_sender.send(job).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                });
+
+                Ok(EvaluationWorker {
+                    jobs: job_sender,
+                    results: result_receiver,
+                    idle_job: Some(EvaluationJob {
+                        points: Vec::new(),
+                        values: Vec::new(),
+                    }),
+                    handle,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { workers })
+    }
+
+    /// Evaluates row-major `points` (each `input_dimensions` long), split evenly
+    /// between the worker threads. Appends the objective values to `values` (in the same order).
+    pub fn evaluate_flat(
+        &mut self,
+        points: &[f64],
+        input_dimensions: usize,
+        values: &mut Vec<f64>,
+    ) {
+        let total_points = points.len() / input_dimensions;
+        let points_per_worker = total_points.div_ceil(self.workers.len()).max(1);
+
+        let mut busy_workers = 0;
+        for (worker, chunk) in self
+            .workers
+            .iter_mut()
+            .zip(points.chunks(points_per_worker * input_dimensions))
+        {
+            let mut job = worker
+                .idle_job
+                .take()
+                .expect("BUG: Evaluation worker is already busy.");
+            job.points.clear();
+            job.points.extend_from_slice(chunk);
+
+            worker
+                .jobs
+                .send(job)
+                .expect("BUG: Evaluation worker thread has exited.");
+            busy_workers += 1;
+        }
+
+        for worker in self.workers.iter_mut().take(busy_workers) {
+            let job = worker
+                .results
+                .recv()
+                .expect("BUG: Evaluation worker thread has exited.");
+
+            values.extend_from_slice(&job.values);
+            worker.idle_job = Some(job);
+        }
+    }
+}
+
+impl Drop for ParallelEvaluator {
+    fn drop(&mut self) {
+        for worker in self.workers.drain(..) {
+            // Closing the job channel stops the worker.
+            drop(worker.jobs);
+            let _ = worker.handle.join();
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+/// Objective values of a bi-objective problem (both are minimized).
+pub type ObjectiveVector = [f64; 2];
+
+/// Whether `first` Pareto-dominates `second`: it is at least as good in both objectives
+/// and strictly better in at least one of them.
+#[inline]
+pub fn dominates(first: &ObjectiveVector, second: &ObjectiveVector) -> bool {
+    first[0] <= second[0]
+        && first[1] <= second[1]
+        && (first[0] < second[0] || first[1] < second[1])
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParetoSolution {
+    pub vector: Vec<f64>,
+    pub objectives: ObjectiveVector,
+}
+
+/// Unbounded archive of all non-dominated solutions found so far.
+///
+/// The solutions are kept sorted by their first objective (ascending),
+/// so their second objective is strictly descending.
+#[derive(Default)]
+pub struct ParetoArchive {
+    solutions: Vec<ParetoSolution>,
+}
+
+impl ParetoArchive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the solution unless an archived solution dominates it (or has the same
+    /// objective values) and removes all archived solutions it dominates.
+    /// Returns whether the solution was added.
+    pub fn insert(
+        &mut self,
+        vector: &[f64],
+        objectives: ObjectiveVector,
+    ) -> bool {
+        if objectives.iter().any(|objective| objective.is_nan()) {
+            return false;
+        }
+
+        // First solution with a larger first objective.
+        let position = self
+            .solutions
+            .partition_point(|solution| solution.objectives[0] <= objectives[0]);
+
+        // The previous solution has the smallest second objective of all solutions
+        // that are at least as good in the first one.
+        let mut start = position;
+        if position > 0 {
+            let previous = &self.solutions[position - 1].objectives;
+            if previous[1] <= objectives[1] {
+                return false;
+            }
+
+            if previous[0] == objectives[0] {
+                start = position - 1;
+            }
+        }
+
+        let end = position
+            + self.solutions[position..]
+                .iter()
+                .take_while(|solution| solution.objectives[1] >= objectives[1])
+                .count();
+
+        self.solutions.splice(
+            start..end,
+            [ParetoSolution {
+                vector: vector.to_vec(),
+                objectives,
+            }],
+        );
+
+        true
+    }
+
+    /// Archived solutions, sorted by their first objective.
+    pub fn solutions(&self) -> &[ParetoSolution] {
+        &self.solutions
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    /// Best value of each objective in the archive.
+    pub fn ideal_point(&self) -> Option<ObjectiveVector> {
+        Some([
+            self.solutions.first()?.objectives[0],
+            self.solutions.last()?.objectives[1],
+        ])
+    }
+
+    /// Worst value of each objective in the archive.
+    pub fn nadir_point(&self) -> Option<ObjectiveVector> {
+        Some([
+            self.solutions.last()?.objectives[0],
+            self.solutions.first()?.objectives[1],
+        ])
+    }
+
+    /// Area dominated by the archived solutions and bounded by `reference_point`
+    /// (solutions that don't dominate the reference point don't contribute).
+    pub fn hypervolume(&self, reference_point: ObjectiveVector) -> f64 {
+        let mut hypervolume = 0f64;
+        let mut previous_second_objective = reference_point[1];
+
+        for solution in &self.solutions {
+            let [first_objective, second_objective] = solution.objectives;
+            if first_objective >= reference_point[0] {
+                break;
+            }
+            if second_objective >= previous_second_objective {
+                continue;
+            }
+
+            hypervolume += (reference_point[0] - first_objective)
+                * (previous_second_objective - second_objective);
+            previous_second_objective = second_objective;
+        }
+
+        hypervolume
+    }
+}
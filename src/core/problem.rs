@@ -1,71 +1,691 @@
+use std::num::NonZeroUsize;
+
 use coco_rs::Problem;
 use miette::{miette, Result};
 use rand::distributions::Uniform;
+use serde::{Deserialize, Serialize};
 
-use crate::core::functions::BBOBFunctionType;
+use crate::core::cache::{EvaluationCache, EvaluationCacheStatistics};
+use crate::core::functions::{
+    BBOBBiobjFunctionType,
+    BBOBFunctionType,
+    BiObjectiveFunction,
+    SuiteFunction,
+};
+use crate::core::noise::{ProblemNoise, Resampling};
+use crate::core::parallel::ParallelEvaluator;
+use crate::core::pareto::ObjectiveVector;
+use crate::core::suite::{
+    BBOBProblemFactory,
+    OwnedBBOBProblem,
+    BBOB_DIMENSIONS,
+    BBOB_INSTANCE,
+    BBOB_LARGESCALE_DIMENSIONS,
+};
 
-#[derive(Copy, Clone, PartialEq)]
+/// Bounds of the search space, given separately for each dimension.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bounds {
-    pub lower_bound: f64,
-    pub upper_bound: f64,
+    pub lower_bounds: Vec<f64>,
+    pub upper_bounds: Vec<f64>,
 }
 
 impl Bounds {
-    pub fn new(lower_bound: f64, upper_bound: f64) -> Self {
+    /// Same bounds in each of the `dimensions` dimensions.
+    pub fn uniform(
+        lower_bound: f64,
+        upper_bound: f64,
+        dimensions: usize,
+    ) -> Self {
         Self {
-            lower_bound,
-            upper_bound,
+            lower_bounds: vec![lower_bound; dimensions],
+            upper_bounds: vec![upper_bound; dimensions],
+        }
+    }
+
+    /// Separate bounds for each dimension.
+    pub fn per_dimension(
+        lower_bounds: Vec<f64>,
+        upper_bounds: Vec<f64>,
+    ) -> Result<Self> {
+        if lower_bounds.len() != upper_bounds.len() {
+            return Err(miette!(
+                "Lower and upper bounds have a different amount of dimensions!"
+            ));
+        }
+
+        if lower_bounds.iter().zip(upper_bounds.iter()).any(
+            |(lower_bound, upper_bound)| {
+                lower_bound.is_nan()
+                    || upper_bound.is_nan()
+                    || lower_bound > upper_bound
+            },
+        ) {
+            return Err(miette!("Lower bound is above the upper bound!"));
+        }
+
+        Ok(Self {
+            lower_bounds,
+            upper_bounds,
+        })
+    }
+
+    #[inline]
+    pub fn dimensions(&self) -> usize {
+        self.lower_bounds.len()
+    }
+
+    #[inline]
+    pub fn lower_bound(&self, dimension: usize) -> f64 {
+        self.lower_bounds[dimension]
+    }
+
+    #[inline]
+    pub fn upper_bound(&self, dimension: usize) -> f64 {
+        self.upper_bounds[dimension]
+    }
+
+    /// Whether `value` is inside the bounds of `dimension`.
+    #[inline]
+    pub fn contains(&self, dimension: usize, value: f64) -> bool {
+        (self.lower_bounds[dimension]..=self.upper_bounds[dimension])
+            .contains(&value)
+    }
+
+    /// Uniform distribution over the bounds of each dimension.
+    pub fn uniform_random_generators(&self) -> Vec<Uniform<f64>> {
+        self.lower_bounds
+            .iter()
+            .zip(self.upper_bounds.iter())
+            .map(|(lower_bound, upper_bound)| {
+                Uniform::new_inclusive(lower_bound, upper_bound)
+            })
+            .collect()
+    }
+}
+
+/// Type of a single variable (dimension) of a problem.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariableType {
+    Continuous,
+
+    /// Integer variable with values from `lower_bound` to `upper_bound` (inclusive).
+    Integer {
+        lower_bound: i64,
+        upper_bound: i64,
+    },
+}
+
+impl VariableType {
+    /// Closest valid value of this variable to `value`
+    /// (integer variables are rounded and clamped to their range).
+    #[inline]
+    pub fn nearest_valid_value(&self, value: f64) -> f64 {
+        match *self {
+            VariableType::Continuous => value,
+            VariableType::Integer {
+                lower_bound,
+                upper_bound,
+            } => value.round().clamp(lower_bound as f64, upper_bound as f64),
         }
     }
 
-    pub fn uniform_random_generator(&self) -> Uniform<f64> {
-        Uniform::new_inclusive(self.lower_bound, self.upper_bound)
+    /// Step of a move of this variable that is as close as possible to `step`, but still
+    /// changes its value (integer variables move by whole, non-zero steps unless `step` is 0).
+    #[inline]
+    pub fn discrete_step(&self, step: f64) -> f64 {
+        match self {
+            VariableType::Integer { .. } if step != 0f64 => {
+                step.signum() * step.abs().round().max(1f64)
+            }
+            _ => step,
+        }
     }
 }
 
 pub struct BBOBProblem<'suite> {
     problem: Problem<'suite>,
 
-    pub name: BBOBFunctionType,
+    pub name: SuiteFunction,
 
     pub input_dimensions: usize,
 
     pub bounds: Bounds,
+
+    /// Type of each variable (all continuous, except on bbob-mixint problems).
+    pub variable_types: Vec<VariableType>,
+
+    /// Whether any of the variables is an integer one.
+    mixed_integer: bool,
+
+    /// Amount of objective function evaluations performed so far.
+    evaluations: u64,
+
+    /// Output buffer of the objective function, reused by all evaluations.
+    objective_values: Vec<f64>,
+
+    /// Output buffer of the constraints (empty on unconstrained problems),
+    /// reused by all constraint evaluations.
+    constraint_values: Vec<f64>,
+
+    /// Amount of constraint evaluations (of all constraints at once) performed so far.
+    constraint_evaluations: u64,
+
+    /// Worker threads for batch evaluations, if enabled (see `set_evaluation_threads`).
+    parallel_evaluator: Option<ParallelEvaluator>,
+
+    /// Row-major points of a batch, reused by all batch evaluations on the worker threads.
+    batch_points: Vec<f64>,
+
+    /// Row-major points of a batch that are missing from the cache and their objective values,
+    /// reused by all batch evaluations on the worker threads.
+    uncached_batch_points: Vec<f64>,
+    uncached_batch_values: Vec<f64>,
+
+    /// Cache of already evaluated points, if enabled (see `set_evaluation_cache`).
+    evaluation_cache: Option<EvaluationCache>,
+
+    /// Noise added to the objective values of noisy functions.
+    noise: Option<ProblemNoise>,
+
+    /// How noisy objective values are resampled (see `set_resampling`).
+    resampling: Resampling,
 }
 
 impl<'suite> BBOBProblem<'suite> {
     pub fn from_problem_and_name(
         problem: Problem<'suite>,
-        function_name: BBOBFunctionType,
+        function_name: SuiteFunction,
         bounds: Bounds,
     ) -> Result<Self> {
         let input_dimensions = problem.dimension();
-        if input_dimensions != 40 {
-            return Err(miette!("Problem doesn't have 40 dimensions!"));
+        if input_dimensions != BBOB_DIMENSIONS
+            && !BBOB_LARGESCALE_DIMENSIONS.contains(&input_dimensions)
+        {
+            return Err(miette!(
+                "Problem doesn't have 40 (or 80, 160, 320 or 640) dimensions!"
+            ));
         }
 
         if problem.number_of_objectives() > 1 {
             return Err(miette!("Problem has more than one objective!"));
         }
 
+        if bounds.dimensions() != input_dimensions {
+            return Err(miette!(
+                "Bounds don't have {} dimensions!",
+                input_dimensions
+            ));
+        }
+
+        // Like in coco, the first variables are the integer ones
+        // and their ranges of interest are their ranges.
+        let integer_variables = problem.number_of_integer_variables();
+        let variable_types: Vec<VariableType> = problem
+            .get_ranges_of_interest()
+            .into_iter()
+            .enumerate()
+            .map(|(dimension, range)| {
+                if dimension < integer_variables {
+                    VariableType::Integer {
+                        lower_bound: range.start().round() as i64,
+                        upper_bound: range.end().round() as i64,
+                    }
+                } else {
+                    VariableType::Continuous
+                }
+            })
+            .collect();
+        let mixed_integer = integer_variables > 0;
+        let number_of_constraints = problem.number_of_constraints();
+
+        let noise = match function_name {
+            SuiteFunction::Bbob(_) | SuiteFunction::BbobConstrained(_) => None,
+            SuiteFunction::BbobNoisy(function) => Some(ProblemNoise::new(
+                function.noise_model(input_dimensions),
+                function.global_minimum(),
+                (BBOB_INSTANCE as u64) << 32 | function.index() as u64,
+            )),
+        };
+
         Ok(Self {
             problem,
             name: function_name,
             input_dimensions,
             bounds,
+            variable_types,
+            mixed_integer,
+            evaluations: 0,
+            objective_values: vec![0f64; 1],
+            constraint_values: vec![0f64; number_of_constraints],
+            constraint_evaluations: 0,
+            parallel_evaluator: None,
+            batch_points: Vec::new(),
+            uncached_batch_points: Vec::new(),
+            uncached_batch_values: Vec::new(),
+            evaluation_cache: None,
+            noise,
+            resampling: Resampling::Single,
         })
     }
 
     pub fn evaluate(&mut self, input: &[f64]) -> f64 {
+        let (noiseless_value, counted) = self.evaluate_noiseless(input);
+        self.add_noise(noiseless_value, counted)
+    }
+
+    /// Evaluates `input` without noise (using the evaluation cache, if enabled).
+    /// Also returns whether the evaluation was counted (see `cached_value`).
+    fn evaluate_noiseless(&mut self, input: &[f64]) -> (f64, bool) {
+        if let Some(objective_value) = self.cached_value(input) {
+            return (objective_value, self.counts_cache_hits());
+        }
+
         // Safety: problem.number_of_objectives() is guaranteed to be 1 on initialization.
-        let mut values = vec![0f64; 1];
+        self.problem
+            .evaluate_function(input, &mut self.objective_values);
+        self.evaluations += 1;
+
+        let objective_value = self.objective_values[0];
+        if let Some(evaluation_cache) = self.evaluation_cache.as_mut() {
+            evaluation_cache.insert(input, objective_value);
+        }
+
+        (objective_value, true)
+    }
+
+    /// Adds noise to `noiseless_value` on noisy problems (see `set_resampling`).
+    /// All samples except the first one are counted as additional evaluations,
+    /// unless the evaluation itself wasn't `counted` (an uncounted cache hit).
+    fn add_noise(&mut self, noiseless_value: f64, counted: bool) -> f64 {
+        let Some(noise) = self.noise.as_mut() else {
+            return noiseless_value;
+        };
+
+        let samples = self.resampling.samples();
+        if counted {
+            self.evaluations += samples as u64 - 1;
+        }
+
+        if samples == 1 {
+            return noise.sample(noiseless_value);
+        }
 
-        self.problem.evaluate_function(input, &mut values);
+        let mut noisy_values: Vec<f64> = (0..samples)
+            .map(|_| noise.sample(noiseless_value))
+            .collect();
+        self.resampling.combine(&mut noisy_values)
+    }
+
+    /// Evaluates all `points` and returns their objective values (in the same order).
+    /// Uses the worker threads if parallel evaluation is enabled.
+    pub fn evaluate_batch(&mut self, points: &[Vec<f64>]) -> Vec<f64> {
+        if self.parallel_evaluator.is_none() {
+            return points.iter().map(|point| self.evaluate(point)).collect();
+        }
+
+        let mut batch_points = std::mem::take(&mut self.batch_points);
+        batch_points.clear();
+        for point in points {
+            batch_points.extend_from_slice(point);
+        }
+
+        let values = self.evaluate_batch_flat(&batch_points);
+        self.batch_points = batch_points;
+
+        values
+    }
+
+    /// Evaluates row-major `points` (a matrix with one point of `input_dimensions` values per row)
+    /// and returns their objective values (in the same order).
+    /// Uses the worker threads if parallel evaluation is enabled.
+    pub fn evaluate_batch_flat(&mut self, points: &[f64]) -> Vec<f64> {
+        let mut values =
+            Vec::with_capacity(points.len() / self.input_dimensions);
+        self.evaluate_batch_flat_into(points, &mut values);
+
+        values
+    }
+
+    /// Like `evaluate_batch_flat`, but appends the objective values to `values`.
+    pub fn evaluate_batch_flat_into(
+        &mut self,
+        points: &[f64],
+        values: &mut Vec<f64>,
+    ) {
+        let rows = points.chunks_exact(self.input_dimensions);
+        assert!(
+            rows.remainder().is_empty(),
+            "Batch size is not a multiple of the input dimensions!"
+        );
+
+        if self.parallel_evaluator.is_none() {
+            values.extend(rows.map(|point| self.evaluate(point)));
+            return;
+        }
+
+        // Only the points missing from the cache are sent to the worker threads.
+        let mut cached_values: Vec<Option<f64>> =
+            rows.map(|point| self.cached_value(point)).collect();
+        let cache_hits: Vec<bool> =
+            cached_values.iter().map(|value| value.is_some()).collect();
+        let counts_cache_hits = self.counts_cache_hits();
+
+        let mut uncached_points =
+            std::mem::take(&mut self.uncached_batch_points);
+        uncached_points.clear();
+        for (point, _) in points
+            .chunks_exact(self.input_dimensions)
+            .zip(cached_values.iter())
+            .filter(|(_, value)| value.is_none())
+        {
+            uncached_points.extend_from_slice(point);
+        }
+
+        let mut uncached_values =
+            std::mem::take(&mut self.uncached_batch_values);
+        uncached_values.clear();
+        if let Some(parallel_evaluator) = self.parallel_evaluator.as_mut() {
+            if !uncached_points.is_empty() {
+                parallel_evaluator.evaluate_flat(
+                    &uncached_points,
+                    self.input_dimensions,
+                    &mut uncached_values,
+                );
+            }
+        }
+        self.evaluations += uncached_values.len() as u64;
+
+        if let Some(evaluation_cache) = self.evaluation_cache.as_mut() {
+            for (point, value) in uncached_points
+                .chunks_exact(self.input_dimensions)
+                .zip(uncached_values.iter())
+            {
+                evaluation_cache.insert(point, *value);
+            }
+        }
+
+        let mut evaluated_values = uncached_values.iter().copied();
+        for value in cached_values.iter_mut().filter(|value| value.is_none()) {
+            *value = evaluated_values.next();
+        }
+
+        self.uncached_batch_points = uncached_points;
+        self.uncached_batch_values = uncached_values;
+
+        for (value, cache_hit) in cached_values.into_iter().zip(cache_hits) {
+            let noisy_value = self.add_noise(
+                value.expect("BUG: Point was not evaluated."),
+                !cache_hit || counts_cache_hits,
+            );
+            values.push(noisy_value);
+        }
+    }
+
+    /// Amount of inequality constraints of the problem (`0` if it is unconstrained).
+    #[allow(dead_code)]
+    #[inline]
+    pub fn number_of_constraints(&self) -> usize {
+        self.constraint_values.len()
+    }
+
+    #[inline]
+    pub fn is_constrained(&self) -> bool {
+        !self.constraint_values.is_empty()
+    }
+
+    /// Evaluates all constraints at `input` and returns their values.
+    /// A constraint is satisfied if its value is zero or negative.
+    pub fn evaluate_constraints(&mut self, input: &[f64]) -> &[f64] {
+        if self.is_constrained() {
+            self.problem
+                .evaluate_constraint(input, &mut self.constraint_values);
+            self.constraint_evaluations += 1;
+        }
+
+        &self.constraint_values
+    }
+
+    /// Constraint violation at `input`: the sum of the positive constraint values
+    /// (`0` if `input` is feasible). Unconstrained problems are not evaluated.
+    pub fn constraint_violation(&mut self, input: &[f64]) -> f64 {
+        self.evaluate_constraints(input)
+            .iter()
+            .map(|value| value.max(0f64))
+            .sum()
+    }
+
+    /// Amount of constraint evaluations performed so far
+    /// (these are not counted as objective function evaluations).
+    #[allow(dead_code)]
+    pub fn constraint_evaluations(&self) -> u64 {
+        self.constraint_evaluations
+    }
+
+    /// Whether any of the variables is an integer one (see `variable_types`).
+    #[inline]
+    pub fn is_mixed_integer(&self) -> bool {
+        self.mixed_integer
+    }
+
+    /// Rounds the integer variables of `point` to their closest valid values
+    /// (see `VariableType::nearest_valid_value`). Continuous variables are not changed.
+    #[inline]
+    pub fn round_integer_variables(&self, point: &mut [f64]) {
+        if !self.mixed_integer {
+            return;
+        }
+
+        for (value, variable_type) in point.iter_mut().zip(&self.variable_types)
+        {
+            *value = variable_type.nearest_valid_value(*value);
+        }
+    }
+
+    /// Looks up `point` in the evaluation cache (if enabled),
+    /// counting the hit as an evaluation if the cache is configured so.
+    fn cached_value(&mut self, point: &[f64]) -> Option<f64> {
+        let evaluation_cache = self.evaluation_cache.as_mut()?;
+        let objective_value = evaluation_cache.get(point)?;
+
+        if evaluation_cache.counts_hits_as_evaluations() {
+            self.evaluations += 1;
+        }
+
+        Some(objective_value)
+    }
+
+    /// Whether cache hits are counted as evaluations (`true` if the cache is disabled).
+    fn counts_cache_hits(&self) -> bool {
+        match self.evaluation_cache.as_ref() {
+            Some(evaluation_cache) => {
+                evaluation_cache.counts_hits_as_evaluations()
+            }
+            None => true,
+        }
+    }
+
+    /// Enables (or, with `None`, disables) caching the objective values of evaluated points.
+    /// Affects both single and batch evaluations.
+    pub fn set_evaluation_cache(
+        &mut self,
+        evaluation_cache: Option<EvaluationCache>,
+    ) {
+        self.evaluation_cache = evaluation_cache;
+    }
+
+    /// Hit and miss counts of the evaluation cache, if enabled.
+    pub fn evaluation_cache_statistics(
+        &self,
+    ) -> Option<EvaluationCacheStatistics> {
+        self.evaluation_cache
+            .as_ref()
+            .map(|evaluation_cache| evaluation_cache.statistics())
+    }
+
+    /// Sets how many noisy samples are drawn per evaluated point and how they are combined.
+    /// Has no effect on noiseless problems.
+    pub fn set_resampling(&mut self, resampling: Resampling) {
+        self.resampling = resampling;
+    }
+
+    /// Enables evaluating batches on `threads` worker threads, each with its own problem handle
+    /// (a single thread disables parallel evaluation). Single evaluations are not affected.
+    /// The worker threads evaluate the noiseless function, noise is still added on this thread.
+    /// Not supported on constrained problems (the workers can only create unconstrained ones).
+    pub fn set_evaluation_threads(
+        &mut self,
+        threads: NonZeroUsize,
+    ) -> Result<()> {
+        if self.is_constrained() && threads.get() > 1 {
+            return Err(miette!(
+                "Parallel evaluation is not supported on constrained problems."
+            ));
+        }
+
+        self.parallel_evaluator = if threads.get() > 1 {
+            Some(ParallelEvaluator::new(
+                BBOBProblemFactory::new(self.name.base_function())
+                    .with_dimensions(self.input_dimensions)
+                    .with_mixed_integer(self.mixed_integer),
+                threads,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(())
+    }
+
+    pub fn bounds(&self) -> &Bounds {
+        &self.bounds
+    }
+
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations
+    }
+
+    /// Restores the evaluation counter (e.g. when resuming from a checkpoint).
+    pub fn restore_evaluations(&mut self, evaluations: u64) {
+        self.evaluations = evaluations;
+    }
+
+    /// Amount of noise samples drawn so far (`None` on noiseless problems).
+    pub fn noise_samples_drawn(&self) -> Option<u64> {
+        self.noise.as_ref().map(|noise| noise.samples_drawn())
+    }
+
+    /// Restores the amount of noise samples drawn (e.g. when resuming from a checkpoint).
+    /// Has no effect on noiseless problems.
+    pub fn restore_noise_samples_drawn(&mut self, samples_drawn: u64) {
+        if let Some(noise) = self.noise.as_mut() {
+            noise.restore_samples_drawn(samples_drawn);
+        }
+    }
+}
+
+/// Where the objective values of a `BiObjectiveProblem` come from.
+enum BiObjectiveEvaluator<'suite> {
+    /// A problem of the coco bbob-biobj suite.
+    Suite {
+        problem: Problem<'suite>,
+
+        /// Output buffer of the objective function, reused by all evaluations.
+        objective_values: Vec<f64>,
+    },
+
+    /// Two independent BBOB problems, evaluated at the same point.
+    Pair(Box<[OwnedBBOBProblem; 2]>),
+}
+
+/// A problem with two objectives that are minimized at the same time.
+pub struct BiObjectiveProblem<'suite> {
+    evaluator: BiObjectiveEvaluator<'suite>,
+
+    pub name: BiObjectiveFunction,
+
+    pub bounds: Bounds,
+
+    /// Amount of objective function evaluations (of both objectives at once) performed so far.
+    evaluations: u64,
+}
+
+impl<'suite> BiObjectiveProblem<'suite> {
+    pub fn from_biobj_problem(
+        problem: Problem<'suite>,
+        function: BBOBBiobjFunctionType,
+        bounds: Bounds,
+    ) -> Result<Self> {
+        let input_dimensions = problem.dimension();
+        if input_dimensions != BBOB_DIMENSIONS {
+            return Err(miette!("Problem doesn't have 40 dimensions!"));
+        }
+
+        if problem.number_of_objectives() != 2 {
+            return Err(miette!("Problem doesn't have two objectives!"));
+        }
+
+        if bounds.dimensions() != input_dimensions {
+            return Err(miette!("Bounds don't have 40 dimensions!"));
+        }
+
+        Ok(Self {
+            evaluator: BiObjectiveEvaluator::Suite {
+                problem,
+                objective_values: vec![0f64; 2],
+            },
+            name: BiObjectiveFunction::Biobj(function),
+            bounds,
+            evaluations: 0,
+        })
+    }
+
+    /// Minimizes both (noiseless) BBOB `functions` at the same time.
+    pub fn from_function_pair(functions: [BBOBFunctionType; 2]) -> Result<Self> {
+        let [first, second] = functions;
+        let mut problems = Box::new([
+            BBOBProblemFactory::new(first).create()?,
+            BBOBProblemFactory::new(second).create()?,
+        ]);
+
+        let bounds =
+            problems[0].with_problem(|problem| problem.bounds().clone());
+
+        Ok(Self {
+            evaluator: BiObjectiveEvaluator::Pair(problems),
+            name: BiObjectiveFunction::Pair(functions),
+            bounds,
+            evaluations: 0,
+        })
+    }
+
+    pub fn evaluate(&mut self, input: &[f64]) -> ObjectiveVector {
+        self.evaluations += 1;
+
+        match &mut self.evaluator {
+            BiObjectiveEvaluator::Suite {
+                problem,
+                objective_values,
+            } => {
+                // Safety: problem.number_of_objectives() is guaranteed to be 2 on initialization.
+                problem.evaluate_function(input, objective_values);
+                [objective_values[0], objective_values[1]]
+            }
+            BiObjectiveEvaluator::Pair(problems) => {
+                let [first, second] = problems.as_mut();
+                [
+                    first.with_problem(|problem| problem.evaluate(input)),
+                    second.with_problem(|problem| problem.evaluate(input)),
+                ]
+            }
+        }
+    }
 
-        values[0]
+    pub fn bounds(&self) -> &Bounds {
+        &self.bounds
     }
 
-    pub fn bounds(&self) -> Bounds {
-        self.bounds
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations
     }
 }
@@ -0,0 +1,320 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::functions::SuiteFunction;
+use crate::core::pareto::{ObjectiveVector, ParetoSolution};
+use crate::core::suite::SuiteType;
+
+/// Machine-readable result file format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ResultFormat {
+    /// Pretty-printed JSON array of result records.
+    Json,
+
+    /// CSV file with a header row. Options and vectors are stored as JSON strings.
+    Csv,
+}
+
+impl ResultFormat {
+    /// Infers the format from the file extension (`.json` or `.csv`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+
+        match extension.as_str() {
+            "json" => Some(ResultFormat::Json),
+            "csv" => Some(ResultFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Result of a single optimization of a single BBOB problem.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OptimizationResultRecord {
+    /// Suite the function is from (see `SuiteType::of_recorded_function` for older files without it).
+    #[serde(default)]
+    pub suite: Option<SuiteType>,
+
+    pub function_index: usize,
+    pub function_name: String,
+    pub instance: usize,
+    pub dimension: usize,
+
+    /// Name of the algorithm used (e.g. `firefly`).
+    pub algorithm: String,
+
+    /// Repetition number (1-based) of independent runs on the same problem.
+    #[serde(default = "default_repetition")]
+    pub repetition: usize,
+
+    /// Full set of options the algorithm was run with.
+    pub options: serde_json::Value,
+
+    /// Main random generator seed (see `--seed`).
+    pub seed: u128,
+
+    pub best_value: f64,
+    pub best_vector: Vec<f64>,
+
+    /// Amount of objective function evaluations performed.
+    pub evaluations: u64,
+
+    pub wall_time_seconds: f64,
+
+    /// Distance between the best value and the global minimum.
+    pub gap_to_optimum: f64,
+
+    /// Whether the optimization was interrupted (Ctrl-C) before finishing,
+    /// in which case the best value is the best one found up to that point.
+    #[serde(default)]
+    pub interrupted: bool,
+
+    /// Constraint violation of the best vector (sum of its positive constraint values),
+    /// only on constrained problems.
+    #[serde(default)]
+    pub constraint_violation: Option<f64>,
+
+    /// Whether the best vector satisfies all constraints, only on constrained problems.
+    #[serde(default)]
+    pub feasible: Option<bool>,
+}
+
+impl OptimizationResultRecord {
+    /// Suite the function is from (also for records written before it was recorded).
+    pub fn suite(&self) -> SuiteType {
+        SuiteType::of_recorded_function(self.suite, self.function_index)
+    }
+
+    /// The optimized function (`None` if it isn't one of the supported functions).
+    pub fn function(&self) -> Option<SuiteFunction> {
+        self.suite().function(self.function_index)
+    }
+}
+
+/// Flattened version of `OptimizationResultRecord` for the CSV format
+/// (CSV can't hold nested values, so those are stored as JSON strings).
+#[derive(Serialize, Deserialize)]
+struct CsvResultRow {
+    #[serde(default)]
+    suite: Option<SuiteType>,
+    function_index: usize,
+    function_name: String,
+    instance: usize,
+    dimension: usize,
+    algorithm: String,
+    #[serde(default = "default_repetition")]
+    repetition: usize,
+    options: String,
+    seed: u128,
+    best_value: f64,
+    best_vector: String,
+    evaluations: u64,
+    wall_time_seconds: f64,
+    gap_to_optimum: f64,
+    #[serde(default)]
+    interrupted: bool,
+    #[serde(default)]
+    constraint_violation: Option<f64>,
+    #[serde(default)]
+    feasible: Option<bool>,
+}
+
+impl TryFrom<&OptimizationResultRecord> for CsvResultRow {
+    type Error = miette::Report;
+
+    fn try_from(record: &OptimizationResultRecord) -> Result<Self> {
+        Ok(Self {
+            suite: record.suite,
+            function_index: record.function_index,
+            function_name: record.function_name.clone(),
+            instance: record.instance,
+            dimension: record.dimension,
+            algorithm: record.algorithm.clone(),
+            repetition: record.repetition,
+            options: serde_json::to_string(&record.options).into_diagnostic()?,
+            seed: record.seed,
+            best_value: record.best_value,
+            best_vector: format!(
+                "[{}]",
+                record
+                    .best_vector
+                    .iter()
+                    .map(|value| value.to_string())
+                    .join(",")
+            ),
+            evaluations: record.evaluations,
+            wall_time_seconds: record.wall_time_seconds,
+            gap_to_optimum: record.gap_to_optimum,
+            interrupted: record.interrupted,
+            constraint_violation: record.constraint_violation,
+            feasible: record.feasible,
+        })
+    }
+}
+
+impl TryFrom<CsvResultRow> for OptimizationResultRecord {
+    type Error = miette::Report;
+
+    fn try_from(row: CsvResultRow) -> Result<Self> {
+        Ok(Self {
+            suite: row.suite,
+            function_index: row.function_index,
+            function_name: row.function_name,
+            instance: row.instance,
+            dimension: row.dimension,
+            algorithm: row.algorithm,
+            repetition: row.repetition,
+            options: serde_json::from_str(&row.options).into_diagnostic()?,
+            seed: row.seed,
+            best_value: row.best_value,
+            best_vector: serde_json::from_str(&row.best_vector)
+                .into_diagnostic()?,
+            evaluations: row.evaluations,
+            wall_time_seconds: row.wall_time_seconds,
+            gap_to_optimum: row.gap_to_optimum,
+            interrupted: row.interrupted,
+            constraint_violation: row.constraint_violation,
+            feasible: row.feasible,
+        })
+    }
+}
+
+fn default_repetition() -> usize {
+    1
+}
+
+/// Converts a 16-byte random generator seed into its integer form
+/// (the same form `--seed` accepts).
+#[inline]
+pub fn seed_to_u128(seed: [u8; 16]) -> u128 {
+    u128::from_le_bytes(seed)
+}
+
+/// Writes (overwrites) `records` to `path` in the given format,
+/// creating any missing parent directories.
+pub fn write_result_records(
+    path: &Path,
+    format: ResultFormat,
+    records: &[OptimizationResultRecord],
+) -> Result<()> {
+    if let Some(parent_directory) = path.parent() {
+        if !parent_directory.as_os_str().is_empty() {
+            fs::create_dir_all(parent_directory).into_diagnostic()?;
+        }
+    }
+
+    match format {
+        ResultFormat::Json => {
+            let file = File::create(path).into_diagnostic()?;
+            serde_json::to_writer_pretty(BufWriter::new(file), records)
+                .into_diagnostic()
+        }
+        ResultFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path).into_diagnostic()?;
+
+            for record in records {
+                writer
+                    .serialize(CsvResultRow::try_from(record)?)
+                    .into_diagnostic()?;
+            }
+
+            writer.flush().into_diagnostic()
+        }
+    }
+    .wrap_err_with(|| miette!("Could not write results to {}.", path.display()))
+}
+
+/// Reads result records from `path` (as written by `write_result_records`).
+/// The format is inferred from the file extension.
+pub fn read_result_records(
+    path: &Path,
+) -> Result<Vec<OptimizationResultRecord>> {
+    let format = ResultFormat::from_path(path).ok_or_else(|| {
+        miette!(
+            "Could not infer result format from {}, expected a .json or .csv file.",
+            path.display()
+        )
+    })?;
+
+    match format {
+        ResultFormat::Json => {
+            let file = File::open(path).into_diagnostic()?;
+            serde_json::from_reader(BufReader::new(file)).into_diagnostic()
+        }
+        ResultFormat::Csv => {
+            let mut reader = csv::Reader::from_path(path).into_diagnostic()?;
+
+            reader
+                .deserialize::<CsvResultRow>()
+                .map(|row| {
+                    OptimizationResultRecord::try_from(row.into_diagnostic()?)
+                })
+                .collect()
+        }
+    }
+    .wrap_err_with(|| miette!("Could not read results from {}.", path.display()))
+}
+
+/// Result of a single bi-objective optimization (see `run-nsga2`).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParetoFrontRecord {
+    /// Index in the bbob-biobj suite (missing for other pairs of BBOB functions).
+    pub function_index: Option<usize>,
+    pub function_name: String,
+
+    /// Indices of the BBOB functions of both objectives.
+    pub objective_function_indices: [usize; 2],
+
+    pub instance: usize,
+    pub dimension: usize,
+    pub algorithm: String,
+    pub options: serde_json::Value,
+    pub seed: u128,
+
+    /// Amount of objective function evaluations (of both objectives at once) performed.
+    pub evaluations: u64,
+
+    pub wall_time_seconds: f64,
+
+    /// Hypervolume of the Pareto front, bounded by `reference_point`.
+    pub hypervolume: f64,
+    pub reference_point: ObjectiveVector,
+
+    /// All non-dominated solutions found, sorted by their first objective.
+    pub pareto_front: Vec<ParetoSolution>,
+
+    #[serde(default)]
+    pub interrupted: bool,
+}
+
+/// Writes (overwrites) `records` to `path` as a pretty-printed JSON array,
+/// creating any missing parent directories.
+pub fn write_pareto_front_records(
+    path: &Path,
+    records: &[ParetoFrontRecord],
+) -> Result<()> {
+    if let Some(parent_directory) = path.parent() {
+        if !parent_directory.as_os_str().is_empty() {
+            fs::create_dir_all(parent_directory).into_diagnostic()?;
+        }
+    }
+
+    File::create(path)
+        .into_diagnostic()
+        .and_then(|file| {
+            serde_json::to_writer_pretty(BufWriter::new(file), records)
+                .into_diagnostic()
+        })
+        .wrap_err_with(|| {
+            miette!(
+                "Could not write Pareto fronts to {}.",
+                path.display()
+            )
+        })
+}
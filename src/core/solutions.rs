@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use miette::{miette, Context, IntoDiagnostic, Result};
+
+use crate::core::functions::{SuiteFunction, ALL_BBOB_FUNCTIONS};
+use crate::core::results::{read_result_records, ResultFormat};
+use crate::core::suite::SuiteType;
+
+/// Previously found solutions (one vector per function of a single suite),
+/// used to warm-start optimization.
+pub struct SavedSolutions {
+    suite: SuiteType,
+    vectors: HashMap<usize, Vec<f64>>,
+}
+
+impl SavedSolutions {
+    /// Loads the saved solutions of functions from `suite` from `path`. Two formats are supported:
+    /// - result files (`.json` or `.csv`, see `--output`): the best vector of each function is used,
+    ///   records of other suites are skipped,
+    /// - vector files such as `results/firefly-optimization-results.txt`: one tab-separated vector
+    ///   per line, in function order (f1 to f24). Since runs append to these files, the file may
+    ///   contain multiple blocks of 24 lines - the last block is used. These only hold bbob solutions.
+    ///   Lines of that block without exactly `dimensions` fields are reported, not repaired.
+    ///
+    /// Fails if the file has no solutions from `suite`.
+    pub fn load(
+        path: &Path,
+        suite: SuiteType,
+        dimensions: usize,
+    ) -> Result<Self> {
+        let vectors = match ResultFormat::from_path(path) {
+            Some(_) => Self::load_from_result_file(path, suite)?,
+            None if suite == SuiteType::Bbob => Self::load_from_vector_file(path, dimensions)?,
+            None => {
+                return Err(miette!(
+                    "{} is a vector file with bbob solutions, which can't be used with the {} suite.",
+                    path.display(),
+                    suite.name()
+                ))
+            }
+        };
+
+        if vectors.is_empty() {
+            return Err(miette!(
+                "{} has no saved solutions from the {} suite.",
+                path.display(),
+                suite.name()
+            ));
+        }
+
+        for (function_index, vector) in &vectors {
+            if vector.len() != dimensions {
+                return Err(miette!(
+                    "Saved solution for function {} in {} has {} values, expected {}.",
+                    function_index,
+                    path.display(),
+                    vector.len(),
+                    dimensions
+                ));
+            }
+        }
+
+        Ok(Self { suite, vectors })
+    }
+
+    fn load_from_result_file(
+        path: &Path,
+        suite: SuiteType,
+    ) -> Result<HashMap<usize, Vec<f64>>> {
+        let mut best_records: HashMap<usize, (f64, Vec<f64>)> = HashMap::new();
+
+        for record in read_result_records(path)? {
+            if record.suite() != suite {
+                continue;
+            }
+
+            let is_better = best_records
+                .get(&record.function_index)
+                .map(|(best_value, _)| record.best_value < *best_value)
+                .unwrap_or(true);
+
+            if is_better {
+                best_records.insert(
+                    record.function_index,
+                    (record.best_value, record.best_vector),
+                );
+            }
+        }
+
+        Ok(best_records
+            .into_iter()
+            .map(|(function_index, (_, vector))| (function_index, vector))
+            .collect())
+    }
+
+    fn load_from_vector_file(
+        path: &Path,
+        dimensions: usize,
+    ) -> Result<HashMap<usize, Vec<f64>>> {
+        let contents = fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| miette!("Could not read {}.", path.display()))?;
+
+        // Tuples of (1-based line number, line).
+        let lines: Vec<(usize, &str)> = contents
+            .lines()
+            .enumerate()
+            .map(|(line_index, line)| (line_index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .collect();
+
+        let function_count = ALL_BBOB_FUNCTIONS.len();
+        let blocks = lines.chunks_exact(function_count);
+
+        let last_block = match blocks.remainder() {
+            [] => blocks.last(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            miette!(
+                "{} has {} vectors, expected a multiple of {} (one per function).",
+                path.display(),
+                lines.len(),
+                function_count
+            )
+        })?;
+
+        ALL_BBOB_FUNCTIONS
+            .iter()
+            .zip(last_block)
+            .map(|(function, (line_number, line))| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() != dimensions {
+                    return Err(miette!(
+                        "Line {} of {} (vector for function {}) has {} tab-separated fields, expected {}.",
+                        line_number,
+                        path.display(),
+                        function.index(),
+                        fields.len(),
+                        dimensions
+                    ));
+                }
+
+                let vector = fields
+                    .iter()
+                    .map(|value| value.trim().parse::<f64>())
+                    .collect::<Result<Vec<f64>, _>>()
+                    .into_diagnostic()
+                    .wrap_err_with(|| {
+                        miette!(
+                            "Invalid value on line {} of {} (vector for function {}).",
+                            line_number,
+                            path.display(),
+                            function.index()
+                        )
+                    })?;
+
+                Ok((function.index(), vector))
+            })
+            .collect()
+    }
+
+    /// Returns the saved solution for `function` from `suite`, if there is one
+    /// (never for suites other than the one the solutions were loaded for).
+    pub fn vector_for(
+        &self,
+        suite: SuiteType,
+        function: SuiteFunction,
+    ) -> Option<&[f64]> {
+        if suite != self.suite {
+            return None;
+        }
+
+        self.vectors.get(&function.index()).map(Vec::as_slice)
+    }
+}
@@ -1,11 +1,156 @@
+use std::mem::ManuallyDrop;
+
+use clap::ValueEnum;
 use coco_rs::{Suite, SuiteName};
 use miette::{miette, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::functions::{
+    BBOBConstrainedFunctionType,
+    BBOBFunctionType,
+    BiObjectiveFunction,
+    SuiteFunction,
+    ALL_BBOB_FUNCTIONS,
+    ALL_BBOB_NOISY_FUNCTIONS,
+};
+use crate::core::problem::{BBOBProblem, BiObjectiveProblem, Bounds};
+
+/// Dimension of all the problems in the suite (unless a larger one is chosen,
+/// see `BBOBSuite::with_dimensions`).
+pub const BBOB_DIMENSIONS: usize = 40;
+
+/// Dimensions of the problems in the bbob-largescale suite we support.
+pub const BBOB_LARGESCALE_DIMENSIONS: [usize; 4] = [80, 160, 320, 640];
+
+/// Dimensions of the problems in the bbob-mixint suite we support.
+pub const BBOB_MIXINT_DIMENSIONS: [usize; 3] = [40, 80, 160];
+
+/// BBOB instance of all the problems in the suite.
+pub const BBOB_INSTANCE: usize = 2023;
+
+/// Benchmark suite the optimized problems are taken from.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    ValueEnum,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SuiteType {
+    /// The 24 noiseless BBOB functions (f1 to f24).
+    Bbob,
+
+    /// 27 of the 30 noisy BBOB functions (f101 to f130 without the ellipsoid, f116 to f118),
+    /// approximated by adding noise to the bbob problem with the same landscape: instance
+    /// transformations are those of bbob, and the boundary penalty (zero inside [-5, 5]) is not added.
+    BbobNoisy,
+
+    /// The 24 BBOB functions with mixed-integer variables (bbob-mixint, f1 to f24).
+    BbobMixint,
+
+    /// The 54 BBOB functions with linear inequality constraints (bbob-constrained, f1 to f54).
+    BbobConstrained,
+}
+
+impl SuiteType {
+    /// Name of the suite (as in `--suite`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            SuiteType::Bbob => "bbob",
+            SuiteType::BbobNoisy => "bbob-noisy",
+            SuiteType::BbobMixint => "bbob-mixint",
+            SuiteType::BbobConstrained => "bbob-constrained",
+        }
+    }
+
+    /// Suite of a function recorded in a result, trajectory or solution file.
+    /// Files written before the suite was recorded (`suite` is `None`) only have
+    /// bbob and bbob-noisy functions, which are told apart by their indices.
+    pub fn of_recorded_function(
+        suite: Option<SuiteType>,
+        function_index: usize,
+    ) -> SuiteType {
+        suite.unwrap_or_else(|| {
+            SuiteFunction::from_function_index(function_index)
+                .map(|function| function.suite())
+                .unwrap_or(SuiteType::Bbob)
+        })
+    }
+
+    pub fn functions(&self) -> Vec<SuiteFunction> {
+        match self {
+            SuiteType::Bbob | SuiteType::BbobMixint => ALL_BBOB_FUNCTIONS
+                .iter()
+                .copied()
+                .map(SuiteFunction::Bbob)
+                .collect(),
+            SuiteType::BbobNoisy => ALL_BBOB_NOISY_FUNCTIONS
+                .iter()
+                .copied()
+                .map(SuiteFunction::BbobNoisy)
+                .collect(),
+            SuiteType::BbobConstrained => BBOBConstrainedFunctionType::all()
+                .into_iter()
+                .map(SuiteFunction::BbobConstrained)
+                .collect(),
+        }
+    }
+
+    /// Function of this suite with the given index, if there is one.
+    pub fn function(&self, index: usize) -> Option<SuiteFunction> {
+        // Constrained functions share their indices with the noiseless ones.
+        if *self == SuiteType::BbobConstrained {
+            return BBOBConstrainedFunctionType::from_function_index(index)
+                .map(SuiteFunction::BbobConstrained);
+        }
 
-use crate::core::functions::BBOBFunctionType;
-use crate::core::problem::{BBOBProblem, Bounds};
+        // Mixed-integer problems are variants of the noiseless functions.
+        let function_suite = match self {
+            SuiteType::BbobMixint => SuiteType::Bbob,
+            _ => *self,
+        };
+
+        SuiteFunction::from_function_index(index)
+            .filter(|function| function.suite() == function_suite)
+    }
+
+    /// Range of the function indices of this suite, e.g. `1-24`.
+    pub fn function_index_range(&self) -> &'static str {
+        match self {
+            SuiteType::Bbob | SuiteType::BbobMixint => "1-24",
+            SuiteType::BbobNoisy => "101-115, 119-130",
+            SuiteType::BbobConstrained => "1-54",
+        }
+    }
+
+    /// Highest function index of this suite.
+    pub fn last_function_index(&self) -> usize {
+        match self {
+            SuiteType::Bbob | SuiteType::BbobMixint => 24,
+            SuiteType::BbobNoisy => 130,
+            SuiteType::BbobConstrained => 54,
+        }
+    }
+}
 
 pub struct BBOBSuite {
     suite: Suite,
+
+    /// Dimension of all the problems in the suite.
+    dimensions: usize,
+
+    /// Whether this is the bbob-mixint suite.
+    mixed_integer: bool,
+
+    /// Whether this is the bbob-constrained suite.
+    constrained: bool,
 }
 
 impl BBOBSuite {
@@ -17,26 +162,300 @@ impl BBOBSuite {
         )
         .ok_or_else(|| miette!("Could not initialize BBOX suite!"))?;
 
-        Ok(Self { suite })
+        Ok(Self {
+            suite,
+            dimensions: BBOB_DIMENSIONS,
+            mixed_integer: false,
+            constrained: false,
+        })
     }
 
-    pub fn problem(
-        &mut self,
-        bbob_function: BBOBFunctionType,
-    ) -> Result<BBOBProblem> {
+    /// The coco bbob-largescale suite, which has the same 24 functions in larger dimensions
+    /// (see `BBOB_LARGESCALE_DIMENSIONS`). Its rotations are permuted block-diagonal matrices
+    /// instead of full ones, so the problems are not just larger versions of the bbob ones.
+    /// See http://numbbo.github.io/coco/testsuites/bbob-largescale for more info.
+    pub fn largescale(dimensions: usize) -> Result<Self> {
+        if !BBOB_LARGESCALE_DIMENSIONS.contains(&dimensions) {
+            return Err(miette!(
+                "Unsupported bbob-largescale dimension {} (expected 80, 160, 320 or 640).",
+                dimensions
+            ));
+        }
+
+        let suite = Suite::new(
+            SuiteName::BbobLargescale,
+            "instances: 2023",
+            &format!("dimensions: {dimensions}, function_indices: 1-24"),
+        )
+        .ok_or_else(|| {
+            miette!("Could not initialize BBOX large-scale suite!")
+        })?;
+
+        Ok(Self {
+            suite,
+            dimensions,
+            mixed_integer: false,
+            constrained: false,
+        })
+    }
+
+    /// The coco bbob-mixint suite, which has the same 24 functions with some integer variables:
+    /// the first four fifths of the variables are integers with 2, 4, 8 and 16 values
+    /// (a fifth of the variables each), the rest are continuous.
+    /// See http://numbbo.github.io/coco/testsuites/bbob-mixint for more info.
+    pub fn mixint(dimensions: usize) -> Result<Self> {
+        if !BBOB_MIXINT_DIMENSIONS.contains(&dimensions) {
+            return Err(miette!(
+                "Unsupported bbob-mixint dimension {} (expected 40, 80 or 160).",
+                dimensions
+            ));
+        }
+
+        let suite = Suite::new(
+            SuiteName::BbobMixint,
+            "instances: 2023",
+            &format!("dimensions: {dimensions}, function_indices: 1-24"),
+        )
+        .ok_or_else(|| {
+            miette!("Could not initialize BBOX mixed-integer suite!")
+        })?;
+
+        Ok(Self {
+            suite,
+            dimensions,
+            mixed_integer: true,
+            constrained: false,
+        })
+    }
+
+    /// The coco bbob-constrained suite (in `BBOB_DIMENSIONS`), which has 9 of the BBOB functions
+    /// with 6 different amounts of linear inequality constraints each
+    /// (see `BBOBConstrainedFunctionType`). A point is feasible if none of its constraint values
+    /// is positive (see `BBOBProblem::evaluate_constraints`).
+    /// See http://numbbo.github.io/coco/testsuites/bbob-constrained for more info.
+    pub fn constrained() -> Result<Self> {
+        let suite = Suite::new(
+            SuiteName::BbobConstrained,
+            "instances: 2023",
+            "dimensions: 40, function_indices: 1-54",
+        )
+        .ok_or_else(|| {
+            miette!("Could not initialize BBOX constrained suite!")
+        })?;
+
+        Ok(Self {
+            suite,
+            dimensions: BBOB_DIMENSIONS,
+            mixed_integer: false,
+            constrained: true,
+        })
+    }
+
+    /// The bbob suite for `BBOB_DIMENSIONS`, otherwise the bbob-largescale suite.
+    pub fn with_dimensions(dimensions: usize) -> Result<Self> {
+        if dimensions == BBOB_DIMENSIONS {
+            Self::new()
+        } else {
+            Self::largescale(dimensions)
+        }
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Creates the problem of `function`. Noisy functions are the noiseless BBOB problem
+    /// with the same landscape, with noise added to its objective values (see `ProblemNoise`).
+    ///
+    /// Mixed-integer problems are bounded by the ranges of interest of their variables.
+    pub fn problem(&mut self, function: SuiteFunction) -> Result<BBOBProblem> {
+        let function_index = match function {
+            SuiteFunction::BbobConstrained(function) if self.constrained => {
+                function.index()
+            }
+            SuiteFunction::BbobConstrained(_) => {
+                return Err(miette!(
+                    "Constrained functions are only available in the bbob-constrained suite."
+                ));
+            }
+            _ if self.constrained => {
+                return Err(miette!(
+                    "The bbob-constrained suite only has constrained functions."
+                ));
+            }
+            _ => function.base_function().index(),
+        };
+
         let raw_problem = self
             .suite
             .problem_by_function_dimension_instance(
-                bbob_function.index(),
-                40,
-                2023,
+                function_index,
+                self.dimensions,
+                BBOB_INSTANCE,
             )
             .ok_or_else(|| miette!("Could not get BBOX problem!"))?;
 
-        BBOBProblem::from_problem_and_name(
-            raw_problem,
-            bbob_function,
-            Bounds::new(-5f64, 5f64),
+        let bounds = if self.mixed_integer {
+            let (lower_bounds, upper_bounds) = raw_problem
+                .get_ranges_of_interest()
+                .into_iter()
+                .map(|range| (*range.start(), *range.end()))
+                .unzip();
+
+            Bounds::per_dimension(lower_bounds, upper_bounds)?
+        } else {
+            Bounds::uniform(-5f64, 5f64, self.dimensions)
+        };
+
+        BBOBProblem::from_problem_and_name(raw_problem, function, bounds)
+    }
+}
+
+/// The coco bbob-biobj suite (with the instance and dimensions of `BBOBSuite`).
+pub struct BBOBBiobjSuite {
+    suite: Suite,
+}
+
+impl BBOBBiobjSuite {
+    pub fn new() -> Result<Self> {
+        let suite = Suite::new(
+            SuiteName::BbobBiobj,
+            "instances: 2023",
+            "dimensions: 40, function_indices: 1-55",
         )
+        .ok_or_else(|| {
+            miette!("Could not initialize BBOX bi-objective suite!")
+        })?;
+
+        Ok(Self { suite })
+    }
+
+    /// Creates the problem of `function`. Pairs of BBOB functions don't use the suite,
+    /// each of their objectives is a separate BBOB problem (see `BBOBProblemFactory`).
+    pub fn problem(
+        &mut self,
+        function: BiObjectiveFunction,
+    ) -> Result<BiObjectiveProblem<'_>> {
+        match function {
+            BiObjectiveFunction::Biobj(biobj_function) => {
+                let raw_problem = self
+                    .suite
+                    .problem_by_function_dimension_instance(
+                        biobj_function.index(),
+                        BBOB_DIMENSIONS,
+                        BBOB_INSTANCE,
+                    )
+                    .ok_or_else(|| {
+                        miette!("Could not get BBOX bi-objective problem!")
+                    })?;
+
+                BiObjectiveProblem::from_biobj_problem(
+                    raw_problem,
+                    biobj_function,
+                    Bounds::uniform(-5f64, 5f64, BBOB_DIMENSIONS),
+                )
+            }
+            BiObjectiveFunction::Pair(functions) => {
+                BiObjectiveProblem::from_function_pair(functions)
+            }
+        }
+    }
+}
+
+/// Creates independent handles of a single BBOB problem
+/// (with the instance of the suite, see `BBOB_INSTANCE`, and `BBOB_DIMENSIONS` unless specified).
+///
+/// The factory is plain data, so it can be freely copied and shared between threads,
+/// e.g. to give each thread of a parallel optimization its own problem.
+#[derive(Copy, Clone)]
+pub struct BBOBProblemFactory {
+    pub function: BBOBFunctionType,
+
+    /// Dimension of the created problems (see `BBOBSuite::with_dimensions`).
+    pub dimensions: usize,
+
+    /// Whether the problems are taken from the bbob-mixint suite (see `BBOBSuite::mixint`).
+    pub mixed_integer: bool,
+}
+
+impl BBOBProblemFactory {
+    pub fn new(function: BBOBFunctionType) -> Self {
+        Self {
+            function,
+            dimensions: BBOB_DIMENSIONS,
+            mixed_integer: false,
+        }
+    }
+
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    pub fn with_mixed_integer(mut self, mixed_integer: bool) -> Self {
+        self.mixed_integer = mixed_integer;
+        self
+    }
+
+    /// Creates a new handle of the problem with its own suite (and evaluation counter).
+    pub fn create(&self) -> Result<OwnedBBOBProblem> {
+        let suite = Box::into_raw(Box::new(
+            if self.mixed_integer {
+                BBOBSuite::mixint(self.dimensions)?
+            } else {
+                BBOBSuite::with_dimensions(self.dimensions)?
+            },
+        ));
+
+        // SAFETY: `suite` is only freed when the handle is dropped, after the problem
+        // (see `OwnedBBOBProblem::drop`), so the problem never outlives it.
+        match unsafe { &mut *suite }.problem(self.function.into()) {
+            Ok(problem) => Ok(OwnedBBOBProblem {
+                problem: ManuallyDrop::new(problem),
+                suite,
+            }),
+            Err(error) => {
+                // SAFETY: `suite` was created by `Box::into_raw` above and is not borrowed anymore.
+                drop(unsafe { Box::from_raw(suite) });
+                Err(error)
+            }
+        }
+    }
+}
+
+/// A `BBOBProblem` that owns its suite. Unlike `BBOBProblem`, it can be moved to other threads.
+pub struct OwnedBBOBProblem {
+    /// Borrows `suite` (hence the fake `'static` lifetime), so it must be dropped first.
+    problem: ManuallyDrop<BBOBProblem<'static>>,
+
+    /// Created by `Box::into_raw`, freed on drop.
+    suite: *mut BBOBSuite,
+}
+
+// SAFETY: The suite and the problem created from it are owned exclusively by the handle
+// and coco problems don't share any state with other suites or threads.
+unsafe impl Send for OwnedBBOBProblem {}
+
+impl OwnedBBOBProblem {
+    /// Runs `operation` on the problem (e.g. an entire optimization) and returns its result.
+    ///
+    /// The problem is only lent out for the duration of `operation`,
+    /// so it can't be separated from the suite it borrows.
+    pub fn with_problem<R>(
+        &mut self,
+        operation: impl FnOnce(&mut BBOBProblem<'_>) -> R,
+    ) -> R {
+        operation(&mut self.problem)
+    }
+}
+
+impl Drop for OwnedBBOBProblem {
+    fn drop(&mut self) {
+        // SAFETY: The problem is dropped exactly once, before the suite it borrows,
+        // and `suite` was created by `Box::into_raw` in `BBOBProblemFactory::create`.
+        unsafe {
+            ManuallyDrop::drop(&mut self.problem);
+            drop(Box::from_raw(self.suite));
+        }
     }
 }
@@ -1,3 +1,4 @@
+pub mod algorithms;
+pub mod analysis;
 pub mod commands;
 pub mod core;
-pub mod algorithms;
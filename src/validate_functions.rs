@@ -237,9 +237,11 @@ fn compare_r_with_rust(r_samples: FunctionSamples) -> Result<()> {
 
     for (function_index, samples) in sorted_samples {
         let mut problem = suite.problem(
-            BBOBFunctionType::from_function_index(function_index).ok_or_else(
-                || miette!("Invalid function index! Not in 1-24 range."),
-            )?,
+            BBOBFunctionType::from_function_index(function_index)
+                .ok_or_else(|| {
+                    miette!("Invalid function index! Not in 1-24 range.")
+                })?
+                .into(),
         )?;
 
         for (sample_index, sample) in samples.iter().enumerate() {